@@ -16,6 +16,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::bookmark_collection_items::Entity")]
+    BookmarkCollectionItems,
     #[sea_orm(
         belongs_to = "super::posts::Entity",
         from = "Column::PostId",
@@ -34,6 +36,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::bookmark_collection_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BookmarkCollectionItems.def()
+    }
+}
+
 impl Related<super::posts::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Posts.def()
@@ -1,20 +0,0 @@
-//! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.6
-
-use sea_orm::entity::prelude::*;
-
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
-#[sea_orm(table_name = "following")]
-pub struct Model {
-    #[sea_orm(primary_key)]
-    pub id: i32,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
-    pub user_id: Vec<u8>,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
-    pub following_id: Vec<u8>,
-    pub created_at: DateTimeUtc,
-}
-
-#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
-
-impl ActiveModelBehavior for ActiveModel {}
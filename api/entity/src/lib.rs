@@ -2,14 +2,38 @@
 
 pub mod prelude;
 
+pub mod blocks;
+pub mod bookmark_collection_items;
+pub mod bookmark_collections;
 pub mod bookmarks;
+pub mod comment_likes;
+pub mod conversation_participants;
+pub mod conversations;
+pub mod explore_entries;
 pub mod favorites;
-pub mod followers;
-pub mod following;
+pub mod follow_requests;
+pub mod follows;
+pub mod hashtag_follows;
+pub mod hashtags;
+pub mod highlight_items;
+pub mod highlights;
+pub mod media_uploads;
+pub mod mentions;
+pub mod messages;
+pub mod mutes;
+pub mod notifications;
 pub mod post_comments;
+pub mod post_file_tags;
 pub mod post_files;
+pub mod post_hashtags;
+pub mod post_impressions;
+pub mod post_insights;
 pub mod post_likes;
+pub mod post_viewers;
 pub mod posts;
 pub mod stories;
+pub mod story_views;
+pub mod suggestion_dismissals;
+pub mod timeline_entries;
 pub mod user_links;
 pub mod users;
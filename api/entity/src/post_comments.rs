@@ -11,8 +11,7 @@ pub struct Model {
     pub user_id: Vec<u8>,
     #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))")]
     pub post_id: Vec<u8>,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", nullable)]
-    pub parent_id: Option<Vec<u8>>,
+    pub parent_id: Option<i32>,
     #[sea_orm(column_type = "Text")]
     pub comment: String,
     pub likes_count: i32,
@@ -21,6 +20,12 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::comment_likes::Entity")]
+    CommentLikes,
+    #[sea_orm(has_many = "super::mentions::Entity")]
+    Mentions,
+    #[sea_orm(has_many = "super::notifications::Entity")]
+    Notifications,
     #[sea_orm(
         belongs_to = "super::posts::Entity",
         from = "Column::PostId",
@@ -29,6 +34,40 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Posts,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    SelfRef,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
+}
+
+impl Related<super::comment_likes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CommentLikes.def()
+    }
+}
+
+impl Related<super::mentions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Mentions.def()
+    }
+}
+
+impl Related<super::notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notifications.def()
+    }
 }
 
 impl Related<super::posts::Entity> for Entity {
@@ -37,4 +76,10 @@ impl Related<super::posts::Entity> for Entity {
     }
 }
 
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -15,6 +15,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::post_file_tags::Entity")]
+    PostFileTags,
     #[sea_orm(
         belongs_to = "super::posts::Entity",
         from = "Column::PostId",
@@ -25,6 +27,12 @@ pub enum Relation {
     Posts,
 }
 
+impl Related<super::post_file_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostFileTags.def()
+    }
+}
+
 impl Related<super::posts::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Posts.def()
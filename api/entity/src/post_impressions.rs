@@ -3,14 +3,15 @@
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
-#[sea_orm(table_name = "followers")]
+#[sea_orm(table_name = "post_impressions")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
-    pub user_id: Vec<u8>,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
-    pub follower_id: Vec<u8>,
+    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))")]
+    pub post_id: Vec<u8>,
+    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", nullable)]
+    pub viewer_id: Option<Vec<u8>>,
+    pub source: String,
     pub created_at: DateTimeUtc,
 }
 
@@ -7,10 +7,11 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
+    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))")]
     pub user_id: Vec<u8>,
-    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))", unique)]
+    #[sea_orm(column_type = "Binary(BlobSize::Blob(Some(16)))")]
     pub post_id: Vec<u8>,
+    pub created_at: DateTimeUtc,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -23,6 +24,14 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Posts,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Users,
 }
 
 impl Related<super::posts::Entity> for Entity {
@@ -31,4 +40,10 @@ impl Related<super::posts::Entity> for Entity {
     }
 }
 
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
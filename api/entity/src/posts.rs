@@ -15,6 +15,8 @@ pub struct Model {
     pub user_id: Vec<u8>,
     #[sea_orm(column_type = "Text")]
     pub description: String,
+    pub likes_count: i32,
+    pub comments_enabled: bool,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
@@ -23,14 +25,28 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::bookmarks::Entity")]
     Bookmarks,
+    #[sea_orm(has_many = "super::explore_entries::Entity")]
+    ExploreEntries,
     #[sea_orm(has_many = "super::favorites::Entity")]
     Favorites,
+    #[sea_orm(has_many = "super::mentions::Entity")]
+    Mentions,
+    #[sea_orm(has_many = "super::messages::Entity")]
+    Messages,
+    #[sea_orm(has_many = "super::notifications::Entity")]
+    Notifications,
     #[sea_orm(has_many = "super::post_comments::Entity")]
     PostComments,
     #[sea_orm(has_many = "super::post_files::Entity")]
     PostFiles,
-    #[sea_orm(has_one = "super::post_likes::Entity")]
+    #[sea_orm(has_many = "super::post_hashtags::Entity")]
+    PostHashtags,
+    #[sea_orm(has_many = "super::post_insights::Entity")]
+    PostInsights,
+    #[sea_orm(has_many = "super::post_likes::Entity")]
     PostLikes,
+    #[sea_orm(has_many = "super::post_viewers::Entity")]
+    PostViewers,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -47,12 +63,36 @@ impl Related<super::bookmarks::Entity> for Entity {
     }
 }
 
+impl Related<super::explore_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExploreEntries.def()
+    }
+}
+
 impl Related<super::favorites::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Favorites.def()
     }
 }
 
+impl Related<super::mentions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Mentions.def()
+    }
+}
+
+impl Related<super::messages::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Messages.def()
+    }
+}
+
+impl Related<super::notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notifications.def()
+    }
+}
+
 impl Related<super::post_comments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PostComments.def()
@@ -65,12 +105,30 @@ impl Related<super::post_files::Entity> for Entity {
     }
 }
 
+impl Related<super::post_hashtags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostHashtags.def()
+    }
+}
+
+impl Related<super::post_insights::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostInsights.def()
+    }
+}
+
 impl Related<super::post_likes::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PostLikes.def()
     }
 }
 
+impl Related<super::post_viewers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostViewers.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
@@ -1,13 +1,37 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.6
 
+pub use super::blocks::Entity as Blocks;
+pub use super::bookmark_collection_items::Entity as BookmarkCollectionItems;
+pub use super::bookmark_collections::Entity as BookmarkCollections;
 pub use super::bookmarks::Entity as Bookmarks;
+pub use super::comment_likes::Entity as CommentLikes;
+pub use super::conversation_participants::Entity as ConversationParticipants;
+pub use super::conversations::Entity as Conversations;
+pub use super::explore_entries::Entity as ExploreEntries;
 pub use super::favorites::Entity as Favorites;
-pub use super::followers::Entity as Followers;
-pub use super::following::Entity as Following;
+pub use super::follow_requests::Entity as FollowRequests;
+pub use super::follows::Entity as Follows;
+pub use super::hashtag_follows::Entity as HashtagFollows;
+pub use super::hashtags::Entity as Hashtags;
+pub use super::highlight_items::Entity as HighlightItems;
+pub use super::highlights::Entity as Highlights;
+pub use super::media_uploads::Entity as MediaUploads;
+pub use super::mentions::Entity as Mentions;
+pub use super::messages::Entity as Messages;
+pub use super::mutes::Entity as Mutes;
+pub use super::notifications::Entity as Notifications;
 pub use super::post_comments::Entity as PostComments;
+pub use super::post_file_tags::Entity as PostFileTags;
 pub use super::post_files::Entity as PostFiles;
+pub use super::post_hashtags::Entity as PostHashtags;
+pub use super::post_impressions::Entity as PostImpressions;
+pub use super::post_insights::Entity as PostInsights;
 pub use super::post_likes::Entity as PostLikes;
+pub use super::post_viewers::Entity as PostViewers;
 pub use super::posts::Entity as Posts;
 pub use super::stories::Entity as Stories;
+pub use super::story_views::Entity as StoryViews;
+pub use super::suggestion_dismissals::Entity as SuggestionDismissals;
+pub use super::timeline_entries::Entity as TimelineEntries;
 pub use super::user_links::Entity as UserLinks;
 pub use super::users::Entity as Users;
@@ -20,6 +20,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::story_views::Entity")]
+    StoryViews,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -30,6 +32,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::story_views::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StoryViews.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
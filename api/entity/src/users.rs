@@ -22,36 +22,137 @@ pub struct Model {
     pub picture_url: String,
     #[sea_orm(column_type = "Text")]
     pub password: String,
+    pub is_private: bool,
+    pub allow_tags_from: String,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub followers_count: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::bookmark_collections::Entity")]
+    BookmarkCollections,
     #[sea_orm(has_many = "super::bookmarks::Entity")]
     Bookmarks,
+    #[sea_orm(has_many = "super::comment_likes::Entity")]
+    CommentLikes,
+    #[sea_orm(has_many = "super::conversation_participants::Entity")]
+    ConversationParticipants,
+    #[sea_orm(has_many = "super::conversations::Entity")]
+    Conversations,
     #[sea_orm(has_many = "super::favorites::Entity")]
     Favorites,
+    #[sea_orm(has_many = "super::hashtag_follows::Entity")]
+    HashtagFollows,
+    #[sea_orm(has_many = "super::highlights::Entity")]
+    Highlights,
+    #[sea_orm(has_many = "super::media_uploads::Entity")]
+    MediaUploads,
+    #[sea_orm(has_many = "super::mentions::Entity")]
+    Mentions,
+    #[sea_orm(has_many = "super::messages::Entity")]
+    Messages,
+    #[sea_orm(has_many = "super::post_comments::Entity")]
+    PostComments,
+    #[sea_orm(has_many = "super::post_file_tags::Entity")]
+    PostFileTags,
+    #[sea_orm(has_many = "super::post_likes::Entity")]
+    PostLikes,
     #[sea_orm(has_many = "super::posts::Entity")]
     Posts,
     #[sea_orm(has_many = "super::stories::Entity")]
     Stories,
+    #[sea_orm(has_many = "super::story_views::Entity")]
+    StoryViews,
     #[sea_orm(has_one = "super::user_links::Entity")]
     UserLinks,
 }
 
+impl Related<super::bookmark_collections::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BookmarkCollections.def()
+    }
+}
+
 impl Related<super::bookmarks::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Bookmarks.def()
     }
 }
 
+impl Related<super::comment_likes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CommentLikes.def()
+    }
+}
+
+impl Related<super::conversation_participants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ConversationParticipants.def()
+    }
+}
+
+impl Related<super::conversations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Conversations.def()
+    }
+}
+
 impl Related<super::favorites::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Favorites.def()
     }
 }
 
+impl Related<super::hashtag_follows::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HashtagFollows.def()
+    }
+}
+
+impl Related<super::highlights::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Highlights.def()
+    }
+}
+
+impl Related<super::media_uploads::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MediaUploads.def()
+    }
+}
+
+impl Related<super::mentions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Mentions.def()
+    }
+}
+
+impl Related<super::messages::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Messages.def()
+    }
+}
+
+impl Related<super::post_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostComments.def()
+    }
+}
+
+impl Related<super::post_file_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostFileTags.def()
+    }
+}
+
+impl Related<super::post_likes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostLikes.def()
+    }
+}
+
 impl Related<super::posts::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Posts.def()
@@ -64,6 +165,12 @@ impl Related<super::stories::Entity> for Entity {
     }
 }
 
+impl Related<super::story_views::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StoryViews.def()
+    }
+}
+
 impl Related<super::user_links::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserLinks.def()
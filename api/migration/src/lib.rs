@@ -1,6 +1,29 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20240101_000002_create_follows_table;
+mod m20240101_000003_create_follow_requests_table;
+mod m20240101_000004_create_blocks_and_mutes_tables;
+mod m20240101_000005_create_timeline_entries_table;
+mod m20240101_000006_fix_post_likes_unique_key;
+mod m20240101_000007_thread_post_comments;
+mod m20240101_000008_create_comment_likes_table;
+mod m20240101_000009_create_story_views_table;
+mod m20240101_000010_create_highlights_tables;
+mod m20240101_000011_create_bookmark_collections;
+mod m20240101_000012_add_favorites_unique_key;
+mod m20240101_000013_create_hashtags_tables;
+mod m20240101_000014_create_mentions_and_notifications_tables;
+mod m20240101_000015_create_post_file_tags_table;
+mod m20240101_000016_add_notifications_group_key;
+mod m20240101_000017_create_conversations_tables;
+mod m20240101_000018_add_search_indexes;
+mod m20240101_000019_create_explore_entries_table;
+mod m20240101_000020_create_suggestion_dismissals_table;
+mod m20240101_000021_create_post_insights_tables;
+mod m20240101_000022_add_users_followers_count;
+mod m20240101_000023_create_media_uploads_table;
+mod m20240101_000024_add_users_followers_count_index;
 
 mod tables;
 
@@ -9,6 +32,31 @@ pub struct Migrator;
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20240101_000002_create_follows_table::Migration),
+            Box::new(m20240101_000003_create_follow_requests_table::Migration),
+            Box::new(m20240101_000004_create_blocks_and_mutes_tables::Migration),
+            Box::new(m20240101_000005_create_timeline_entries_table::Migration),
+            Box::new(m20240101_000006_fix_post_likes_unique_key::Migration),
+            Box::new(m20240101_000007_thread_post_comments::Migration),
+            Box::new(m20240101_000008_create_comment_likes_table::Migration),
+            Box::new(m20240101_000009_create_story_views_table::Migration),
+            Box::new(m20240101_000010_create_highlights_tables::Migration),
+            Box::new(m20240101_000011_create_bookmark_collections::Migration),
+            Box::new(m20240101_000012_add_favorites_unique_key::Migration),
+            Box::new(m20240101_000013_create_hashtags_tables::Migration),
+            Box::new(m20240101_000014_create_mentions_and_notifications_tables::Migration),
+            Box::new(m20240101_000015_create_post_file_tags_table::Migration),
+            Box::new(m20240101_000016_add_notifications_group_key::Migration),
+            Box::new(m20240101_000017_create_conversations_tables::Migration),
+            Box::new(m20240101_000018_add_search_indexes::Migration),
+            Box::new(m20240101_000019_create_explore_entries_table::Migration),
+            Box::new(m20240101_000020_create_suggestion_dismissals_table::Migration),
+            Box::new(m20240101_000021_create_post_insights_tables::Migration),
+            Box::new(m20240101_000022_add_users_followers_count::Migration),
+            Box::new(m20240101_000023_create_media_uploads_table::Migration),
+            Box::new(m20240101_000024_add_users_followers_count_index::Migration),
+        ]
     }
 }
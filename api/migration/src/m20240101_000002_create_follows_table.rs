@@ -0,0 +1,153 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Followers, Following, Follows, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+
+        // `followers` and `following` had a unique key on each column,
+        // so a user could only ever have one follower. Both are replaced
+        // by a single `follows` table keyed on the pair
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Follows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Follows::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Follows::FollowerId).uuid().not_null())
+                    .col(ColumnDef::new(Follows::FolloweeId).uuid().not_null())
+                    .col(ColumnDef::new(Follows::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_follows_follower_followee")
+                            .col(Follows::FollowerId)
+                            .col(Follows::FolloweeId)
+                            .unique()
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_follows_followee")
+                            .col(Follows::FolloweeId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_follower_users")
+                            .from(Follows::Table, Follows::FollowerId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follows_followee_users")
+                            .from(Follows::Table, Follows::FolloweeId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Copy the existing graph over. Every edge may live in one or both
+        // of the old tables, INSERT IGNORE drops the duplicates
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "INSERT IGNORE INTO follows (follower_id, followee_id, created_at) \
+             SELECT follower_id, user_id, created_at FROM followers"
+        ).await?;
+
+        db.execute_unprepared(
+            "INSERT IGNORE INTO follows (follower_id, followee_id, created_at) \
+             SELECT user_id, following_id, created_at FROM following"
+        ).await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Followers::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Following::Table)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Followers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Followers::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Followers::UserId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Followers::FollowerId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Followers::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Following::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Following::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Following::UserId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Following::FollowingId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Following::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        // The old tables cannot hold more than one edge per user,
+        // so whatever does not fit is dropped
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "INSERT IGNORE INTO followers (user_id, follower_id, created_at) \
+             SELECT followee_id, follower_id, created_at FROM follows"
+        ).await?;
+
+        db.execute_unprepared(
+            "INSERT IGNORE INTO following (user_id, following_id, created_at) \
+             SELECT follower_id, followee_id, created_at FROM follows"
+        ).await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Follows::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{FollowRequests, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::IsPrivate).boolean().not_null().default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FollowRequests::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FollowRequests::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FollowRequests::RequesterId).uuid().not_null())
+                    .col(ColumnDef::new(FollowRequests::TargetId).uuid().not_null())
+                    .col(ColumnDef::new(FollowRequests::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_follow_requests_requester_target")
+                            .col(FollowRequests::RequesterId)
+                            .col(FollowRequests::TargetId)
+                            .unique()
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_follow_requests_target")
+                            .col(FollowRequests::TargetId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follow_requests_requester_users")
+                            .from(FollowRequests::Table, FollowRequests::RequesterId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_follow_requests_target_users")
+                            .from(FollowRequests::Table, FollowRequests::TargetId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(FollowRequests::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::IsPrivate)
+                    .to_owned(),
+            )
+            .await
+    }
+}
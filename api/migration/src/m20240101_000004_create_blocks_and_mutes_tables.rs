@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Blocks, Mutes, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Blocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Blocks::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Blocks::BlockerId).uuid().not_null())
+                    .col(ColumnDef::new(Blocks::BlockedId).uuid().not_null())
+                    .col(ColumnDef::new(Blocks::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_blocks_blocker_blocked")
+                            .col(Blocks::BlockerId)
+                            .col(Blocks::BlockedId)
+                            .unique()
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_blocks_blocked")
+                            .col(Blocks::BlockedId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_blocks_blocker_users")
+                            .from(Blocks::Table, Blocks::BlockerId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_blocks_blocked_users")
+                            .from(Blocks::Table, Blocks::BlockedId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Mutes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Mutes::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Mutes::MuterId).uuid().not_null())
+                    .col(ColumnDef::new(Mutes::MutedId).uuid().not_null())
+                    .col(ColumnDef::new(Mutes::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_mutes_muter_muted")
+                            .col(Mutes::MuterId)
+                            .col(Mutes::MutedId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mutes_muter_users")
+                            .from(Mutes::Table, Mutes::MuterId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mutes_muted_users")
+                            .from(Mutes::Table, Mutes::MutedId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Blocks::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Mutes::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Posts, TimelineEntries, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimelineEntries::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TimelineEntries::UserId).uuid().not_null())
+                    .col(ColumnDef::new(TimelineEntries::PostId).uuid().not_null())
+                    .col(ColumnDef::new(TimelineEntries::AuthorId).uuid().not_null())
+                    // Copy of the post's created_at so the timeline can be paginated without a join
+                    .col(ColumnDef::new(TimelineEntries::CreatedAt).timestamp().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(TimelineEntries::UserId)
+                            .col(TimelineEntries::PostId)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_timeline_entries_user_created")
+                            .col(TimelineEntries::UserId)
+                            .col(TimelineEntries::CreatedAt)
+                            .col(TimelineEntries::PostId)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_timeline_entries_user_author")
+                            .col(TimelineEntries::UserId)
+                            .col(TimelineEntries::AuthorId)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_timeline_entries_post")
+                            .col(TimelineEntries::PostId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_user_users")
+                            .from(TimelineEntries::Table, TimelineEntries::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_author_users")
+                            .from(TimelineEntries::Table, TimelineEntries::AuthorId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timeline_entries_posts")
+                            .from(TimelineEntries::Table, TimelineEntries::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(TimelineEntries::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
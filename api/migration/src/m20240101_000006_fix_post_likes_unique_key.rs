@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{PostLikes, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The composite key leads with post_id, so it can back fk_post_likes_posts
+        // before the old single column keys are dropped
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_post_likes_post_user")
+                    .table(PostLikes::Table)
+                    .col(PostLikes::PostId)
+                    .col(PostLikes::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // MySQL names an inline unique key after its column
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("user_id")
+                    .table(PostLikes::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("post_id")
+                    .table(PostLikes::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostLikes::Table)
+                    .add_column(ColumnDef::new(PostLikes::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_post_likes_users")
+                            .from_tbl(PostLikes::Table)
+                            .from_col(PostLikes::UserId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(ColumnDef::new(Posts::LikesCount).integer().not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE posts SET likes_count = (SELECT COUNT(*) FROM post_likes WHERE post_likes.post_id = posts.id)"
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::LikesCount)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostLikes::Table)
+                    .drop_foreign_key(Alias::new("fk_post_likes_users"))
+                    .drop_column(PostLikes::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("user_id")
+                    .table(PostLikes::Table)
+                    .col(PostLikes::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("post_id")
+                    .table(PostLikes::Table)
+                    .col(PostLikes::PostId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uq_post_likes_post_user")
+                    .table(PostLikes::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{PostComments, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // parent_id was created as a uuid while comment ids are integers,
+        // so no stored value can point to an existing comment
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE post_comments SET parent_id = NULL")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostComments::Table)
+                    .modify_column(ColumnDef::new(PostComments::ParentId).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_post_comments_parent_post_comments")
+                            .from_tbl(PostComments::Table)
+                            .from_col(PostComments::ParentId)
+                            .to_tbl(PostComments::Table)
+                            .to_col(PostComments::Id)
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_post_comments_users")
+                            .from_tbl(PostComments::Table)
+                            .from_col(PostComments::UserId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_comments_post_parent")
+                    .table(PostComments::Table)
+                    .col(PostComments::PostId)
+                    .col(PostComments::ParentId)
+                    .col(PostComments::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(ColumnDef::new(Posts::CommentsEnabled).boolean().not_null().default(true))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::CommentsEnabled)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_post_comments_post_parent")
+                    .table(PostComments::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostComments::Table)
+                    .drop_foreign_key(Alias::new("fk_post_comments_parent_post_comments"))
+                    .drop_foreign_key(Alias::new("fk_post_comments_users"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE post_comments SET parent_id = NULL")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostComments::Table)
+                    .modify_column(ColumnDef::new(PostComments::ParentId).uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
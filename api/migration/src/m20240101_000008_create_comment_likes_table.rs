@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{CommentLikes, PostComments, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentLikes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentLikes::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CommentLikes::CommentId).integer().not_null())
+                    .col(ColumnDef::new(CommentLikes::UserId).uuid().not_null())
+                    .col(ColumnDef::new(CommentLikes::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_comment_likes_comment_user")
+                            .col(CommentLikes::CommentId)
+                            .col(CommentLikes::UserId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_likes_post_comments")
+                            .from(CommentLikes::Table, CommentLikes::CommentId)
+                            .to(PostComments::Table, PostComments::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_likes_users")
+                            .from(CommentLikes::Table, CommentLikes::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Nothing backed the counter so far, start from the table
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE post_comments SET likes_count = 0")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(CommentLikes::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Stories, StoryViews, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Active stories are always looked up by author and age, and the sweeper by age alone
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stories_user_created")
+                    .table(Stories::Table)
+                    .col(Stories::UserId)
+                    .col(Stories::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stories_created")
+                    .table(Stories::Table)
+                    .col(Stories::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StoryViews::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StoryViews::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(StoryViews::StoryId).uuid().not_null())
+                    .col(ColumnDef::new(StoryViews::ViewerId).uuid().not_null())
+                    .col(ColumnDef::new(StoryViews::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_story_views_story_viewer")
+                            .col(StoryViews::StoryId)
+                            .col(StoryViews::ViewerId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_story_views_stories")
+                            .from(StoryViews::Table, StoryViews::StoryId)
+                            .to(Stories::Table, Stories::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_story_views_viewer_users")
+                            .from(StoryViews::Table, StoryViews::ViewerId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(StoryViews::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_stories_created")
+                    .table(Stories::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // fk_stories_users still needs an index on user_id
+        manager
+            .create_index(
+                Index::create()
+                    .name("fk_stories_users")
+                    .table(Stories::Table)
+                    .col(Stories::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_stories_user_created")
+                    .table(Stories::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
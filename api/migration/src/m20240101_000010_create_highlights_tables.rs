@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{HighlightItems, Highlights, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Highlights::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Highlights::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Highlights::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Highlights::Title).string_len(30).not_null())
+                    .col(ColumnDef::new(Highlights::CoverUrl).text().not_null())
+                    .col(ColumnDef::new(Highlights::Position).integer().not_null().default(0))
+                    .col(ColumnDef::new(Highlights::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .col(ColumnDef::new(Highlights::UpdatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("idx_highlights_user_position")
+                            .col(Highlights::UserId)
+                            .col(Highlights::Position)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_highlights_users")
+                            .from(Highlights::Table, Highlights::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Items copy the story's media, the story row itself is removed by the sweeper
+        // once it expires, so `story_id` is deliberately not a foreign key
+        manager
+            .create_table(
+                Table::create()
+                    .table(HighlightItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HighlightItems::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HighlightItems::HighlightId).uuid().not_null())
+                    .col(ColumnDef::new(HighlightItems::StoryId).uuid().not_null())
+                    .col(ColumnDef::new(HighlightItems::FileUrl).text().not_null())
+                    .col(ColumnDef::new(HighlightItems::StoryCreatedAt).timestamp().not_null())
+                    .col(ColumnDef::new(HighlightItems::Position).integer().not_null().default(0))
+                    .col(ColumnDef::new(HighlightItems::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_highlight_items_highlight_story")
+                            .col(HighlightItems::HighlightId)
+                            .col(HighlightItems::StoryId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_highlight_items_highlights")
+                            .from(HighlightItems::Table, HighlightItems::HighlightId)
+                            .to(Highlights::Table, Highlights::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The story sweeper looks media up by URL before deleting it,
+        // TEXT columns can only be indexed by prefix
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "CREATE INDEX idx_highlight_items_file_url ON highlight_items (file_url(255))"
+        ).await?;
+
+        db.execute_unprepared(
+            "CREATE INDEX idx_highlights_cover_url ON highlights (cover_url(255))"
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(HighlightItems::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Highlights::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
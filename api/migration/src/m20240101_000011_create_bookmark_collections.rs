@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{BookmarkCollectionItems, BookmarkCollections, Bookmarks, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keep the oldest bookmark of every duplicated (user_id, post_id) pair
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE newer FROM bookmarks newer \
+                 JOIN bookmarks older ON older.user_id = newer.user_id AND older.post_id = newer.post_id AND older.id < newer.id"
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_bookmarks_user_post")
+                    .table(Bookmarks::Table)
+                    .col(Bookmarks::UserId)
+                    .col(Bookmarks::PostId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookmarkCollections::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(BookmarkCollections::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(BookmarkCollections::UserId).uuid().not_null())
+                    .col(ColumnDef::new(BookmarkCollections::Name).string_len(50).not_null())
+                    .col(ColumnDef::new(BookmarkCollections::CoverUrl).text())
+                    .col(ColumnDef::new(BookmarkCollections::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .col(ColumnDef::new(BookmarkCollections::UpdatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bookmark_collections_users")
+                            .from(BookmarkCollections::Table, BookmarkCollections::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookmarkCollectionItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BookmarkCollectionItems::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BookmarkCollectionItems::CollectionId).uuid().not_null())
+                    .col(ColumnDef::new(BookmarkCollectionItems::BookmarkId).integer().not_null())
+                    .col(ColumnDef::new(BookmarkCollectionItems::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_bookmark_collection_items_collection_bookmark")
+                            .col(BookmarkCollectionItems::CollectionId)
+                            .col(BookmarkCollectionItems::BookmarkId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bookmark_collection_items_bookmark_collections")
+                            .from(BookmarkCollectionItems::Table, BookmarkCollectionItems::CollectionId)
+                            .to(BookmarkCollections::Table, BookmarkCollections::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bookmark_collection_items_bookmarks")
+                            .from(BookmarkCollectionItems::Table, BookmarkCollectionItems::BookmarkId)
+                            .to(Bookmarks::Table, Bookmarks::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(BookmarkCollectionItems::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(BookmarkCollections::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        // fk_bookmarks_users still needs an index on user_id
+        manager
+            .create_index(
+                Index::create()
+                    .name("fk_bookmarks_users")
+                    .table(Bookmarks::Table)
+                    .col(Bookmarks::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uq_bookmarks_user_post")
+                    .table(Bookmarks::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
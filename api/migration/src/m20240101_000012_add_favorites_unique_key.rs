@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::Favorites;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keep the oldest favorite of every duplicated (user_id, post_id) pair
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE newer FROM favorites newer \
+                 JOIN favorites older ON older.user_id = newer.user_id AND older.post_id = newer.post_id AND older.id < newer.id"
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uq_favorites_user_post")
+                    .table(Favorites::Table)
+                    .col(Favorites::UserId)
+                    .col(Favorites::PostId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // fk_favorites_users still needs an index on user_id
+        manager
+            .create_index(
+                Index::create()
+                    .name("fk_favorites_users")
+                    .table(Favorites::Table)
+                    .col(Favorites::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uq_favorites_user_post")
+                    .table(Favorites::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,148 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{HashtagFollows, Hashtags, PostHashtags, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Names are stored lowercased without the leading `#`
+        manager
+            .create_table(
+                Table::create()
+                    .table(Hashtags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Hashtags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Hashtags::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Hashtags::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_hashtags_name")
+                            .col(Hashtags::Name)
+                            .unique()
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostHashtags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostHashtags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostHashtags::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostHashtags::HashtagId).integer().not_null())
+                    .col(ColumnDef::new(PostHashtags::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_post_hashtags_post_hashtag")
+                            .col(PostHashtags::PostId)
+                            .col(PostHashtags::HashtagId)
+                            .unique()
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_post_hashtags_hashtag_post")
+                            .col(PostHashtags::HashtagId)
+                            .col(PostHashtags::PostId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_hashtags_posts")
+                            .from(PostHashtags::Table, PostHashtags::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_hashtags_hashtags")
+                            .from(PostHashtags::Table, PostHashtags::HashtagId)
+                            .to(Hashtags::Table, Hashtags::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(HashtagFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HashtagFollows::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(HashtagFollows::UserId).uuid().not_null())
+                    .col(ColumnDef::new(HashtagFollows::HashtagId).integer().not_null())
+                    .col(ColumnDef::new(HashtagFollows::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_hashtag_follows_user_hashtag")
+                            .col(HashtagFollows::UserId)
+                            .col(HashtagFollows::HashtagId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hashtag_follows_users")
+                            .from(HashtagFollows::Table, HashtagFollows::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_hashtag_follows_hashtags")
+                            .from(HashtagFollows::Table, HashtagFollows::HashtagId)
+                            .to(Hashtags::Table, Hashtags::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(HashtagFollows::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(PostHashtags::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Hashtags::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,131 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Mentions, Notifications, PostComments, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // A mention in the description of a post has no comment
+        manager
+            .create_table(
+                Table::create()
+                    .table(Mentions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Mentions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Mentions::PostId).uuid().not_null())
+                    .col(ColumnDef::new(Mentions::CommentId).integer().null())
+                    .col(ColumnDef::new(Mentions::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Mentions::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("idx_mentions_user_post")
+                            .col(Mentions::UserId)
+                            .col(Mentions::PostId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_posts")
+                            .from(Mentions::Table, Mentions::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_post_comments")
+                            .from(Mentions::Table, Mentions::CommentId)
+                            .to(PostComments::Table, PostComments::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_users")
+                            .from(Mentions::Table, Mentions::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // `user_id` receives the notification, `actor_id` caused it
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notifications::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notifications::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notifications::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Notifications::ActorId).uuid().not_null())
+                    .col(ColumnDef::new(Notifications::Kind).string_len(20).not_null())
+                    .col(ColumnDef::new(Notifications::PostId).uuid().null())
+                    .col(ColumnDef::new(Notifications::CommentId).integer().null())
+                    .col(ColumnDef::new(Notifications::ReadAt).timestamp().null())
+                    .col(ColumnDef::new(Notifications::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("idx_notifications_user_id")
+                            .col(Notifications::UserId)
+                            .col(Notifications::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_users")
+                            .from(Notifications::Table, Notifications::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_actors")
+                            .from(Notifications::Table, Notifications::ActorId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_posts")
+                            .from(Notifications::Table, Notifications::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notifications_post_comments")
+                            .from(Notifications::Table, Notifications::CommentId)
+                            .to(PostComments::Table, PostComments::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Notifications::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Mentions::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{PostFileTags, PostFiles, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One of `everyone`, `following` or `nobody`
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::AllowTagsFrom).string_len(20).not_null().default("everyone"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Positions are relative to the size of the file, from 0 to 1
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostFileTags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostFileTags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostFileTags::PostFileId).integer().not_null())
+                    .col(ColumnDef::new(PostFileTags::UserId).uuid().not_null())
+                    .col(ColumnDef::new(PostFileTags::X).double().not_null())
+                    .col(ColumnDef::new(PostFileTags::Y).double().not_null())
+                    .col(ColumnDef::new(PostFileTags::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_post_file_tags_file_user")
+                            .col(PostFileTags::PostFileId)
+                            .col(PostFileTags::UserId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_file_tags_post_files")
+                            .from(PostFileTags::Table, PostFileTags::PostFileId)
+                            .to(PostFiles::Table, PostFiles::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_file_tags_users")
+                            .from(PostFileTags::Table, PostFileTags::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(PostFileTags::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::AllowTagsFrom)
+                    .to_owned(),
+            )
+            .await
+    }
+}
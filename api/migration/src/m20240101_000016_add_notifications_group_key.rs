@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::Notifications;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Notifications sharing a key are shown as one, like the likes of a post.
+        // Rows without a key stand alone
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notifications::Table)
+                    .add_column(ColumnDef::new(Notifications::GroupKey).string_len(80).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_user_group")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::GroupKey)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_notifications_user_group")
+                    .table(Notifications::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notifications::Table)
+                    .drop_column(Notifications::GroupKey)
+                    .to_owned(),
+            )
+            .await
+    }
+}
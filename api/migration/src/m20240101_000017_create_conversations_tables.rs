@@ -0,0 +1,161 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{ConversationParticipants, Conversations, Messages, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only group conversations have a name
+        manager
+            .create_table(
+                Table::create()
+                    .table(Conversations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Conversations::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Conversations::CreatorId).uuid().not_null())
+                    .col(ColumnDef::new(Conversations::IsGroup).boolean().not_null().default(false))
+                    .col(ColumnDef::new(Conversations::Name).string_len(50).null())
+                    .col(ColumnDef::new(Conversations::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .col(ColumnDef::new(Conversations::LastMessageAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversations_users")
+                            .from(Conversations::Table, Conversations::CreatorId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // `status` is `accepted` or `request` while a non-follower's message waits for approval.
+        // `last_read_message_id` is the read cursor, messages can be deleted so it is not a foreign key
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConversationParticipants::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConversationParticipants::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ConversationParticipants::ConversationId).uuid().not_null())
+                    .col(ColumnDef::new(ConversationParticipants::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ConversationParticipants::Status).string_len(20).not_null())
+                    .col(ColumnDef::new(ConversationParticipants::LastReadMessageId).integer().null())
+                    .col(ColumnDef::new(ConversationParticipants::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("uq_conversation_participants_conversation_user")
+                            .col(ConversationParticipants::ConversationId)
+                            .col(ConversationParticipants::UserId)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_conversation_participants_user_status")
+                            .col(ConversationParticipants::UserId)
+                            .col(ConversationParticipants::Status)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_participants_conversations")
+                            .from(ConversationParticipants::Table, ConversationParticipants::ConversationId)
+                            .to(Conversations::Table, Conversations::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_participants_users")
+                            .from(ConversationParticipants::Table, ConversationParticipants::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // `kind` is `text`, `post`, `story_reply` or `media`. Stories are removed by the sweeper
+        // once they expire, so like highlight items `story_id` is not a foreign key
+        manager
+            .create_table(
+                Table::create()
+                    .table(Messages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Messages::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Messages::ConversationId).uuid().not_null())
+                    .col(ColumnDef::new(Messages::SenderId).uuid().not_null())
+                    .col(ColumnDef::new(Messages::Kind).string_len(20).not_null())
+                    .col(ColumnDef::new(Messages::Text).string_len(1000).null())
+                    .col(ColumnDef::new(Messages::PostId).uuid().null())
+                    .col(ColumnDef::new(Messages::StoryId).uuid().null())
+                    .col(ColumnDef::new(Messages::MediaUrl).text().null())
+                    .col(ColumnDef::new(Messages::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("idx_messages_conversation_id")
+                            .col(Messages::ConversationId)
+                            .col(Messages::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_messages_conversations")
+                            .from(Messages::Table, Messages::ConversationId)
+                            .to(Conversations::Table, Conversations::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_messages_users")
+                            .from(Messages::Table, Messages::SenderId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_messages_posts")
+                            .from(Messages::Table, Messages::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Messages::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(ConversationParticipants::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(Conversations::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
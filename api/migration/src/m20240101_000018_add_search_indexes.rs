@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{Hashtags, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Searched in boolean mode with prefix terms, see `GET /search`
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_search")
+                    .table(Users::Table)
+                    .col(Users::Username)
+                    .col(Users::Name)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_search")
+                    .table(Posts::Table)
+                    .col(Posts::Description)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_hashtags_search")
+                    .table(Hashtags::Table)
+                    .col(Hashtags::Name)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_hashtags_search")
+                    .table(Hashtags::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_posts_search")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_search")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{ExploreEntries, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rebuilt from scratch by the explore ranking job, never written by requests
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExploreEntries::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ExploreEntries::PostId).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ExploreEntries::AuthorId).uuid().not_null())
+                    .col(ColumnDef::new(ExploreEntries::Score).double().not_null())
+                    // Position once diversified, the page is read in this order
+                    .col(ColumnDef::new(ExploreEntries::Rank).integer().not_null())
+                    .col(
+                        ColumnDef::new(ExploreEntries::ComputedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_explore_entries_rank")
+                            .col(ExploreEntries::Rank)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_explore_entries_author")
+                            .col(ExploreEntries::AuthorId)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_explore_entries_posts")
+                            .from(ExploreEntries::Table, ExploreEntries::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_explore_entries_author_users")
+                            .from(ExploreEntries::Table, ExploreEntries::AuthorId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(ExploreEntries::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{SuggestionDismissals, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SuggestionDismissals::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SuggestionDismissals::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SuggestionDismissals::UserId).uuid().not_null())
+                    .col(ColumnDef::new(SuggestionDismissals::DismissedId).uuid().not_null())
+                    .col(ColumnDef::new(SuggestionDismissals::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("uq_suggestion_dismissals_user_dismissed")
+                            .col(SuggestionDismissals::UserId)
+                            .col(SuggestionDismissals::DismissedId)
+                            .unique()
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_suggestion_dismissals_user_users")
+                            .from(SuggestionDismissals::Table, SuggestionDismissals::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_suggestion_dismissals_dismissed_users")
+                            .from(SuggestionDismissals::Table, SuggestionDismissals::DismissedId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(SuggestionDismissals::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
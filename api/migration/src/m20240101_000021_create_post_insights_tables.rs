@@ -0,0 +1,128 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{PostImpressions, PostInsights, PostViewers, Posts, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Raw log emptied by the insights job. It has no foreign keys so that
+        // writing it never holds up a request nor the deletion of a post
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostImpressions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostImpressions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostImpressions::PostId).uuid().not_null())
+                    // `NULL` when the post was served to a visitor who isn't logged in
+                    .col(ColumnDef::new(PostImpressions::ViewerId).uuid())
+                    .col(ColumnDef::new(PostImpressions::Source).string_len(16).not_null())
+                    .col(ColumnDef::new(PostImpressions::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .index(
+                        Index::create()
+                            .name("idx_post_impressions_post")
+                            .col(PostImpressions::PostId)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Accounts a post reached, a viewer only counts towards the reach of the hour they first saw it
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostViewers::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostViewers::PostId).uuid().not_null())
+                    .col(ColumnDef::new(PostViewers::ViewerId).uuid().not_null())
+                    .col(ColumnDef::new(PostViewers::FirstSeenAt).timestamp().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(PostViewers::PostId)
+                            .col(PostViewers::ViewerId)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_post_viewers_post_first_seen")
+                            .col(PostViewers::PostId)
+                            .col(PostViewers::FirstSeenAt)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_viewers_posts")
+                            .from(PostViewers::Table, PostViewers::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_viewers_viewer_users")
+                            .from(PostViewers::Table, PostViewers::ViewerId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostInsights::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostInsights::PostId).uuid().not_null())
+                    // Start of the hour the bucket covers
+                    .col(ColumnDef::new(PostInsights::BucketStart).timestamp().not_null())
+                    .col(ColumnDef::new(PostInsights::Impressions).integer().not_null().default(0))
+                    .col(ColumnDef::new(PostInsights::Reach).integer().not_null().default(0))
+                    .primary_key(
+                        Index::create()
+                            .col(PostInsights::PostId)
+                            .col(PostInsights::BucketStart)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_insights_posts")
+                            .from(PostInsights::Table, PostInsights::PostId)
+                            .to(Posts::Table, Posts::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(PostInsights::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(PostViewers::Table)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(PostImpressions::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Kept in step with `follows` so the timeline can tell the accounts
+        // fanned out on read without counting their followers
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::FollowersCount).integer().not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE users SET followers_count = (SELECT COUNT(*) FROM follows WHERE follows.followee_id = users.id)"
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::FollowersCount)
+                    .to_owned(),
+            )
+            .await
+    }
+}
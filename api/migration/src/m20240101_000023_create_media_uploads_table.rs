@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::{MediaUploads, Users};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Who uploaded each file served from the media storage,
+        // only its uploader can use it and get it deleted
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaUploads::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaUploads::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaUploads::UserId).uuid().not_null())
+                    .col(ColumnDef::new(MediaUploads::FileUrl).string_len(255).not_null().unique_key())
+                    .col(ColumnDef::new(MediaUploads::CreatedAt).timestamp().not_null().default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_uploads_user_users")
+                            .from(MediaUploads::Table, MediaUploads::UserId)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(MediaUploads::Table)
+                    .to_owned()
+            )
+            .await
+    }
+}
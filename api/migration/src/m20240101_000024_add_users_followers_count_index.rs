@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+use crate::tables::Users;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Popular accounts are suggested by their followers count, see `GET /users/suggestions`
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_followers_count")
+                    .table(Users::Table)
+                    .col(Users::FollowersCount)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_followers_count")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -10,8 +10,11 @@ pub enum Users {
     Bio,
     PictureUrl,
     Password,
+    IsPrivate,
+    AllowTagsFrom,
     CreatedAt,
     UpdatedAt,
+    FollowersCount,
 }
 
 #[derive(DeriveIden)]
@@ -32,6 +35,7 @@ pub enum Followers {
 }
 
 #[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
 pub enum Following {
     Table,
     Id,
@@ -40,6 +44,42 @@ pub enum Following {
     CreatedAt,
 }
 
+#[derive(DeriveIden)]
+pub enum Follows {
+    Table,
+    Id,
+    FollowerId,
+    FolloweeId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum FollowRequests {
+    Table,
+    Id,
+    RequesterId,
+    TargetId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Blocks {
+    Table,
+    Id,
+    BlockerId,
+    BlockedId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Mutes {
+    Table,
+    Id,
+    MuterId,
+    MutedId,
+    CreatedAt,
+}
+
 #[derive(DeriveIden)]
 pub enum Stories {
     Table,
@@ -73,6 +113,8 @@ pub enum Posts {
     Id,
     UserId,
     Description,
+    LikesCount,
+    CommentsEnabled,
     CreatedAt,
     UpdatedAt,
 }
@@ -103,4 +145,228 @@ pub enum PostLikes {
     Id,
     PostId,
     UserId,
-}
\ No newline at end of file
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum TimelineEntries {
+    Table,
+    UserId,
+    PostId,
+    AuthorId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum CommentLikes {
+    Table,
+    Id,
+    CommentId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum StoryViews {
+    Table,
+    Id,
+    StoryId,
+    ViewerId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Highlights {
+    Table,
+    Id,
+    UserId,
+    Title,
+    CoverUrl,
+    Position,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum HighlightItems {
+    Table,
+    Id,
+    HighlightId,
+    StoryId,
+    FileUrl,
+    StoryCreatedAt,
+    Position,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum BookmarkCollections {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CoverUrl,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum BookmarkCollectionItems {
+    Table,
+    Id,
+    CollectionId,
+    BookmarkId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Hashtags {
+    Table,
+    Id,
+    Name,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PostHashtags {
+    Table,
+    Id,
+    PostId,
+    HashtagId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum HashtagFollows {
+    Table,
+    Id,
+    UserId,
+    HashtagId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Mentions {
+    Table,
+    Id,
+    PostId,
+    CommentId,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Notifications {
+    Table,
+    Id,
+    UserId,
+    ActorId,
+    Kind,
+    PostId,
+    CommentId,
+    ReadAt,
+    GroupKey,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PostFileTags {
+    Table,
+    Id,
+    PostFileId,
+    UserId,
+    X,
+    Y,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Conversations {
+    Table,
+    Id,
+    CreatorId,
+    IsGroup,
+    Name,
+    CreatedAt,
+    LastMessageAt,
+}
+
+#[derive(DeriveIden)]
+pub enum ConversationParticipants {
+    Table,
+    Id,
+    ConversationId,
+    UserId,
+    Status,
+    LastReadMessageId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Messages {
+    Table,
+    Id,
+    ConversationId,
+    SenderId,
+    Kind,
+    Text,
+    PostId,
+    StoryId,
+    MediaUrl,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum ExploreEntries {
+    Table,
+    PostId,
+    AuthorId,
+    Score,
+    Rank,
+    ComputedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum SuggestionDismissals {
+    Table,
+    Id,
+    UserId,
+    DismissedId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PostImpressions {
+    Table,
+    Id,
+    PostId,
+    ViewerId,
+    Source,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PostViewers {
+    Table,
+    PostId,
+    ViewerId,
+    FirstSeenAt,
+}
+
+#[derive(DeriveIden)]
+pub enum PostInsights {
+    Table,
+    PostId,
+    BucketStart,
+    Impressions,
+    Reach,
+}
+
+#[derive(DeriveIden)]
+pub enum MediaUploads {
+    Table,
+    Id,
+    UserId,
+    FileUrl,
+    CreatedAt,
+}
@@ -2,10 +2,20 @@ use crate::{AppState, db::connect_db};
 use actix_cors::Cors;
 use actix_web::{dev::Server, middleware, web, App, HttpResponse, HttpServer};
 use std::net::TcpListener;
+use std::sync::Arc;
 use tracing::{info, instrument};
 use tracing_actix_web::TracingLogger;
 use crate::configuration::Settings;
+use crate::comment_likes::comment_likes_worker::spawn_comment_likes_reconciliation;
+use crate::events::events_worker::spawn_event_bus;
+use crate::explore::explore_worker::spawn_explore_ranking;
+use crate::insights::insights_worker::{spawn_impressions_recorder, spawn_insights_aggregation};
+use crate::realtime::Broker;
+use crate::realtime::realtime_broker::InProcessBroker;
 use crate::routes::get_v1_routes;
+use crate::stories::stories_worker::spawn_story_sweeper;
+use crate::timeline::timeline_worker::spawn_timeline_worker;
+use crate::utils::storage::MediaStorage;
 
 async fn hello() -> HttpResponse {
     HttpResponse::Ok().json(serde_json::json!({ "code": 200 }))
@@ -19,9 +29,25 @@ pub async fn app(listener: TcpListener, config: Settings) -> Result<Server, std:
 
     info!("Server started at http://{}:{}", &config.application.host, &config.application.port);
 
+    let timeline = spawn_timeline_worker(db.clone());
+    let broker: Arc<dyn Broker> = Arc::new(InProcessBroker::default());
+    let events = spawn_event_bus(db.clone(), broker.clone());
+    spawn_comment_likes_reconciliation(db.clone());
+    spawn_explore_ranking(db.clone());
+    let impressions = spawn_impressions_recorder(db.clone());
+    spawn_insights_aggregation(db.clone());
+
+    let storage = MediaStorage::new(config.storage.clone());
+    spawn_story_sweeper(db.clone(), storage.clone());
+
     let app_state = AppState {
         db,
         config,
+        timeline,
+        events,
+        impressions,
+        broker,
+        storage,
     };
 
     let app = HttpServer::new(move || {
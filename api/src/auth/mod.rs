@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::str::FromStr;
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
 use regex::Regex;
+use uuid::Uuid;
 use crate::utils::jwt;
 
 pub mod auth_service;
@@ -46,6 +48,11 @@ impl JwtTokenPayload {
     pub fn get_exp() -> i64 {
         (Utc::now() + Duration::minutes(60)).timestamp()
     }
+
+    // The user id as it is stored in the database
+    pub fn get_user_id(&self) -> Vec<u8> {
+        Uuid::from_str(&self.id).map(Vec::from).unwrap_or_default()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
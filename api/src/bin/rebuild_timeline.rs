@@ -0,0 +1,43 @@
+//! Regenerate the materialized timeline of one or more users.
+//!
+//! Usage: `cargo run --bin rebuild_timeline -- <username>...`
+use insta::configuration::Settings;
+use insta::db::connect_db;
+use insta::timeline::timeline_service::rebuild_timeline;
+use insta::users::users_service::find_user_by_username;
+
+#[actix_web::main]
+async fn main() {
+    let usernames: Vec<String> = std::env::args().skip(1).collect();
+
+    if usernames.is_empty() {
+        eprintln!("Usage: rebuild_timeline <username>...");
+        std::process::exit(1);
+    }
+
+    let settings = Settings::get_configuration();
+    let db = connect_db(&settings)
+        .await
+        .expect("Failed while connect to DB.");
+
+    let mut failed = false;
+
+    for username in usernames {
+        let result = match find_user_by_username(&db, &username).await {
+            Ok(user) => rebuild_timeline(&db, &user.id).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(entries_count) => println!("Rebuilt the timeline of {} with {} entries", username, entries_count),
+            Err(e) => {
+                eprintln!("Failed to rebuild the timeline of {}: {:?}", username, e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,46 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::Result;
+use super::blocks_service::{block, get_blocked_users, unblock};
+
+#[get("")]
+pub async fn get_blocked_users_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let blocked_users = get_blocked_users(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": blocked_users
+        })
+    ))
+}
+
+#[post("/{target_username}")]
+pub async fn block_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, target_username) = path.into_inner();
+
+    block(&ctx.db, &ctx.timeline, &jwt_payload, &username, &target_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{target_username}")]
+pub async fn unblock_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, target_username) = path.into_inner();
+
+    unblock(&ctx.db, &jwt_payload, &username, &target_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::blocks_controller::{get_blocked_users_handler, block_handler, unblock_handler};
+
+pub fn get_blocks_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_blocked_users_handler)
+        .service(block_handler)
+        .service(unblock_handler);
+}
@@ -0,0 +1,190 @@
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, Condition, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, TransactionTrait};
+use sea_orm::sea_query::Query;
+use entity::blocks::{Entity, Column, ActiveModel, Relation};
+use entity::{follow_requests, follows};
+use crate::auth::JwtTokenPayload;
+use crate::error::HttpResponseError;
+use crate::favorites::favorites_service::delete_favorites_of_author;
+use crate::follows::Follower;
+use crate::follows::follows_service::remove_from_followers_count;
+use crate::timeline::{TimelineJob, TimelineQueue};
+use crate::timeline::timeline_service::remove_author_from_timeline;
+use crate::users::users_service::{ensure_owner, find_user_by_username};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// Blocking works both ways, it does not matter who blocked whom
+pub async fn is_blocked_between<C: ConnectionTrait>(db: &C, first_user_id: &[u8], second_user_id: &[u8]) -> Result<bool> {
+    let block = Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(Column::BlockerId.eq(first_user_id.to_vec()))
+                        .add(Column::BlockedId.eq(second_user_id.to_vec()))
+                )
+                .add(
+                    Condition::all()
+                        .add(Column::BlockerId.eq(second_user_id.to_vec()))
+                        .add(Column::BlockedId.eq(first_user_id.to_vec()))
+                )
+        )
+        .one(db)
+        .await?;
+
+    Ok(block.is_some())
+}
+
+// Used before any interaction between two users: follows, likes, comments and mentions
+pub async fn ensure_not_blocked<C: ConnectionTrait>(db: &C, first_user_id: &[u8], second_user_id: &[u8]) -> Result<()> {
+    if is_blocked_between(db, first_user_id, second_user_id).await? {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("You cannot interact with this user")
+        );
+    }
+
+    Ok(())
+}
+
+// Excludes users the viewer blocked and users who blocked the viewer
+pub fn not_blocked_condition<C: ColumnTrait>(user_col: C, viewer_id: &[u8]) -> Condition {
+    let blocked_by_viewer = Query::select()
+        .column(Column::BlockedId)
+        .from(Entity)
+        .and_where(Column::BlockerId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    let blocking_viewer = Query::select()
+        .column(Column::BlockerId)
+        .from(Entity)
+        .and_where(Column::BlockedId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    Condition::all()
+        .add(user_col.not_in_subquery(blocked_by_viewer))
+        .add(user_col.not_in_subquery(blocking_viewer))
+}
+
+pub async fn block(db: &DatabaseConnection, timeline: &TimelineQueue, jwt_payload: &JwtTokenPayload, username: &str, target_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    if username == target_username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot block yourself")
+        );
+    }
+
+    let user = find_user_by_username(db, username).await?;
+    let target = find_user_by_username(db, target_username).await?;
+
+    let txn = db.begin().await?;
+
+    let result = ActiveModel {
+        blocker_id: Set(user.id.clone()),
+        blocked_id: Set(target.id.clone()),
+        ..Default::default()
+    }.insert(&txn).await;
+
+    match result {
+        Ok(_) => {},
+        Err(e) if is_unique_violation(&e) => return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You already blocked this user")
+        ),
+        Err(e) => return Err(e.into()),
+    }
+
+    // Break every follow and pending request between the two, in both directions
+    let mut dropped_under_limit = vec![];
+
+    for (follower_id, followee_id) in [(&user.id, &target.id), (&target.id, &user.id)] {
+        let result = follows::Entity::delete_many()
+            .filter(follows::Column::FollowerId.eq(follower_id.clone()))
+            .filter(follows::Column::FolloweeId.eq(followee_id.clone()))
+            .exec(&txn)
+            .await?;
+
+        if remove_from_followers_count(&txn, followee_id, result.rows_affected).await? {
+            dropped_under_limit.push(followee_id.clone());
+        }
+    }
+
+    follow_requests::Entity::delete_many()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(follow_requests::Column::RequesterId.eq(user.id.clone()))
+                        .add(follow_requests::Column::TargetId.eq(target.id.clone()))
+                )
+                .add(
+                    Condition::all()
+                        .add(follow_requests::Column::RequesterId.eq(target.id.clone()))
+                        .add(follow_requests::Column::TargetId.eq(user.id.clone()))
+                )
+        )
+        .exec(&txn)
+        .await?;
+
+    // Neither can keep the other's posts among their favorites
+    delete_favorites_of_author(&txn, &user.id, &target.id).await?;
+    delete_favorites_of_author(&txn, &target.id, &user.id).await?;
+
+    remove_author_from_timeline(&txn, &user.id, &target.id).await?;
+    remove_author_from_timeline(&txn, &target.id, &user.id).await?;
+
+    txn.commit().await?;
+
+    for author_id in dropped_under_limit {
+        timeline.enqueue(TimelineJob::FanOutAuthor { author_id });
+    }
+
+    Ok(())
+}
+
+pub async fn unblock(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, target_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let target = find_user_by_username(db, target_username).await?;
+
+    let result = Entity::delete_many()
+        .filter(Column::BlockerId.eq(user.id))
+        .filter(Column::BlockedId.eq(target.id))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You have not blocked this user")
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn get_blocked_users(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<Vec<Follower>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let blocked_users = entity::users::Entity::find()
+        .join(JoinType::InnerJoin, Relation::Blocked.def().rev())
+        .filter(Column::BlockerId.eq(user.id))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Follower::from)
+        .collect();
+
+    Ok(blocked_users)
+}
@@ -0,0 +1,3 @@
+pub mod blocks_service;
+pub mod blocks_controller;
+pub mod blocks_routes;
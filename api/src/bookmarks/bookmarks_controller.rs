@@ -0,0 +1,145 @@
+use actix_web::{get, post, patch, put, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::bookmarks::{BookmarksFilter, CreateBookmarkCollectionPayload, CreateBookmarkPayload, UpdateBookmarkCollectionPayload};
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::bookmarks_service::{add_to_collection, create_bookmark, create_bookmark_collection, delete_bookmark, delete_bookmark_collection, get_bookmark_collections, get_bookmarks, remove_from_collection, update_bookmark_collection};
+
+#[get("")]
+pub async fn get_bookmarks_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>, filter: Query<BookmarksFilter>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+    let filter = filter.into_inner();
+
+    validate_data(&query)?;
+    validate_data(&filter)?;
+
+    let page = get_bookmarks(&ctx.db, &jwt_payload, &username, &query, &filter).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.posts,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("")]
+pub async fn create_bookmark_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateBookmarkPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    create_bookmark(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16()
+        })
+    ))
+}
+
+#[get("/collections")]
+pub async fn get_bookmark_collections_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let collections = get_bookmark_collections(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": collections
+        })
+    ))
+}
+
+#[post("/collections")]
+pub async fn create_bookmark_collection_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateBookmarkCollectionPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let collection = create_bookmark_collection(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": collection
+        })
+    ))
+}
+
+#[patch("/collections/{collection_id}")]
+pub async fn update_bookmark_collection_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<UpdateBookmarkCollectionPayload>) -> Result<HttpResponse> {
+    let (username, collection_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let collection = update_bookmark_collection(&ctx.db, &ctx.storage, &jwt_payload, &username, &collection_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": collection
+        })
+    ))
+}
+
+#[delete("/collections/{collection_id}")]
+pub async fn delete_bookmark_collection_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, collection_id) = path.into_inner();
+
+    delete_bookmark_collection(&ctx.db, &ctx.storage, &jwt_payload, &username, &collection_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[put("/collections/{collection_id}/posts/{post_id}")]
+pub async fn add_to_collection_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, collection_id, post_id) = path.into_inner();
+
+    add_to_collection(&ctx.db, &jwt_payload, &username, &collection_id, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/collections/{collection_id}/posts/{post_id}")]
+pub async fn remove_from_collection_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, collection_id, post_id) = path.into_inner();
+
+    remove_from_collection(&ctx.db, &jwt_payload, &username, &collection_id, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{post_id}")]
+pub async fn delete_bookmark_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    delete_bookmark(&ctx.db, &jwt_payload, &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
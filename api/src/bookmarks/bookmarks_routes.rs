@@ -0,0 +1,15 @@
+use actix_web::web::ServiceConfig;
+
+use super::bookmarks_controller::{add_to_collection_handler, create_bookmark_collection_handler, create_bookmark_handler, delete_bookmark_collection_handler, delete_bookmark_handler, get_bookmark_collections_handler, get_bookmarks_handler, remove_from_collection_handler, update_bookmark_collection_handler};
+
+pub fn get_bookmarks_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_bookmarks_handler)
+        .service(create_bookmark_handler)
+        .service(get_bookmark_collections_handler)
+        .service(create_bookmark_collection_handler)
+        .service(update_bookmark_collection_handler)
+        .service(delete_bookmark_collection_handler)
+        .service(add_to_collection_handler)
+        .service(remove_from_collection_handler)
+        .service(delete_bookmark_handler);
+}
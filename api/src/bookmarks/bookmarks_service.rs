@@ -0,0 +1,522 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use chrono::Utc;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Statement, TransactionTrait, Value};
+use sea_orm::sea_query::{Expr, Query};
+use uuid::Uuid;
+use entity::bookmarks::{Entity, Column, Model, ActiveModel};
+use entity::bookmark_collections;
+use entity::{bookmark_collection_items, post_files, posts};
+use crate::auth::JwtTokenPayload;
+use crate::bookmarks::{BookmarkCollectionDetail, BookmarksFilter, BookmarksPage, CreateBookmarkCollectionPayload, CreateBookmarkPayload, UpdateBookmarkCollectionPayload};
+use crate::error::HttpResponseError;
+use crate::media::media_service::{delete_unreferenced_uploads, is_uploaded_by};
+use crate::posts::posts_service::{build_post_details, find_visible_post_by_id};
+use crate::users::users_service::{ensure_owner, find_user_by_username, visible_authors_condition};
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::utils::is_unique_violation;
+use crate::utils::storage::MediaStorage;
+use crate::Result;
+
+const BOOKMARK_NOT_FOUND_ERROR: &str = "Bookmark not found";
+const COLLECTION_NOT_FOUND_ERROR: &str = "Bookmark collection not found";
+
+fn bookmark_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(BOOKMARK_NOT_FOUND_ERROR)
+}
+
+fn collection_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(COLLECTION_NOT_FOUND_ERROR)
+}
+
+fn bad_request(message: &'static str) -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::BAD_REQUEST.as_u16())
+        .set_error_message(message)
+}
+
+pub async fn find_user_collection<C: ConnectionTrait>(db: &C, user_id: &[u8], collection_id: &str) -> Result<bookmark_collections::Model> {
+    let collection_id = Uuid::from_str(collection_id).map_err(|_| collection_not_found())?;
+
+    let collection = bookmark_collections::Entity::find_by_id(collection_id)
+        .filter(bookmark_collections::Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?;
+
+    collection.ok_or_else(collection_not_found)
+}
+
+// Every collection has to belong to the user, a single unknown id fails the whole request
+async fn find_user_collection_ids<C: ConnectionTrait>(db: &C, user_id: &[u8], collection_ids: &[String]) -> Result<Vec<Vec<u8>>> {
+    let collection_ids: HashSet<Vec<u8>> = collection_ids
+        .iter()
+        .map(|collection_id| Uuid::from_str(collection_id).map(Vec::from).map_err(|_| collection_not_found()))
+        .collect::<Result<_>>()?;
+
+    if collection_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let found: Vec<Vec<u8>> = bookmark_collections::Entity::find()
+        .select_only()
+        .column(bookmark_collections::Column::Id)
+        .filter(bookmark_collections::Column::Id.is_in(collection_ids.clone()))
+        .filter(bookmark_collections::Column::UserId.eq(user_id.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    if found.len() != collection_ids.len() {
+        return Err(collection_not_found());
+    }
+
+    Ok(found)
+}
+
+async fn find_user_bookmark<C: ConnectionTrait>(db: &C, user_id: &[u8], post_id: &str) -> Result<Model> {
+    let post_id = Uuid::from_str(post_id).map_err(|_| bookmark_not_found())?;
+
+    let bookmark = Entity::find()
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .filter(Column::PostId.eq(Vec::from(post_id)))
+        .one(db)
+        .await?;
+
+    bookmark.ok_or_else(bookmark_not_found)
+}
+
+// Bookmarking a post twice keeps the first bookmark
+async fn insert_bookmark<C: ConnectionTrait>(db: &C, user_id: &[u8], post_id: &[u8]) -> Result<Model> {
+    let result = ActiveModel {
+        user_id: Set(user_id.to_vec()),
+        post_id: Set(post_id.to_vec()),
+        ..Default::default()
+    }.insert(db).await;
+
+    match result {
+        Ok(bookmark) => Ok(bookmark),
+        Err(e) if is_unique_violation(&e) => {
+            let bookmark = Entity::find()
+                .filter(Column::UserId.eq(user_id.to_vec()))
+                .filter(Column::PostId.eq(post_id.to_vec()))
+                .one(db)
+                .await?;
+
+            bookmark.ok_or_else(bookmark_not_found)
+        },
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn add_to_collections<C: ConnectionTrait>(db: &C, bookmark_id: i32, collection_ids: Vec<Vec<u8>>) -> Result<()> {
+    if collection_ids.is_empty() {
+        return Ok(());
+    }
+
+    // A bookmark already saved to a collection stays where it is
+    let placeholders = vec!["(?, ?)"; collection_ids.len()].join(", ");
+    let values: Vec<Value> = collection_ids
+        .iter()
+        .flat_map(|collection_id| [collection_id.clone().into(), bookmark_id.into()])
+        .collect();
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!("INSERT IGNORE INTO bookmark_collection_items (collection_id, bookmark_id) VALUES {}", placeholders),
+        values,
+    )).await?;
+
+    touch_collections(db, collection_ids).await
+}
+
+async fn touch_collections<C: ConnectionTrait>(db: &C, collection_ids: Vec<Vec<u8>>) -> Result<()> {
+    bookmark_collections::Entity::update_many()
+        .col_expr(bookmark_collections::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(bookmark_collections::Column::Id.is_in(collection_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_bookmarks_rows<C: ConnectionTrait>(db: &C, bookmark_ids: Vec<i32>) -> Result<()> {
+    bookmark_collection_items::Entity::delete_many()
+        .filter(bookmark_collection_items::Column::BookmarkId.is_in(bookmark_ids.clone()))
+        .exec(db)
+        .await?;
+
+    Entity::delete_many()
+        .filter(Column::Id.is_in(bookmark_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Removes every bookmark of a post together with its collection entries
+pub async fn delete_post_bookmarks<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    let bookmark_ids: Vec<i32> = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    if bookmark_ids.is_empty() {
+        return Ok(());
+    }
+
+    delete_bookmarks_rows(db, bookmark_ids).await
+}
+
+pub async fn create_bookmark(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: CreateBookmarkPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let post = find_visible_post_by_id(db, &user.id, &data.post_id.unwrap()).await?;
+    let collection_ids = find_user_collection_ids(db, &user.id, &data.collection_ids.unwrap_or_default()).await?;
+
+    let txn = db.begin().await?;
+
+    let bookmark = insert_bookmark(&txn, &user.id, &post.id).await?;
+    add_to_collections(&txn, bookmark.id, collection_ids).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+// Newest bookmarks first. Posts whose author the user can no longer see are left out
+pub async fn get_bookmarks(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, query: &CursorQuery, filter: &BookmarksFilter) -> Result<BookmarksPage> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let collection = match filter.collection_id.as_deref() {
+        Some(collection_id) => Some(find_user_collection(db, &user.id, collection_id).await?),
+        None => None,
+    };
+
+    let limit = query.get_limit();
+    let cursor = query.get_id_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut bookmarks = Entity::find()
+        .find_also_related(posts::Entity)
+        .filter(Column::UserId.eq(user.id.clone()))
+        .filter(visible_authors_condition(posts::Column::UserId, Some(&user.id)))
+        .apply_if(collection, |query, collection| {
+            let saved = Query::select()
+                .column(bookmark_collection_items::Column::BookmarkId)
+                .from(bookmark_collection_items::Entity)
+                .and_where(bookmark_collection_items::Column::CollectionId.eq(collection.id))
+                .to_owned();
+
+            query.filter(Column::Id.in_subquery(saved))
+        })
+        .apply_if(cursor, |query, cursor| query.filter(Column::Id.lt(cursor)))
+        .order_by_desc(Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if bookmarks.len() as u64 > limit {
+        bookmarks.truncate(limit as usize);
+        bookmarks.last().map(|(bookmark, _)| encode_id_cursor(bookmark.id))
+    } else {
+        None
+    };
+
+    let posts = bookmarks.into_iter().filter_map(|(_, post)| post).collect();
+
+    Ok(BookmarksPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor,
+    })
+}
+
+// Bookmarks are addressed by post id, which is what clients know about
+pub async fn delete_bookmark(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let bookmark = find_user_bookmark(db, &user.id, post_id).await?;
+
+    let txn = db.begin().await?;
+    delete_bookmarks_rows(&txn, vec![bookmark.id]).await?;
+    txn.commit().await?;
+
+    Ok(())
+}
+
+// Without an explicit cover a collection shows the first file of its latest saved post
+async fn default_covers<C: ConnectionTrait>(db: &C, collection_ids: Vec<Vec<u8>>) -> Result<HashMap<Vec<u8>, String>> {
+    let latest_item_ids: Vec<i32> = bookmark_collection_items::Entity::find()
+        .select_only()
+        .column_as(bookmark_collection_items::Column::Id.max(), "latest_item_id")
+        .filter(bookmark_collection_items::Column::CollectionId.is_in(collection_ids))
+        .group_by(bookmark_collection_items::Column::CollectionId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    if latest_item_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let latest_posts: Vec<(Vec<u8>, Vec<u8>)> = bookmark_collection_items::Entity::find()
+        .find_also_related(Entity)
+        .filter(bookmark_collection_items::Column::Id.is_in(latest_item_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(item, bookmark)| Some((item.collection_id, bookmark?.post_id)))
+        .collect();
+
+    let mut first_files: HashMap<Vec<u8>, String> = HashMap::new();
+    post_files::Entity::find()
+        .filter(post_files::Column::PostId.is_in(latest_posts.iter().map(|(_, post_id)| post_id.clone())))
+        .order_by_asc(post_files::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .for_each(|file| {
+            first_files.entry(file.post_id).or_insert(file.file_url);
+        });
+
+    let covers = latest_posts
+        .into_iter()
+        .filter_map(|(collection_id, post_id)| Some((collection_id, first_files.get(&post_id)?.clone())))
+        .collect();
+
+    Ok(covers)
+}
+
+// A cover is either a file of one of the collection's posts or a file the owner uploaded
+async fn ensure_valid_collection_cover<C: ConnectionTrait>(db: &C, user_id: &[u8], collection_id: Option<&[u8]>, cover_url: &str) -> Result<()> {
+    if is_uploaded_by(db, user_id, cover_url).await? {
+        return Ok(());
+    }
+
+    if let Some(collection_id) = collection_id {
+        let collection_posts = Query::select()
+            .column((Entity, Column::PostId))
+            .from(bookmark_collection_items::Entity)
+            .inner_join(
+                Entity,
+                Expr::col((Entity, Column::Id)).equals((bookmark_collection_items::Entity, bookmark_collection_items::Column::BookmarkId))
+            )
+            .and_where(bookmark_collection_items::Column::CollectionId.eq(collection_id.to_vec()))
+            .to_owned();
+
+        let post_file = post_files::Entity::find()
+            .filter(post_files::Column::PostId.in_subquery(collection_posts))
+            .filter(post_files::Column::FileUrl.eq(cover_url))
+            .one(db)
+            .await?;
+
+        if post_file.is_some() {
+            return Ok(());
+        }
+    }
+
+    Err(bad_request("The cover must be a file of one of the collection's posts or a file you uploaded"))
+}
+
+async fn build_collection_details<C: ConnectionTrait>(db: &C, collections: Vec<bookmark_collections::Model>) -> Result<Vec<BookmarkCollectionDetail>> {
+    if collections.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let collection_ids: Vec<Vec<u8>> = collections.iter().map(|collection| collection.id.clone()).collect();
+
+    let bookmarks_counts: HashMap<Vec<u8>, i64> = bookmark_collection_items::Entity::find()
+        .select_only()
+        .column(bookmark_collection_items::Column::CollectionId)
+        .column_as(Expr::col(bookmark_collection_items::Column::Id).count(), "bookmarks_count")
+        .filter(bookmark_collection_items::Column::CollectionId.is_in(collection_ids.clone()))
+        .group_by(bookmark_collection_items::Column::CollectionId)
+        .into_tuple::<(Vec<u8>, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut covers = default_covers(db, collection_ids).await?;
+
+    let collections = collections
+        .into_iter()
+        .map(|collection| BookmarkCollectionDetail {
+            bookmarks_count: bookmarks_counts.get(&collection.id).copied().unwrap_or_default().max(0) as u64,
+            cover_url: collection.cover_url.or_else(|| covers.remove(&collection.id)),
+            id: Uuid::from_slice(&collection.id).unwrap().to_string(),
+            name: collection.name,
+            created_at: collection.created_at,
+            updated_at: collection.updated_at,
+        })
+        .collect();
+
+    Ok(collections)
+}
+
+async fn build_collection_detail<C: ConnectionTrait>(db: &C, collection: bookmark_collections::Model) -> Result<BookmarkCollectionDetail> {
+    build_collection_details(db, vec![collection]).await?
+        .pop()
+        .ok_or_else(collection_not_found)
+}
+
+pub async fn get_bookmark_collections(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<Vec<BookmarkCollectionDetail>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let collections = bookmark_collections::Entity::find()
+        .filter(bookmark_collections::Column::UserId.eq(user.id))
+        .order_by_desc(bookmark_collections::Column::CreatedAt)
+        .order_by_desc(bookmark_collections::Column::Id)
+        .all(db)
+        .await?;
+
+    build_collection_details(db, collections).await
+}
+
+pub async fn create_bookmark_collection(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: CreateBookmarkCollectionPayload) -> Result<BookmarkCollectionDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    // A new collection has no posts yet, so only the owner's uploads can be its cover
+    if let Some(cover_url) = data.cover_url.as_deref() {
+        ensure_valid_collection_cover(db, &user.id, None, cover_url).await?;
+    }
+
+    let collection = bookmark_collections::ActiveModel {
+        id: Set(Vec::from(Uuid::new_v4())),
+        user_id: Set(user.id),
+        name: Set(data.name.unwrap()),
+        cover_url: Set(data.cover_url),
+        ..Default::default()
+    }.insert(db).await?;
+
+    build_collection_detail(db, collection).await
+}
+
+pub async fn update_bookmark_collection(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, collection_id: &str, data: UpdateBookmarkCollectionPayload) -> Result<BookmarkCollectionDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let remove_cover = data.remove_cover.unwrap_or(false);
+    if remove_cover && data.cover_url.is_some() {
+        return Err(bad_request("A cover cannot be set and removed at the same time"));
+    }
+
+    let user = find_user_by_username(db, username).await?;
+    let collection = find_user_collection(db, &user.id, collection_id).await?;
+
+    let old_cover_url = collection.cover_url.clone();
+
+    // None keeps the current cover, removing it falls back to the latest saved post
+    let new_cover_url = match data.cover_url {
+        Some(cover_url) => Some(Some(cover_url)),
+        None if remove_cover => Some(None),
+        None => None,
+    };
+
+    let cover_changed = new_cover_url.as_ref().is_some_and(|cover_url| *cover_url != old_cover_url);
+
+    if let Some(Some(cover_url)) = new_cover_url.as_ref().filter(|_| cover_changed) {
+        ensure_valid_collection_cover(db, &user.id, Some(&collection.id), cover_url).await?;
+    }
+
+    let mut collection: bookmark_collections::ActiveModel = collection.into();
+    if let Some(name) = data.name {
+        collection.name = Set(name);
+    }
+    if let Some(cover_url) = new_cover_url {
+        collection.cover_url = Set(cover_url);
+    }
+    collection.updated_at = Set(Utc::now());
+
+    let collection = collection.update(db).await?;
+
+    // The replaced cover may have been uploaded just for this collection
+    if let Some(old_cover_url) = old_cover_url.filter(|_| cover_changed) {
+        delete_unreferenced_uploads(db, storage, &user.id, vec![old_cover_url]).await?;
+    }
+
+    build_collection_detail(db, collection).await
+}
+
+// The bookmarks themselves are kept, they only leave the collection
+pub async fn delete_bookmark_collection(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, collection_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let collection = find_user_collection(db, &user.id, collection_id).await?;
+
+    let txn = db.begin().await?;
+
+    bookmark_collection_items::Entity::delete_many()
+        .filter(bookmark_collection_items::Column::CollectionId.eq(collection.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    bookmark_collections::Entity::delete_by_id(collection.id)
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    if let Some(cover_url) = collection.cover_url {
+        delete_unreferenced_uploads(db, storage, &user.id, vec![cover_url]).await?;
+    }
+
+    Ok(())
+}
+
+// Saving to a collection bookmarks the post first when needed
+pub async fn add_to_collection(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, collection_id: &str, post_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let collection = find_user_collection(db, &user.id, collection_id).await?;
+    let post = find_visible_post_by_id(db, &user.id, post_id).await?;
+
+    let txn = db.begin().await?;
+
+    let bookmark = insert_bookmark(&txn, &user.id, &post.id).await?;
+    add_to_collections(&txn, bookmark.id, vec![collection.id]).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn remove_from_collection(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, collection_id: &str, post_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let collection = find_user_collection(db, &user.id, collection_id).await?;
+    let bookmark = find_user_bookmark(db, &user.id, post_id).await?;
+
+    let txn = db.begin().await?;
+
+    let result = bookmark_collection_items::Entity::delete_many()
+        .filter(bookmark_collection_items::Column::CollectionId.eq(collection.id.clone()))
+        .filter(bookmark_collection_items::Column::BookmarkId.eq(bookmark.id))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected > 0 {
+        touch_collections(&txn, vec![collection.id]).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::posts::PostDetail;
+use crate::utils::{check_valid_uuid, check_valid_uuids};
+
+pub mod bookmarks_service;
+pub mod bookmarks_controller;
+pub mod bookmarks_routes;
+
+// ---- BOOKMARKS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookmarksPage {
+    pub posts: Vec<PostDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookmarkCollectionDetail {
+    pub id: String,
+    pub name: String,
+    // The chosen cover, otherwise the first file of the latest saved post
+    pub cover_url: Option<String>,
+    pub bookmarks_count: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---- END OF BOOKMARKS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+// Read next to `CursorQuery` from the same query string
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct BookmarksFilter {
+    // Only the bookmarks saved to this collection
+    #[serde(rename = "collectionId")]
+    #[validate(
+    custom(
+    function = "check_valid_uuid",
+    message = "Collection id must be a valid UUID"
+    )
+    )]
+    pub collection_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateBookmarkPayload {
+    #[serde(rename = "postId")]
+    #[validate(
+    custom(
+    function = "check_valid_uuid",
+    message = "Post id must be a valid UUID"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub post_id: Option<String>,
+
+    #[serde(rename = "collectionIds")]
+    #[validate(
+    length(
+    max = 100,
+    message = "A bookmark can be saved to at most 100 collections at once"
+    ),
+    custom(
+    function = "check_valid_uuids",
+    message = "Collection ids must be valid UUIDs"
+    )
+    )]
+    pub collection_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateBookmarkCollectionPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 50,
+    message = "Name must be between 1 and 50 characters"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub name: Option<String>,
+
+    #[serde(rename = "coverUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "Cover URL cannot be empty"
+    )
+    )]
+    pub cover_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateBookmarkCollectionPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 50,
+    message = "Name must be between 1 and 50 characters"
+    )
+    )]
+    pub name: Option<String>,
+
+    #[serde(rename = "coverUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "Cover URL cannot be empty"
+    )
+    )]
+    pub cover_url: Option<String>,
+
+    // Drops the chosen cover so the latest saved post is shown again
+    #[serde(rename = "removeCover")]
+    pub remove_cover: Option<bool>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,34 @@
+use actix_web::{post, delete, HttpResponse, web::{Data, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::Result;
+use super::comment_likes_service::{like_comment, unlike_comment};
+
+#[post("")]
+pub async fn like_comment_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, post_id, comment_id) = path.into_inner();
+
+    let status = like_comment(&ctx.db, &jwt_payload, &username, &post_id, &comment_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": status
+        })
+    ))
+}
+
+#[delete("")]
+pub async fn unlike_comment_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, post_id, comment_id) = path.into_inner();
+
+    let status = unlike_comment(&ctx.db, &jwt_payload, &username, &post_id, &comment_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": status
+        })
+    ))
+}
@@ -0,0 +1,8 @@
+use actix_web::web::ServiceConfig;
+
+use super::comment_likes_controller::{like_comment_handler, unlike_comment_handler};
+
+pub fn get_comment_likes_routes(cfg: &mut ServiceConfig) {
+    cfg.service(like_comment_handler)
+        .service(unlike_comment_handler);
+}
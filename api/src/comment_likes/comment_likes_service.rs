@@ -0,0 +1,158 @@
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, QuerySelect, Statement, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use entity::comment_likes::{Entity, Column, ActiveModel};
+use entity::post_comments;
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::ensure_not_blocked;
+use crate::comment_likes::CommentLikeStatus;
+use crate::post_comments::post_comments_service::{find_post_comment, find_visible_post};
+use crate::posts::posts_service::find_user_post;
+use crate::users::users_service::find_user_by_username;
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// Same rule as post likes: the counter only moves in the transaction that adds or removes the like
+async fn add_to_likes_count<C: ConnectionTrait>(db: &C, comment_id: i32, delta: i32) -> Result<()> {
+    post_comments::Entity::update_many()
+        .col_expr(post_comments::Column::LikesCount, Expr::col(post_comments::Column::LikesCount).add(delta))
+        .filter(post_comments::Column::Id.eq(comment_id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+async fn get_likes_count<C: ConnectionTrait>(db: &C, comment_id: i32) -> Result<u64> {
+    let likes_count: Option<i32> = post_comments::Entity::find_by_id(comment_id)
+        .select_only()
+        .column(post_comments::Column::LikesCount)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(likes_count.unwrap_or_default().max(0) as u64)
+}
+
+pub async fn like_comment(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, comment_id: &str) -> Result<CommentLikeStatus> {
+    let viewer_id = jwt_payload.get_user_id();
+    let post = find_visible_post(db, Some(&viewer_id), username, post_id).await?;
+    let comment = find_post_comment(db, &post.id, comment_id).await?;
+    ensure_not_blocked(db, &viewer_id, &comment.user_id).await?;
+
+    let txn = db.begin().await?;
+
+    let result = ActiveModel {
+        comment_id: Set(comment.id),
+        user_id: Set(viewer_id),
+        ..Default::default()
+    }.insert(&txn).await;
+
+    match result {
+        Ok(_) => add_to_likes_count(&txn, comment.id, 1).await?,
+        Err(e) if is_unique_violation(&e) => {},
+        Err(e) => return Err(e.into()),
+    }
+
+    txn.commit().await?;
+
+    Ok(CommentLikeStatus {
+        liked: true,
+        likes_count: get_likes_count(db, comment.id).await?,
+    })
+}
+
+pub async fn unlike_comment(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, comment_id: &str) -> Result<CommentLikeStatus> {
+    let viewer_id = jwt_payload.get_user_id();
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+    let comment = find_post_comment(db, &post.id, comment_id).await?;
+
+    let txn = db.begin().await?;
+
+    let result = Entity::delete_many()
+        .filter(Column::CommentId.eq(comment.id))
+        .filter(Column::UserId.eq(viewer_id))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected > 0 {
+        add_to_likes_count(&txn, comment.id, -1).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(CommentLikeStatus {
+        liked: false,
+        likes_count: get_likes_count(db, comment.id).await?,
+    })
+}
+
+pub async fn delete_comments_likes<C: ConnectionTrait>(db: &C, comment_ids: Vec<i32>) -> Result<()> {
+    if comment_ids.is_empty() {
+        return Ok(());
+    }
+
+    Entity::delete_many()
+        .filter(Column::CommentId.is_in(comment_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Recompute every `post_comments.likes_count` that does not match `comment_likes`.
+// Returns how many counters were fixed
+pub async fn reconcile_comment_likes_counts(db: &DatabaseConnection) -> Result<u64> {
+    let drifted_ids: Vec<i32> = db.query_all(Statement::from_string(
+        DbBackend::MySql,
+        "SELECT c.id FROM post_comments c \
+         LEFT JOIN (SELECT comment_id, COUNT(*) AS likes FROM comment_likes GROUP BY comment_id) l ON l.comment_id = c.id \
+         WHERE c.likes_count <> COALESCE(l.likes, 0)",
+    ))
+        .await?
+        .into_iter()
+        .filter_map(|row| row.try_get::<i32>("", "id").ok())
+        .collect();
+
+    let mut fixed_count = 0;
+
+    for comment_id in drifted_ids {
+        let txn = db.begin().await?;
+
+        // Likes and unlikes update this row too, so holding its lock keeps them out
+        // until the new value is written. The count below reads a snapshot taken after
+        // the lock, and likes still in flight add themselves on top once it is released
+        let comment = post_comments::Entity::find_by_id(comment_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+
+        let Some(comment) = comment else {
+            txn.commit().await?;
+            continue;
+        };
+
+        let likes_count: Option<i64> = Entity::find()
+            .select_only()
+            .column_as(Column::Id.count(), "count")
+            .filter(Column::CommentId.eq(comment_id))
+            .into_tuple()
+            .one(&txn)
+            .await?;
+        let likes_count = likes_count.unwrap_or_default() as i32;
+
+        if comment.likes_count != likes_count {
+            post_comments::Entity::update_many()
+                .col_expr(post_comments::Column::LikesCount, Expr::value(likes_count))
+                .filter(post_comments::Column::Id.eq(comment_id))
+                .exec(&txn)
+                .await?;
+
+            fixed_count += 1;
+        }
+
+        txn.commit().await?;
+    }
+
+    Ok(fixed_count)
+}
@@ -0,0 +1,24 @@
+use std::time::Duration;
+use actix_web::rt::time::{interval_at, Instant};
+use sea_orm::DatabaseConnection;
+use super::comment_likes_service::reconcile_comment_likes_counts;
+
+pub const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+// Periodically repair comment like counters that drifted from `comment_likes`,
+// for instance after rows were edited by hand
+pub fn spawn_comment_likes_reconciliation(db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let mut interval = interval_at(Instant::now() + RECONCILIATION_INTERVAL, RECONCILIATION_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match reconcile_comment_likes_counts(&db).await {
+                Ok(0) => {},
+                Ok(fixed_count) => tracing::info!("Reconciled {} comment like counters", fixed_count),
+                Err(e) => tracing::error!("Failed to reconcile comment like counters: {:?}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+pub mod comment_likes_service;
+pub mod comment_likes_controller;
+pub mod comment_likes_routes;
+pub mod comment_likes_worker;
+
+// ---- COMMENT LIKES STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentLikeStatus {
+    pub liked: bool,
+    pub likes_count: u64,
+}
+
+// ---- END OF COMMENT LIKES STRUCTS ----
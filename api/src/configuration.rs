@@ -25,12 +25,20 @@ pub struct JwtSettings {
     pub public_key: String,
 }
 
+// Where uploaded media lives on disk and the public URL it is served from
+#[derive(Deserialize, Clone, Debug)]
+pub struct StorageSettings {
+    pub base_url: String,
+    pub path: String,
+}
+
 #[derive(Deserialize, Clone)]
 pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
     pub test_database: Option<DatabaseSettings>,
-    pub jwt: JwtSettings
+    pub jwt: JwtSettings,
+    pub storage: Option<StorageSettings>
 }
 
 pub enum Environment {
@@ -75,8 +83,7 @@ impl Settings {
 
         let environment: Environment = std::env::var("RUST_ENV")
             .unwrap_or_else(|_| "development".into())
-            .try_into()
-            .unwrap();
+            .into();
         let environment_filename = format!("{}.yaml", environment.as_str());
 
         let settings = config::Config::builder()
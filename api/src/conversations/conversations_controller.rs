@@ -0,0 +1,109 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::conversations::{CreateConversationPayload, MarkConversationReadPayload, PARTICIPANT_STATUS_ACCEPTED, PARTICIPANT_STATUS_REQUEST};
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::conversations_service::{accept_conversation, create_conversation, get_conversation, get_conversations, leave_conversation, mark_conversation_read};
+
+async fn conversations_response(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>, status: &str) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_conversations(&ctx.db, &jwt_payload, &username, status, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.conversations,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_inbox_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    conversations_response(ctx, jwt_payload, path, query, PARTICIPANT_STATUS_ACCEPTED).await
+}
+
+#[get("/requests")]
+pub async fn get_message_requests_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    conversations_response(ctx, jwt_payload, path, query, PARTICIPANT_STATUS_REQUEST).await
+}
+
+#[post("")]
+pub async fn create_conversation_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateConversationPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let conversation = create_conversation(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": conversation
+        })
+    ))
+}
+
+#[get("/{conversation_id}")]
+pub async fn get_conversation_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+
+    let conversation = get_conversation(&ctx.db, &jwt_payload, &username, &conversation_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": conversation
+        })
+    ))
+}
+
+#[post("/{conversation_id}/accept")]
+pub async fn accept_conversation_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+
+    accept_conversation(&ctx.db, &jwt_payload, &username, &conversation_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[post("/{conversation_id}/read")]
+pub async fn mark_conversation_read_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<MarkConversationReadPayload>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    mark_conversation_read(&ctx.db, &ctx.events, &jwt_payload, &username, &conversation_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{conversation_id}")]
+pub async fn leave_conversation_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+
+    leave_conversation(&ctx.db, &jwt_payload, &username, &conversation_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,14 @@
+use actix_web::web::{ServiceConfig, scope};
+use crate::messages::messages_routes::get_messages_routes;
+use super::conversations_controller::{accept_conversation_handler, create_conversation_handler, get_conversation_handler, get_inbox_handler, get_message_requests_handler, leave_conversation_handler, mark_conversation_read_handler};
+
+pub fn get_conversations_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_inbox_handler)
+        .service(get_message_requests_handler)
+        .service(create_conversation_handler)
+        .service(scope("/{conversation_id}/messages").configure(get_messages_routes))
+        .service(get_conversation_handler)
+        .service(accept_conversation_handler)
+        .service(mark_conversation_read_handler)
+        .service(leave_conversation_handler);
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait};
+use sea_orm::sea_query::{Expr, Query};
+use uuid::Uuid;
+use entity::conversations::{Entity, Column, Model, ActiveModel};
+use entity::{conversation_participants as participants, messages, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::{ensure_not_blocked, not_blocked_condition};
+use crate::conversations::{ConversationDetail, ConversationsPage, CreateConversationPayload, MarkConversationReadPayload, ParticipantDetail, PARTICIPANT_STATUS_ACCEPTED, PARTICIPANT_STATUS_REQUEST};
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::follows::follows_service::is_following;
+use crate::messages::messages_service::{build_message_details, delete_conversation_messages};
+use crate::users::users_service::{ensure_owner, find_user_by_username, get_post_authors};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::Result;
+
+fn conversation_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Conversation not found")
+}
+
+// Conversations the user does not take part in are reported as not found
+pub async fn find_user_conversation<C: ConnectionTrait>(db: &C, user_id: &[u8], conversation_id: &str) -> Result<(Model, participants::Model)> {
+    let conversation_id = Uuid::from_str(conversation_id).map_err(|_| conversation_not_found())?;
+
+    let participant = participants::Entity::find()
+        .filter(participants::Column::ConversationId.eq(Vec::from(conversation_id)))
+        .filter(participants::Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?
+        .ok_or_else(conversation_not_found)?;
+
+    let conversation = Entity::find_by_id(participant.conversation_id.clone())
+        .one(db)
+        .await?
+        .ok_or_else(conversation_not_found)?;
+
+    Ok((conversation, participant))
+}
+
+pub async fn get_other_participant_ids<C: ConnectionTrait>(db: &C, conversation_id: &[u8], user_id: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let user_ids = participants::Entity::find()
+        .select_only()
+        .column(participants::Column::UserId)
+        .filter(participants::Column::ConversationId.eq(conversation_id.to_vec()))
+        .filter(participants::Column::UserId.ne(user_id.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(user_ids)
+}
+
+// Messages from someone the recipient does not follow start as a request
+async fn add_recipient<C: ConnectionTrait>(db: &C, conversation_id: &[u8], sender_id: &[u8], recipient: &users::Model) -> Result<()> {
+    let status = if is_following(db, &recipient.id, sender_id).await? {
+        PARTICIPANT_STATUS_ACCEPTED
+    } else {
+        PARTICIPANT_STATUS_REQUEST
+    };
+
+    participants::ActiveModel {
+        conversation_id: Set(conversation_id.to_vec()),
+        user_id: Set(recipient.id.clone()),
+        status: Set(status.to_string()),
+        ..Default::default()
+    }.insert(db).await?;
+
+    Ok(())
+}
+
+async fn create_conversation_with<C: ConnectionTrait>(db: &C, creator: &users::Model, recipients: &[users::Model], is_group: bool, name: Option<String>) -> Result<(Model, participants::Model)> {
+    let conversation = ActiveModel {
+        id: Set(Vec::from(Uuid::new_v4())),
+        creator_id: Set(creator.id.clone()),
+        is_group: Set(is_group),
+        name: Set(name),
+        ..Default::default()
+    }.insert(db).await?;
+
+    let participant = participants::ActiveModel {
+        conversation_id: Set(conversation.id.clone()),
+        user_id: Set(creator.id.clone()),
+        status: Set(PARTICIPANT_STATUS_ACCEPTED.to_string()),
+        ..Default::default()
+    }.insert(db).await?;
+
+    for recipient in recipients {
+        add_recipient(db, &conversation.id, &creator.id, recipient).await?;
+    }
+
+    Ok((conversation, participant))
+}
+
+// Two users share at most one one-to-one conversation. Returns it with the sender's participation
+pub async fn get_or_create_direct_conversation<C: ConnectionTrait>(db: &C, sender: &users::Model, recipient: &users::Model) -> Result<(Model, participants::Model)> {
+    let conversations_of = |user_id: &[u8]| {
+        Query::select()
+            .column(participants::Column::ConversationId)
+            .from(participants::Entity)
+            .and_where(participants::Column::UserId.eq(user_id.to_vec()))
+            .to_owned()
+    };
+
+    let existing = Entity::find()
+        .filter(Column::IsGroup.eq(false))
+        .filter(Column::Id.in_subquery(conversations_of(&sender.id)))
+        .filter(Column::Id.in_subquery(conversations_of(&recipient.id)))
+        .one(db)
+        .await?;
+
+    match existing {
+        Some(conversation) => {
+            let participant = participants::Entity::find()
+                .filter(participants::Column::ConversationId.eq(conversation.id.clone()))
+                .filter(participants::Column::UserId.eq(sender.id.clone()))
+                .one(db)
+                .await?
+                .ok_or_else(conversation_not_found)?;
+
+            Ok((conversation, participant))
+        },
+        None => create_conversation_with(db, sender, std::slice::from_ref(recipient), false, None).await,
+    }
+}
+
+// Builds the detail view of many conversations as seen by the viewer
+async fn build_conversation_details<C: ConnectionTrait>(db: &C, viewer_id: &[u8], conversations: Vec<Model>) -> Result<Vec<ConversationDetail>> {
+    let conversation_ids: Vec<Vec<u8>> = conversations.iter().map(|conversation| conversation.id.clone()).collect();
+
+    let participants = participants::Entity::find()
+        .filter(participants::Column::ConversationId.is_in(conversation_ids.clone()))
+        .order_by_asc(participants::Column::Id)
+        .all(db)
+        .await?;
+
+    let authors = get_post_authors(db, participants.iter().map(|participant| participant.user_id.clone()).collect()).await?;
+
+    let last_message_ids: Vec<i32> = messages::Entity::find()
+        .select_only()
+        .column_as(Expr::col(messages::Column::Id).max(), "id")
+        .filter(messages::Column::ConversationId.is_in(conversation_ids))
+        .filter(not_blocked_condition(messages::Column::SenderId, viewer_id))
+        .group_by(messages::Column::ConversationId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let last_messages = messages::Entity::find()
+        .filter(messages::Column::Id.is_in(last_message_ids))
+        .all(db)
+        .await?;
+
+    let mut last_messages: HashMap<String, _> = build_message_details(db, viewer_id, last_messages).await?
+        .into_iter()
+        .map(|message| (message.conversation_id.clone(), message))
+        .collect();
+
+    let mut conversation_details = Vec::with_capacity(conversations.len());
+
+    for conversation in conversations {
+        let members: Vec<&participants::Model> = participants
+            .iter()
+            .filter(|participant| participant.conversation_id == conversation.id)
+            .collect();
+
+        let Some(viewer) = members.iter().find(|participant| participant.user_id == viewer_id) else {
+            continue;
+        };
+
+        // What others sent after the viewer's read cursor
+        let unread_count = messages::Entity::find()
+            .filter(messages::Column::ConversationId.eq(conversation.id.clone()))
+            .filter(messages::Column::SenderId.ne(viewer_id.to_vec()))
+            .filter(messages::Column::Id.gt(viewer.last_read_message_id.unwrap_or_default()))
+            .filter(not_blocked_condition(messages::Column::SenderId, viewer_id))
+            .count(db)
+            .await?;
+
+        let id = Uuid::from_slice(&conversation.id).unwrap_or_default().to_string();
+
+        conversation_details.push(ConversationDetail {
+            participants: members
+                .iter()
+                .filter(|participant| participant.user_id != viewer_id)
+                .filter_map(|participant| Some(ParticipantDetail {
+                    user: authors.get(&participant.user_id)?.clone(),
+                    last_read_message_id: participant.last_read_message_id,
+                }))
+                .collect(),
+            status: viewer.status.clone(),
+            last_message: last_messages.remove(&id),
+            unread_count,
+            id,
+            is_group: conversation.is_group,
+            name: conversation.name,
+            last_message_at: conversation.last_message_at,
+        });
+    }
+
+    Ok(conversation_details)
+}
+
+// A single user without a name opens the one-to-one conversation, anything else starts a group
+pub async fn create_conversation(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: CreateConversationPayload) -> Result<ConversationDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let creator = find_user_by_username(db, username).await?;
+
+    let mut usernames = data.usernames.unwrap();
+    usernames.retain(|other| other != username);
+    usernames.sort();
+    usernames.dedup();
+
+    if usernames.is_empty() {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot start a conversation with yourself")
+        );
+    }
+
+    let mut recipients = Vec::with_capacity(usernames.len());
+    for other in usernames {
+        let recipient = find_user_by_username(db, &other).await?;
+        ensure_not_blocked(db, &creator.id, &recipient.id).await?;
+        recipients.push(recipient);
+    }
+
+    let txn = db.begin().await?;
+
+    let (conversation, _) = if recipients.len() == 1 && data.name.is_none() {
+        get_or_create_direct_conversation(&txn, &creator, &recipients[0]).await?
+    } else {
+        create_conversation_with(&txn, &creator, &recipients, true, data.name).await?
+    };
+
+    txn.commit().await?;
+
+    build_conversation_details(db, &creator.id, vec![conversation]).await?
+        .pop()
+        .ok_or_else(conversation_not_found)
+}
+
+pub async fn get_conversation(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str) -> Result<ConversationDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, _) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    build_conversation_details(db, &viewer_id, vec![conversation]).await?
+        .pop()
+        .ok_or_else(conversation_not_found)
+}
+
+// The inbox holds accepted conversations and the requests the others, latest activity first.
+// A conversation nobody wrote in yet only shows up for its creator
+pub async fn get_conversations(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, status: &str, query: &CursorQuery) -> Result<ConversationsPage> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    let joined = Query::select()
+        .column(participants::Column::ConversationId)
+        .from(participants::Entity)
+        .and_where(participants::Column::UserId.eq(viewer_id.clone()))
+        .and_where(participants::Column::Status.eq(status))
+        .to_owned();
+
+    let with_messages = Query::select()
+        .column(messages::Column::ConversationId)
+        .from(messages::Entity)
+        .to_owned();
+
+    // One extra row tells whether there is a next page
+    let mut conversations = Entity::find()
+        .filter(Column::Id.in_subquery(joined))
+        .filter(
+            Condition::any()
+                .add(Column::CreatorId.eq(viewer_id.clone()))
+                .add(Column::Id.in_subquery(with_messages))
+        )
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(Column::LastMessageAt, Column::Id)))
+        .order_by_desc(Column::LastMessageAt)
+        .order_by_desc(Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if conversations.len() as u64 > limit {
+        conversations.truncate(limit as usize);
+        conversations.last().map(|conversation| Cursor::new(conversation.last_message_at, conversation.id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(ConversationsPage {
+        conversations: build_conversation_details(db, &viewer_id, conversations).await?,
+        next_cursor,
+    })
+}
+
+// Moves a message request to the inbox
+pub async fn accept_conversation(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (_, participant) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    let mut participant: participants::ActiveModel = participant.into();
+    participant.status = Set(PARTICIPANT_STATUS_ACCEPTED.to_string());
+    participant.update(db).await?;
+
+    Ok(())
+}
+
+// Leaving also declines a message request. The last one to leave deletes the conversation
+pub async fn leave_conversation(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, participant) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    let txn = db.begin().await?;
+
+    participants::Entity::delete_by_id(participant.id)
+        .exec(&txn)
+        .await?;
+
+    let remaining = participants::Entity::find()
+        .filter(participants::Column::ConversationId.eq(conversation.id.clone()))
+        .count(&txn)
+        .await?;
+
+    if remaining == 0 {
+        delete_conversation_messages(&txn, &conversation.id).await?;
+
+        Entity::delete_by_id(conversation.id)
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+// The read cursor only moves forward
+pub async fn mark_conversation_read(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str, data: MarkConversationReadPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, participant) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    let message = messages::Entity::find_by_id(data.message_id.unwrap())
+        .filter(messages::Column::ConversationId.eq(conversation.id))
+        .one(db)
+        .await?
+        .ok_or_else(|| {
+            HttpResponseError::default()
+                .set_code(StatusCode::NOT_FOUND.as_u16())
+                .set_error_message("Message not found")
+        })?;
+
+    if participant.last_read_message_id >= Some(message.id) {
+        return Ok(());
+    }
+
+    let mut participant: participants::ActiveModel = participant.into();
+    participant.last_read_message_id = Set(Some(message.id));
+    participant.update(db).await?;
+
+    events.publish(DomainEvent::ConversationRead { conversation_id: message.conversation_id, user_id: viewer_id, message_id: message.id });
+
+    Ok(())
+}
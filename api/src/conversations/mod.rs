@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::messages::MessageDetail;
+use crate::users::PostAuthor;
+
+pub mod conversations_service;
+pub mod conversations_controller;
+pub mod conversations_routes;
+
+// Messages from users the recipient does not follow wait in their requests
+pub const PARTICIPANT_STATUS_ACCEPTED: &str = "accepted";
+pub const PARTICIPANT_STATUS_REQUEST: &str = "request";
+
+// ---- CONVERSATIONS STRUCTS ----
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantDetail {
+    pub user: PostAuthor,
+    // The latest message the participant has read
+    pub last_read_message_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationDetail {
+    pub id: String,
+    pub is_group: bool,
+    pub name: Option<String>,
+    // Every participant but the viewer
+    pub participants: Vec<ParticipantDetail>,
+    // `accepted`, or `request` while the viewer has not accepted it yet
+    pub status: String,
+    pub last_message: Option<MessageDetail>,
+    pub unread_count: u64,
+    pub last_message_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationsPage {
+    pub conversations: Vec<ConversationDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF CONVERSATIONS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+// A single user without a name opens the one-to-one conversation with them,
+// reusing it when it already exists
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateConversationPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 31,
+    message = "A conversation must have between 1 and 31 other participants"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub usernames: Option<Vec<String>>,
+
+    #[validate(
+    length(
+    min = 1,
+    max = 50,
+    message = "Name must be between 1 and 50 characters"
+    )
+    )]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MarkConversationReadPayload {
+    #[serde(rename = "messageId")]
+    #[validate(required(message = "This field is required"))]
+    pub message_id: Option<i32>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
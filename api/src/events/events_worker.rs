@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use sea_orm::DatabaseConnection;
+use crate::events::{DomainEvent, EventBus};
+use crate::notifications::notifications_service::handle_event;
+use crate::realtime::Broker;
+use crate::realtime::realtime_service::push_event;
+
+// Start the background task that dispatches published events and return the bus feeding it.
+// Events are handled one at a time, in the order they were published
+pub fn spawn_event_bus(db: DatabaseConnection, broker: Arc<dyn Broker>) -> EventBus {
+    let (sender, mut receiver) = mpsc::unbounded::<DomainEvent>();
+
+    actix_web::rt::spawn(async move {
+        while let Some(event) = receiver.next().await {
+            let changes = match handle_event(&db, &event).await {
+                Ok(changes) => changes,
+                Err(e) => {
+                    tracing::error!("Failed to handle event {:?}: {:?}", event, e);
+                    continue;
+                },
+            };
+
+            // Connected clients are told once the notifications are written
+            if let Err(e) = push_event(&db, broker.as_ref(), &event, changes).await {
+                tracing::error!("Failed to push event {:?}: {:?}", event, e);
+            }
+        }
+    });
+
+    EventBus::new(sender)
+}
@@ -0,0 +1,53 @@
+use futures::channel::mpsc::UnboundedSender;
+
+pub mod events_worker;
+
+// ---- EVENTS STRUCTS ----
+
+// Something that happened in a domain service and that other parts of the app react to.
+// Events are published once the change is committed
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    Followed { follower_id: Vec<u8>, followee_id: Vec<u8> },
+    Unfollowed { follower_id: Vec<u8>, followee_id: Vec<u8> },
+    FollowRequested { requester_id: Vec<u8>, target_id: Vec<u8> },
+    // The request was accepted, declined or cancelled
+    FollowRequestClosed { requester_id: Vec<u8>, target_id: Vec<u8> },
+    PostLiked { user_id: Vec<u8>, post_id: Vec<u8> },
+    PostUnliked { user_id: Vec<u8>, post_id: Vec<u8> },
+    // Covers replies too, the comment tells whether it has a parent
+    CommentCreated { comment_id: i32 },
+    // `comment_id` is `None` for mentions in the description of a post
+    UsersMentioned { actor_id: Vec<u8>, user_ids: Vec<Vec<u8>>, post_id: Vec<u8>, comment_id: Option<i32> },
+    // Users no longer mentioned in the description of a post
+    MentionsRemoved { user_ids: Vec<Vec<u8>>, post_id: Vec<u8> },
+    UserTagged { actor_id: Vec<u8>, user_id: Vec<u8>, post_id: Vec<u8> },
+    // The user is no longer tagged on any file of the post
+    TagRemoved { user_id: Vec<u8>, post_id: Vec<u8> },
+    StoryReplied { user_id: Vec<u8>, story_owner_id: Vec<u8> },
+    StoryPosted { user_id: Vec<u8>, story_id: Vec<u8> },
+    MessageSent { message_id: i32 },
+    // The read cursor of the user moved forward to the message
+    ConversationRead { conversation_id: Vec<u8>, user_id: Vec<u8>, message_id: i32 },
+}
+
+// Handle used by the domain services to publish events to the background worker
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: UnboundedSender<DomainEvent>,
+}
+
+impl EventBus {
+    pub fn new(sender: UnboundedSender<DomainEvent>) -> Self {
+        Self { sender }
+    }
+
+    // Reactions to an event are side effects, a lost event is logged and not surfaced to the user
+    pub fn publish(&self, event: DomainEvent) {
+        if let Err(e) = self.sender.unbounded_send(event) {
+            tracing::error!("Failed to publish event: {:?}", e);
+        }
+    }
+}
+
+// ---- END OF EVENTS STRUCTS ----
@@ -0,0 +1,25 @@
+use actix_web::{get, HttpResponse, web::{Data, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::explore_service::get_explore;
+
+#[get("")]
+pub async fn get_explore_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let explore = get_explore(&ctx.db, &ctx.impressions, &jwt_payload, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": explore.posts,
+            "nextCursor": explore.next_cursor
+        })
+    ))
+}
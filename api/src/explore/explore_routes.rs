@@ -0,0 +1,7 @@
+use actix_web::web::ServiceConfig;
+
+use super::explore_controller::get_explore_handler;
+
+pub fn get_explore_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_explore_handler);
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use chrono::{DateTime, Duration, Utc};
+use futures::lock::Mutex;
+use sea_orm::{Set, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Statement, TransactionTrait};
+use sea_orm::sea_query::Query;
+use entity::explore_entries::{Entity, Column, ActiveModel};
+use entity::{follows, posts};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::explore::{AUTHOR_REPEAT_PENALTY, COMMENT_WEIGHT, EXPLORE_SIZE, EXPLORE_WINDOW_DAYS, GRAVITY, MAX_POSTS_PER_AUTHOR};
+use crate::feed::FeedPage;
+use crate::insights::{ImpressionSource, ImpressionsQueue};
+use crate::mutes::mutes_service::not_muted_condition;
+use crate::posts::posts_service::build_post_details;
+use crate::users::users_service::visible_authors_condition;
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::Result;
+
+// Runs rebuilding the table at the same time would fight over the same rows
+static REFRESH_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+struct ExploreCandidate {
+    post_id: Vec<u8>,
+    author_id: Vec<u8>,
+    score: f64,
+}
+
+// Weighted engagement per hour since the post was published. The `+ 2` keeps
+// brand new posts from getting a huge score out of their very first like
+fn engagement_velocity(likes: i64, comments: i64, age: Duration) -> f64 {
+    let hours = age.num_seconds().max(0) as f64 / 3600.0;
+
+    (likes as f64 + COMMENT_WEIGHT * comments as f64) / (hours + 2.0).powf(GRAVITY)
+}
+
+// Best first, each further post of an author is pushed down and
+// the ones beyond `MAX_POSTS_PER_AUTHOR` are dropped
+fn diversify(mut candidates: Vec<ExploreCandidate>) -> Vec<ExploreCandidate> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut ranked_per_author: HashMap<Vec<u8>, i32> = HashMap::new();
+    let mut diversified: Vec<ExploreCandidate> = candidates
+        .into_iter()
+        .filter_map(|mut candidate| {
+            let ranked = ranked_per_author.entry(candidate.author_id.clone()).or_default();
+            if *ranked >= MAX_POSTS_PER_AUTHOR {
+                return None;
+            }
+
+            candidate.score *= AUTHOR_REPEAT_PENALTY.powi(*ranked);
+            *ranked += 1;
+
+            Some(candidate)
+        })
+        .collect();
+
+    diversified.sort_by(|a, b| b.score.total_cmp(&a.score));
+    diversified.truncate(EXPLORE_SIZE);
+
+    diversified
+}
+
+// Recent posts of public accounts with some engagement. The author's own likes and comments don't count
+async fn get_explore_candidates<C: ConnectionTrait>(db: &C, now: DateTime<Utc>) -> Result<Vec<ExploreCandidate>> {
+    let rows = db.query_all(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        "SELECT p.id, p.user_id, p.created_at, \
+         (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id AND l.user_id <> p.user_id) AS likes, \
+         (SELECT COUNT(*) FROM post_comments c WHERE c.post_id = p.id AND c.user_id <> p.user_id) AS comments \
+         FROM posts p \
+         INNER JOIN users u ON u.id = p.user_id \
+         WHERE u.is_private = false AND p.created_at >= ? \
+         HAVING likes + comments > 0",
+        [(now - Duration::days(EXPLORE_WINDOW_DAYS)).into()],
+    )).await?;
+
+    let candidates = rows
+        .into_iter()
+        .filter_map(|row| {
+            let created_at: DateTime<Utc> = row.try_get("", "created_at").ok()?;
+            let likes: i64 = row.try_get("", "likes").ok()?;
+            let comments: i64 = row.try_get("", "comments").ok()?;
+
+            Some(ExploreCandidate {
+                post_id: row.try_get("", "id").ok()?,
+                author_id: row.try_get("", "user_id").ok()?,
+                score: engagement_velocity(likes, comments, now - created_at),
+            })
+        })
+        .collect();
+
+    Ok(candidates)
+}
+
+// Ranks the explore page again from scratch and returns how many posts made it
+pub async fn refresh_explore_entries(db: &DatabaseConnection) -> Result<u64> {
+    let _guard = REFRESH_LOCK.lock().await;
+
+    let now = Utc::now();
+    let ranked = diversify(get_explore_candidates(db, now).await?);
+    let ranked_count = ranked.len() as u64;
+
+    // Readers keep seeing the previous ranking until the new one is committed
+    let txn = db.begin().await?;
+
+    Entity::delete_many()
+        .exec(&txn)
+        .await?;
+
+    if !ranked.is_empty() {
+        let entries = ranked
+            .into_iter()
+            .enumerate()
+            .map(|(position, candidate)| ActiveModel {
+                post_id: Set(candidate.post_id),
+                author_id: Set(candidate.author_id),
+                score: Set(candidate.score),
+                rank: Set(position as i32 + 1),
+                computed_at: Set(now),
+            });
+
+        Entity::insert_many(entries)
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(ranked_count)
+}
+
+// Posts of accounts the viewer doesn't follow, in ranking order. The ranking is shared by everyone,
+// so the viewer's follows, blocks and mutes are applied here, as well as accounts that went private since
+pub async fn get_explore(db: &DatabaseConnection, impressions: &ImpressionsQueue, jwt_payload: &JwtTokenPayload, query: &CursorQuery) -> Result<FeedPage> {
+    let viewer_id = jwt_payload.get_user_id();
+
+    let limit = query.get_limit();
+    let cursor = query.get_id_cursor()?;
+
+    let followees = Query::select()
+        .column(follows::Column::FolloweeId)
+        .from(follows::Entity)
+        .and_where(follows::Column::FollowerId.eq(viewer_id.clone()))
+        .to_owned();
+
+    // One extra row tells whether there is a next page
+    let mut entries = Entity::find()
+        .filter(Column::AuthorId.ne(viewer_id.clone()))
+        .filter(Column::AuthorId.not_in_subquery(followees))
+        .filter(visible_authors_condition(Column::AuthorId, None))
+        .filter(not_blocked_condition(Column::AuthorId, &viewer_id))
+        .filter(not_muted_condition(Column::AuthorId, &viewer_id))
+        .apply_if(cursor, |query, cursor| query.filter(Column::Rank.gt(cursor)))
+        .order_by_asc(Column::Rank)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if entries.len() as u64 > limit {
+        entries.truncate(limit as usize);
+        entries.last().map(|entry| encode_id_cursor(entry.rank))
+    } else {
+        None
+    };
+
+    let mut posts: HashMap<Vec<u8>, posts::Model> = posts::Entity::find()
+        .filter(posts::Column::Id.is_in(entries.iter().map(|entry| entry.post_id.clone())))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|post| (post.id.clone(), post))
+        .collect();
+
+    let posts: Vec<posts::Model> = entries
+        .into_iter()
+        .filter_map(|entry| posts.remove(&entry.post_id))
+        .collect();
+
+    impressions.record(Some(viewer_id), posts.iter().map(|post| post.id.clone()).collect(), ImpressionSource::Explore);
+
+    Ok(FeedPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor,
+    })
+}
+
+pub async fn remove_post_from_explore<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
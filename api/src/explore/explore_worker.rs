@@ -0,0 +1,24 @@
+use std::time::Duration;
+use actix_web::rt::time::{interval_at, Instant};
+use sea_orm::DatabaseConnection;
+use super::explore_service::refresh_explore_entries;
+
+pub const EXPLORE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+// Ranking every recent post is too slow for a request,
+// the explore page reads what the last run left in `explore_entries`
+pub fn spawn_explore_ranking(db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let mut interval = interval_at(Instant::now() + EXPLORE_INTERVAL, EXPLORE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match refresh_explore_entries(&db).await {
+                Ok(0) => {},
+                Ok(ranked_count) => tracing::info!("Ranked {} posts for the explore page", ranked_count),
+                Err(e) => tracing::error!("Failed to rank the explore page: {:?}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,22 @@
+pub mod explore_service;
+pub mod explore_controller;
+pub mod explore_routes;
+pub mod explore_worker;
+
+// Only posts younger than this are ranked
+pub const EXPLORE_WINDOW_DAYS: i64 = 7;
+
+// Rows kept in `explore_entries` after each ranking
+pub const EXPLORE_SIZE: usize = 500;
+
+// Posts of the same author that can appear in the explore page
+pub const MAX_POSTS_PER_AUTHOR: i32 = 3;
+
+// A comment takes more effort than a like, so it weighs more
+pub const COMMENT_WEIGHT: f64 = 2.0;
+
+// How fast the score of a post fades as it gets older
+pub const GRAVITY: f64 = 1.5;
+
+// Each further post of an author already ranked has its score multiplied by this
+pub const AUTHOR_REPEAT_PENALTY: f64 = 0.5;
@@ -0,0 +1,56 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::favorites::CreateFavoritePayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::favorites_service::{add_favorite, get_favorites, remove_favorite};
+
+#[get("")]
+pub async fn get_favorites_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_favorites(&ctx.db, &jwt_payload, &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.posts,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("")]
+pub async fn add_favorite_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateFavoritePayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    add_favorite(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16()
+        })
+    ))
+}
+
+#[delete("/{post_id}")]
+pub async fn remove_favorite_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    remove_favorite(&ctx.db, &jwt_payload, &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::favorites_controller::{add_favorite_handler, get_favorites_handler, remove_favorite_handler};
+
+pub fn get_favorites_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_favorites_handler)
+        .service(add_favorite_handler)
+        .service(remove_favorite_handler);
+}
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+use sea_orm::{Set, ActiveModelTrait, Condition, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait};
+use sea_orm::sea_query::Query;
+use uuid::Uuid;
+use entity::favorites::{Entity, Column, ActiveModel};
+use entity::posts;
+use crate::auth::JwtTokenPayload;
+use crate::favorites::{CreateFavoritePayload, FavoritesPage};
+use crate::posts::posts_service::{build_post_summaries, find_visible_post_by_id};
+use crate::users::users_service::{ensure_owner, find_user_by_username, visible_authors_condition};
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// Removes the favorites `user_id` made on posts of `author_id`, used when one blocks the other
+pub async fn delete_favorites_of_author<C: ConnectionTrait>(db: &C, user_id: &[u8], author_id: &[u8]) -> Result<()> {
+    let author_posts = Query::select()
+        .column(posts::Column::Id)
+        .from(posts::Entity)
+        .and_where(posts::Column::UserId.eq(author_id.to_vec()))
+        .to_owned();
+
+    Entity::delete_many()
+        .filter(
+            Condition::all()
+                .add(Column::UserId.eq(user_id.to_vec()))
+                .add(Column::PostId.in_subquery(author_posts))
+        )
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Adding a post twice keeps the first favorite
+pub async fn add_favorite(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: CreateFavoritePayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let post = find_visible_post_by_id(db, &user.id, &data.post_id.unwrap()).await?;
+
+    let result = ActiveModel {
+        user_id: Set(user.id),
+        post_id: Set(post.id),
+        ..Default::default()
+    }.insert(db).await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if is_unique_violation(&e) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Removing a post that is not a favorite is not an error.
+// Favorites are addressed by post id, which is what clients know about
+pub async fn remove_favorite(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let Ok(post_id) = Uuid::from_str(post_id) else {
+        return Ok(());
+    };
+
+    Entity::delete_many()
+        .filter(Column::UserId.eq(user.id))
+        .filter(Column::PostId.eq(Vec::from(post_id)))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Newest favorites first. Posts whose author the user can no longer see are left out
+pub async fn get_favorites(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, query: &CursorQuery) -> Result<FavoritesPage> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_id_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut favorites = Entity::find()
+        .find_also_related(posts::Entity)
+        .filter(Column::UserId.eq(user.id.clone()))
+        .filter(visible_authors_condition(posts::Column::UserId, Some(&user.id)))
+        .apply_if(cursor, |query, cursor| query.filter(Column::Id.lt(cursor)))
+        .order_by_desc(Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if favorites.len() as u64 > limit {
+        favorites.truncate(limit as usize);
+        favorites.last().map(|(favorite, _)| encode_id_cursor(favorite.id))
+    } else {
+        None
+    };
+
+    let posts = favorites.into_iter().filter_map(|(_, post)| post).collect();
+
+    Ok(FavoritesPage {
+        posts: build_post_summaries(db, posts).await?,
+        next_cursor,
+    })
+}
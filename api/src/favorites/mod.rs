@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::posts::PostSummary;
+use crate::utils::check_valid_uuid;
+
+pub mod favorites_service;
+pub mod favorites_controller;
+pub mod favorites_routes;
+
+// ---- FAVORITES STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoritesPage {
+    pub posts: Vec<PostSummary>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF FAVORITES STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateFavoritePayload {
+    #[serde(rename = "postId")]
+    #[validate(
+    custom(
+    function = "check_valid_uuid",
+    message = "Post id must be a valid UUID"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub post_id: Option<String>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,43 @@
+use actix_web::{get, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::feed_service::{get_feed, get_following_posts};
+
+#[get("")]
+pub async fn get_feed_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let feed = get_feed(&ctx.db, &ctx.impressions, &jwt_payload, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": feed.posts,
+            "nextCursor": feed.next_cursor
+        })
+    ))
+}
+
+#[get("/posts")]
+pub async fn get_following_posts_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let feed = get_following_posts(&ctx.db, &ctx.impressions, &jwt_payload, &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": feed.posts,
+            "nextCursor": feed.next_cursor
+        })
+    ))
+}
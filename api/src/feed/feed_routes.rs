@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use crate::stories::stories_controller::get_story_tray_handler;
+use super::feed_controller::get_feed_handler;
+
+pub fn get_feed_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_feed_handler)
+        .service(get_story_tray_handler);
+}
@@ -0,0 +1,30 @@
+use sea_orm::DatabaseConnection;
+use crate::auth::JwtTokenPayload;
+use crate::feed::FeedPage;
+use crate::insights::{ImpressionSource, ImpressionsQueue};
+use crate::posts::posts_service::build_post_details;
+use crate::timeline::timeline_service::get_timeline_page;
+use crate::users::users_service::{ensure_owner, find_user_by_username};
+use crate::utils::cursor::CursorQuery;
+use crate::Result;
+
+pub async fn get_feed(db: &DatabaseConnection, impressions: &ImpressionsQueue, jwt_payload: &JwtTokenPayload, query: &CursorQuery) -> Result<FeedPage> {
+    let viewer_id = jwt_payload.get_user_id();
+
+    let (posts, next_cursor) = get_timeline_page(db, &viewer_id, query.get_limit(), query.get_cursor()?).await?;
+
+    impressions.record(Some(viewer_id), posts.iter().map(|post| post.id.clone()).collect(), ImpressionSource::Feed);
+
+    Ok(FeedPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+    })
+}
+
+pub async fn get_following_posts(db: &DatabaseConnection, impressions: &ImpressionsQueue, jwt_payload: &JwtTokenPayload, username: &str, query: &CursorQuery) -> Result<FeedPage> {
+    // The feed is personal, nobody else can read it
+    ensure_owner(jwt_payload, username)?;
+    find_user_by_username(db, username).await?;
+
+    get_feed(db, impressions, jwt_payload, query).await
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use crate::posts::PostDetail;
+
+pub mod feed_service;
+pub mod feed_controller;
+pub mod feed_routes;
+
+// ---- FEED STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedPage {
+    pub posts: Vec<PostDetail>,
+    // `None` once the end of the feed is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF FEED STRUCTS ----
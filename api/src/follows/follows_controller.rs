@@ -0,0 +1,103 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::Result;
+use super::follows_service::{accept_follow_request, decline_follow_request, follow, get_follow_requests, get_followers, get_following, unfollow};
+
+#[get("")]
+pub async fn get_followers_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let followers = get_followers(&ctx.db, viewer.as_ref(), &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": followers
+        })
+    ))
+}
+
+#[post("")]
+pub async fn follow_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let status = follow(&ctx.db, &ctx.timeline, &ctx.events, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": {
+                "status": status
+            }
+        })
+    ))
+}
+
+#[delete("")]
+pub async fn unfollow_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    unfollow(&ctx.db, &ctx.timeline, &ctx.events, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_following_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let following = get_following(&ctx.db, viewer.as_ref(), &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": following
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_follow_requests_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let requests = get_follow_requests(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": requests
+        })
+    ))
+}
+
+#[post("/{requester_username}")]
+pub async fn accept_follow_request_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, requester_username) = path.into_inner();
+
+    accept_follow_request(&ctx.db, &ctx.timeline, &ctx.events, &jwt_payload, &username, &requester_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{requester_username}")]
+pub async fn decline_follow_request_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, requester_username) = path.into_inner();
+
+    decline_follow_request(&ctx.db, &ctx.events, &jwt_payload, &username, &requester_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
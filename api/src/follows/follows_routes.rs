@@ -0,0 +1,24 @@
+use actix_web::web::ServiceConfig;
+use crate::feed::feed_controller::get_following_posts_handler;
+
+use super::follows_controller::{
+    get_followers_handler, follow_handler, unfollow_handler, get_following_handler,
+    get_follow_requests_handler, accept_follow_request_handler, decline_follow_request_handler,
+};
+
+pub fn get_followers_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_followers_handler)
+        .service(follow_handler)
+        .service(unfollow_handler);
+}
+
+pub fn get_following_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_following_handler)
+        .service(get_following_posts_handler);
+}
+
+pub fn get_follow_requests_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_follow_requests_handler)
+        .service(accept_follow_request_handler)
+        .service(decline_follow_request_handler);
+}
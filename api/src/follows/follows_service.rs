@@ -0,0 +1,311 @@
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, TransactionTrait};
+use entity::follows::{Entity, Column, ActiveModel, Relation};
+use sea_orm::sea_query::Expr;
+use entity::{follow_requests, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::{ensure_not_blocked, not_blocked_condition};
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::follows::{Follower, FollowStatus};
+use crate::timeline::{TimelineJob, TimelineQueue};
+use crate::timeline::timeline_service::{remove_author_from_timeline, FAN_OUT_FOLLOWER_LIMIT};
+use crate::users::users_service::{ensure_can_view_content, ensure_owner, find_user_by_username};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+pub async fn is_following<C: ConnectionTrait>(db: &C, follower_id: &[u8], followee_id: &[u8]) -> Result<bool> {
+    let follow = Entity::find()
+        .filter(Column::FollowerId.eq(follower_id.to_vec()))
+        .filter(Column::FolloweeId.eq(followee_id.to_vec()))
+        .one(db)
+        .await?;
+
+    Ok(follow.is_some())
+}
+
+// Every write to `follows` moves `users.followers_count` of the followee in the same transaction
+pub async fn add_to_followers_count<C: ConnectionTrait>(db: &C, user_id: &[u8], delta: i32) -> Result<()> {
+    users::Entity::update_many()
+        .col_expr(users::Column::FollowersCount, Expr::col(users::Column::FollowersCount).add(delta))
+        .filter(users::Column::Id.eq(user_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Returns whether losing the followers brought the user back under the fan out limit,
+// the posts they made while above it were never copied into timelines and have to be then
+pub async fn remove_from_followers_count<C: ConnectionTrait>(db: &C, user_id: &[u8], removed_count: u64) -> Result<bool> {
+    if removed_count == 0 {
+        return Ok(false);
+    }
+
+    add_to_followers_count(db, user_id, -(removed_count as i32)).await?;
+
+    let followers_count: Option<i32> = users::Entity::find_by_id(user_id.to_vec())
+        .select_only()
+        .column(users::Column::FollowersCount)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    let followers_count = followers_count.unwrap_or_default().max(0) as u64;
+
+    Ok(followers_count <= FAN_OUT_FOLLOWER_LIMIT && followers_count + removed_count > FAN_OUT_FOLLOWER_LIMIT)
+}
+
+pub async fn follow(db: &DatabaseConnection, timeline: &TimelineQueue, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str) -> Result<FollowStatus> {
+    if jwt_payload.username == username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot follow yourself")
+        );
+    }
+
+    let follower = find_user_by_username(db, &jwt_payload.username).await?;
+    let followee = find_user_by_username(db, username).await?;
+
+    ensure_not_blocked(db, &follower.id, &followee.id).await?;
+
+    let already_following_error = HttpResponseError::default()
+        .set_code(StatusCode::BAD_REQUEST.as_u16())
+        .set_error_message("You already followed this user");
+
+    if is_following(db, &follower.id, &followee.id).await? {
+        return Err(already_following_error);
+    }
+
+    // Private accounts have to approve their followers first
+    if followee.is_private {
+        let result = follow_requests::ActiveModel {
+            requester_id: Set(follower.id.clone()),
+            target_id: Set(followee.id.clone()),
+            ..Default::default()
+        }.insert(db).await;
+
+        return match result {
+            Ok(_) => {
+                events.publish(DomainEvent::FollowRequested { requester_id: follower.id, target_id: followee.id });
+                Ok(FollowStatus::Requested)
+            },
+            Err(e) if is_unique_violation(&e) => Err(
+                HttpResponseError::default()
+                    .set_code(StatusCode::BAD_REQUEST.as_u16())
+                    .set_error_message("You already requested to follow this user")
+            ),
+            Err(e) => Err(e.into()),
+        };
+    }
+
+    let txn = db.begin().await?;
+
+    // Two concurrent requests can both pass the check above,
+    // the unique key on (follower_id, followee_id) settles it
+    let result = ActiveModel {
+        follower_id: Set(follower.id.clone()),
+        followee_id: Set(followee.id.clone()),
+        ..Default::default()
+    }.insert(&txn).await;
+
+    match result {
+        Ok(_) => {
+            add_to_followers_count(&txn, &followee.id, 1).await?;
+            txn.commit().await?;
+
+            events.publish(DomainEvent::Followed { follower_id: follower.id.clone(), followee_id: followee.id.clone() });
+            timeline.enqueue(TimelineJob::Backfill { user_id: follower.id, author_id: followee.id });
+            Ok(FollowStatus::Following)
+        },
+        Err(e) if is_unique_violation(&e) => Err(already_following_error),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn unfollow(db: &DatabaseConnection, timeline: &TimelineQueue, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str) -> Result<()> {
+    if jwt_payload.username == username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot unfollow yourself")
+        );
+    }
+
+    let follower = find_user_by_username(db, &jwt_payload.username).await?;
+    let followee = find_user_by_username(db, username).await?;
+
+    let txn = db.begin().await?;
+
+    let result = Entity::delete_many()
+        .filter(Column::FollowerId.eq(follower.id.clone()))
+        .filter(Column::FolloweeId.eq(followee.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected > 0 {
+        remove_author_from_timeline(&txn, &follower.id, &followee.id).await?;
+        let dropped_under_limit = remove_from_followers_count(&txn, &followee.id, result.rows_affected).await?;
+        txn.commit().await?;
+
+        if dropped_under_limit {
+            timeline.enqueue(TimelineJob::FanOutAuthor { author_id: followee.id.clone() });
+        }
+        events.publish(DomainEvent::Unfollowed { follower_id: follower.id, followee_id: followee.id });
+
+        return Ok(());
+    }
+
+    txn.commit().await?;
+
+    // Unfollowing a private account that has not answered yet cancels the request
+    let result = follow_requests::Entity::delete_many()
+        .filter(follow_requests::Column::RequesterId.eq(follower.id.clone()))
+        .filter(follow_requests::Column::TargetId.eq(followee.id.clone()))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You are not following this user")
+        );
+    }
+
+    events.publish(DomainEvent::FollowRequestClosed { requester_id: follower.id, target_id: followee.id });
+
+    Ok(())
+}
+
+pub async fn get_followers(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str) -> Result<Vec<Follower>> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let followers = entity::users::Entity::find()
+        .join(JoinType::InnerJoin, Relation::Follower.def().rev())
+        .filter(Column::FolloweeId.eq(user.id))
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(entity::users::Column::Id, &viewer_id)))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Follower::from)
+        .collect();
+
+    Ok(followers)
+}
+
+pub async fn get_following(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str) -> Result<Vec<Follower>> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let following = entity::users::Entity::find()
+        .join(JoinType::InnerJoin, Relation::Followee.def().rev())
+        .filter(Column::FollowerId.eq(user.id))
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(entity::users::Column::Id, &viewer_id)))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Follower::from)
+        .collect();
+
+    Ok(following)
+}
+
+pub async fn get_follow_requests(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<Vec<Follower>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let requesters = entity::users::Entity::find()
+        .join(JoinType::InnerJoin, follow_requests::Relation::Requester.def().rev())
+        .filter(follow_requests::Column::TargetId.eq(user.id))
+        .order_by_desc(follow_requests::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Follower::from)
+        .collect();
+
+    Ok(requesters)
+}
+
+fn follow_request_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Follow request not found")
+}
+
+pub async fn accept_follow_request(db: &DatabaseConnection, timeline: &TimelineQueue, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, requester_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let requester = find_user_by_username(db, requester_username).await?;
+
+    let txn = db.begin().await?;
+
+    let result = follow_requests::Entity::delete_many()
+        .filter(follow_requests::Column::RequesterId.eq(requester.id.clone()))
+        .filter(follow_requests::Column::TargetId.eq(user.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(follow_request_not_found());
+    }
+
+    let result = ActiveModel {
+        follower_id: Set(requester.id.clone()),
+        followee_id: Set(user.id.clone()),
+        ..Default::default()
+    }.insert(&txn).await;
+
+    let followed = match result {
+        Ok(_) => true,
+        // Already following, the request was stale
+        Err(e) if is_unique_violation(&e) => false,
+        Err(e) => return Err(e.into()),
+    };
+
+    if followed {
+        add_to_followers_count(&txn, &user.id, 1).await?;
+    }
+
+    txn.commit().await?;
+
+    events.publish(DomainEvent::FollowRequestClosed { requester_id: requester.id.clone(), target_id: user.id.clone() });
+    if followed {
+        events.publish(DomainEvent::Followed { follower_id: requester.id.clone(), followee_id: user.id.clone() });
+    }
+    timeline.enqueue(TimelineJob::Backfill { user_id: requester.id, author_id: user.id });
+
+    Ok(())
+}
+
+pub async fn decline_follow_request(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, requester_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let requester = find_user_by_username(db, requester_username).await?;
+
+    let result = follow_requests::Entity::delete_many()
+        .filter(follow_requests::Column::RequesterId.eq(requester.id.clone()))
+        .filter(follow_requests::Column::TargetId.eq(user.id.clone()))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(follow_request_not_found());
+    }
+
+    events.publish(DomainEvent::FollowRequestClosed { requester_id: requester.id, target_id: user.id });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod follows_service;
+pub mod follows_controller;
+pub mod follows_routes;
+
+// ---- FOLLOWS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Follower {
+    pub id: String,
+    pub full_name: String,
+    pub username: String,
+}
+
+impl From<entity::users::Model> for Follower {
+    fn from(user: entity::users::Model) -> Self {
+        Self {
+            id: Uuid::from_slice(&user.id).unwrap().to_string(),
+            full_name: user.name,
+            username: user.username,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum FollowStatus {
+    Following,
+    // The target is private, so a follow request is waiting for their approval
+    Requested,
+}
+
+// ---- END OF FOLLOWS STRUCTS ----
@@ -0,0 +1,94 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::hashtags_service::{follow_hashtag, get_followed_hashtags, get_hashtag, get_recent_posts, get_top_posts, unfollow_hashtag};
+
+#[get("/{name}")]
+pub async fn get_hashtag_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let name = path.into_inner();
+
+    let hashtag = get_hashtag(&ctx.db, viewer.as_ref(), &name).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": hashtag
+        })
+    ))
+}
+
+#[get("/{name}/top")]
+pub async fn get_top_posts_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let name = path.into_inner();
+
+    let posts = get_top_posts(&ctx.db, viewer.as_ref(), &name).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": posts
+        })
+    ))
+}
+
+#[get("/{name}/recent")]
+pub async fn get_recent_posts_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let name = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_recent_posts(&ctx.db, viewer.as_ref(), &name, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.posts,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("/{name}/follow")]
+pub async fn follow_hashtag_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let name = path.into_inner();
+
+    follow_hashtag(&ctx.db, &jwt_payload, &name).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{name}/follow")]
+pub async fn unfollow_hashtag_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let name = path.into_inner();
+
+    unfollow_hashtag(&ctx.db, &jwt_payload, &name).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_followed_hashtags_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let hashtags = get_followed_hashtags(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": hashtags
+        })
+    ))
+}
@@ -0,0 +1,15 @@
+use actix_web::web::ServiceConfig;
+
+use super::hashtags_controller::{follow_hashtag_handler, get_followed_hashtags_handler, get_hashtag_handler, get_recent_posts_handler, get_top_posts_handler, unfollow_hashtag_handler};
+
+pub fn get_hashtags_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_hashtag_handler)
+        .service(get_top_posts_handler)
+        .service(get_recent_posts_handler)
+        .service(follow_hashtag_handler)
+        .service(unfollow_hashtag_handler);
+}
+
+pub fn get_followed_hashtags_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_followed_hashtags_handler);
+}
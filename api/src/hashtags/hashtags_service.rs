@@ -0,0 +1,356 @@
+use std::collections::{HashMap, HashSet};
+use actix_web::http::StatusCode;
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Statement, Value};
+use sea_orm::sea_query::{Expr, Query};
+use entity::hashtags::{Entity, Column, Model};
+use entity::{hashtag_follows, post_comments, post_hashtags, posts};
+use crate::auth::JwtTokenPayload;
+use crate::error::HttpResponseError;
+use crate::hashtags::{HashtagDetail, HashtagPostsPage};
+use crate::posts::PostDetail;
+use crate::posts::posts_service::build_post_details;
+use crate::users::users_service::{ensure_owner, find_user_by_username, visible_authors_condition};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::utils::text::{extract_hashtags, normalize_hashtag, MAX_HASHTAGS_PER_TEXT};
+use crate::Result;
+
+// Hashtag pages show this many top posts, like the first rows of a grid
+pub const TOP_POSTS_LIMIT: u64 = 9;
+
+const HASHTAG_NOT_FOUND_ERROR: &str = "Hashtag not found";
+
+fn hashtag_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(HASHTAG_NOT_FOUND_ERROR)
+}
+
+pub async fn find_hashtag<C: ConnectionTrait>(db: &C, name: &str) -> Result<Model> {
+    let name = normalize_hashtag(name).ok_or_else(hashtag_not_found)?;
+
+    let hashtag = Entity::find()
+        .filter(Column::Name.eq(name))
+        .one(db)
+        .await?;
+
+    hashtag.ok_or_else(hashtag_not_found)
+}
+
+// Ids of the given hashtags, creating the ones that are used for the first time
+async fn ensure_hashtags<C: ConnectionTrait>(db: &C, names: &[String]) -> Result<Vec<i32>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders = vec!["(?)"; names.len()].join(", ");
+    let values: Vec<Value> = names.iter().map(|name| name.clone().into()).collect();
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!("INSERT IGNORE INTO hashtags (name) VALUES {}", placeholders),
+        values,
+    )).await?;
+
+    let hashtag_ids = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::Name.is_in(names.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(hashtag_ids)
+}
+
+// A post is tagged by its description and by the comments of its author,
+// comments of other users cannot put a post on a hashtag page.
+// Has to be called whenever one of those texts changes
+pub async fn sync_post_hashtags<C: ConnectionTrait>(db: &C, post: &posts::Model) -> Result<()> {
+    let author_comments: Vec<String> = post_comments::Entity::find()
+        .select_only()
+        .column(post_comments::Column::Comment)
+        .filter(post_comments::Column::PostId.eq(post.id.clone()))
+        .filter(post_comments::Column::UserId.eq(post.user_id.clone()))
+        .order_by_asc(post_comments::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut names: Vec<String> = Vec::new();
+    for text in std::iter::once(&post.description).chain(author_comments.iter()) {
+        for name in extract_hashtags(text) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names.truncate(MAX_HASHTAGS_PER_TEXT);
+
+    let wanted: HashSet<i32> = ensure_hashtags(db, &names).await?.into_iter().collect();
+
+    let current: HashSet<i32> = post_hashtags::Entity::find()
+        .select_only()
+        .column(post_hashtags::Column::HashtagId)
+        .filter(post_hashtags::Column::PostId.eq(post.id.clone()))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let removed: Vec<i32> = current.difference(&wanted).copied().collect();
+    if !removed.is_empty() {
+        post_hashtags::Entity::delete_many()
+            .filter(post_hashtags::Column::PostId.eq(post.id.clone()))
+            .filter(post_hashtags::Column::HashtagId.is_in(removed))
+            .exec(db)
+            .await?;
+    }
+
+    let added: Vec<i32> = wanted.difference(&current).copied().collect();
+    if !added.is_empty() {
+        let placeholders = vec!["(?, ?)"; added.len()].join(", ");
+        let values: Vec<Value> = added
+            .into_iter()
+            .flat_map(|hashtag_id| [post.id.clone().into(), hashtag_id.into()])
+            .collect();
+
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::MySql,
+            format!("INSERT IGNORE INTO post_hashtags (post_id, hashtag_id) VALUES {}", placeholders),
+            values,
+        )).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_post_hashtags<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    post_hashtags::Entity::delete_many()
+        .filter(post_hashtags::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Posts tagged with any hashtag the user follows, as a filter on the id column of posts
+pub fn followed_hashtags_condition<C: ColumnTrait>(post_id_col: C, user_id: &[u8]) -> Condition {
+    let followed_hashtags = Query::select()
+        .column(hashtag_follows::Column::HashtagId)
+        .from(hashtag_follows::Entity)
+        .and_where(hashtag_follows::Column::UserId.eq(user_id.to_vec()))
+        .to_owned();
+
+    let tagged_posts = Query::select()
+        .column(post_hashtags::Column::PostId)
+        .from(post_hashtags::Entity)
+        .and_where(post_hashtags::Column::HashtagId.in_subquery(followed_hashtags))
+        .to_owned();
+
+    Condition::all().add(post_id_col.in_subquery(tagged_posts))
+}
+
+pub async fn follows_any_hashtag<C: ConnectionTrait>(db: &C, user_id: &[u8]) -> Result<bool> {
+    let follow = hashtag_follows::Entity::find()
+        .filter(hashtag_follows::Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?;
+
+    Ok(follow.is_some())
+}
+
+fn tagged_posts_condition(hashtag_id: i32) -> Condition {
+    let tagged_posts = Query::select()
+        .column(post_hashtags::Column::PostId)
+        .from(post_hashtags::Entity)
+        .and_where(post_hashtags::Column::HashtagId.eq(hashtag_id))
+        .to_owned();
+
+    Condition::all().add(posts::Column::Id.in_subquery(tagged_posts))
+}
+
+async fn count_hashtags_posts<C: ConnectionTrait>(db: &C, hashtag_ids: Vec<i32>) -> Result<HashMap<i32, u64>> {
+    let posts_counts = post_hashtags::Entity::find()
+        .select_only()
+        .column(post_hashtags::Column::HashtagId)
+        .column_as(Expr::col(post_hashtags::Column::Id).count(), "posts_count")
+        .filter(post_hashtags::Column::HashtagId.is_in(hashtag_ids))
+        .group_by(post_hashtags::Column::HashtagId)
+        .into_tuple::<(i32, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(hashtag_id, posts_count)| (hashtag_id, posts_count.max(0) as u64))
+        .collect();
+
+    Ok(posts_counts)
+}
+
+// The usage count includes posts the viewer cannot see, like the total shown on a hashtag page
+pub async fn get_hashtag(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, name: &str) -> Result<HashtagDetail> {
+    let hashtag = find_hashtag(db, name).await?;
+
+    let posts_count = count_hashtags_posts(db, vec![hashtag.id]).await?
+        .get(&hashtag.id)
+        .copied()
+        .unwrap_or_default();
+
+    let following = match viewer {
+        Some(viewer) => Some(
+            hashtag_follows::Entity::find()
+                .filter(hashtag_follows::Column::UserId.eq(viewer.get_user_id()))
+                .filter(hashtag_follows::Column::HashtagId.eq(hashtag.id))
+                .one(db)
+                .await?
+                .is_some()
+        ),
+        None => None,
+    };
+
+    Ok(HashtagDetail {
+        name: hashtag.name,
+        posts_count,
+        following,
+    })
+}
+
+// Most liked posts first, newer posts win ties
+pub async fn get_top_posts(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, name: &str) -> Result<Vec<PostDetail>> {
+    let hashtag = find_hashtag(db, name).await?;
+    let viewer_id = viewer.map(|v| v.get_user_id());
+
+    let posts = posts::Entity::find()
+        .filter(tagged_posts_condition(hashtag.id))
+        .filter(visible_authors_condition(posts::Column::UserId, viewer_id.as_deref()))
+        .order_by_desc(posts::Column::LikesCount)
+        .order_by_desc(posts::Column::CreatedAt)
+        .order_by_desc(posts::Column::Id)
+        .limit(TOP_POSTS_LIMIT)
+        .all(db)
+        .await?;
+
+    build_post_details(db, posts).await
+}
+
+pub async fn get_recent_posts(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, name: &str, query: &CursorQuery) -> Result<HashtagPostsPage> {
+    let hashtag = find_hashtag(db, name).await?;
+    let viewer_id = viewer.map(|v| v.get_user_id());
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut posts = posts::Entity::find()
+        .filter(tagged_posts_condition(hashtag.id))
+        .filter(visible_authors_condition(posts::Column::UserId, viewer_id.as_deref()))
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(posts::Column::CreatedAt, posts::Column::Id)))
+        .order_by_desc(posts::Column::CreatedAt)
+        .order_by_desc(posts::Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if posts.len() as u64 > limit {
+        posts.truncate(limit as usize);
+        posts.last().map(|post| Cursor::new(post.created_at, post.id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(HashtagPostsPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor,
+    })
+}
+
+// Following a hashtag nobody used yet is allowed, the hashtag is created on the way.
+// Following twice is not an error
+pub async fn follow_hashtag(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, name: &str) -> Result<()> {
+    let name = normalize_hashtag(name).ok_or_else(hashtag_not_found)?;
+    let hashtag_id = ensure_hashtags(db, &[name]).await?
+        .pop()
+        .ok_or_else(hashtag_not_found)?;
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        "INSERT IGNORE INTO hashtag_follows (user_id, hashtag_id) VALUES (?, ?)",
+        [jwt_payload.get_user_id().into(), hashtag_id.into()],
+    )).await?;
+
+    Ok(())
+}
+
+pub async fn unfollow_hashtag(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, name: &str) -> Result<()> {
+    let hashtag = find_hashtag(db, name).await?;
+
+    hashtag_follows::Entity::delete_many()
+        .filter(hashtag_follows::Column::UserId.eq(jwt_payload.get_user_id()))
+        .filter(hashtag_follows::Column::HashtagId.eq(hashtag.id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Most recently followed first
+pub async fn get_followed_hashtags(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<Vec<HashtagDetail>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let hashtags: Vec<Model> = hashtag_follows::Entity::find()
+        .find_also_related(Entity)
+        .filter(hashtag_follows::Column::UserId.eq(user.id))
+        .order_by_desc(hashtag_follows::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(_, hashtag)| hashtag)
+        .collect();
+
+    let posts_counts = count_hashtags_posts(db, hashtags.iter().map(|hashtag| hashtag.id).collect()).await?;
+
+    let hashtags = hashtags
+        .into_iter()
+        .map(|hashtag| HashtagDetail {
+            posts_count: posts_counts.get(&hashtag.id).copied().unwrap_or_default(),
+            name: hashtag.name,
+            following: Some(true),
+        })
+        .collect();
+
+    Ok(hashtags)
+}
+
+// Builds the detail view of many hashtags, `following` is only known for an authenticated viewer
+pub async fn build_hashtag_details<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, hashtags: Vec<Model>) -> Result<Vec<HashtagDetail>> {
+    let hashtag_ids: Vec<i32> = hashtags.iter().map(|hashtag| hashtag.id).collect();
+
+    let posts_counts = count_hashtags_posts(db, hashtag_ids.clone()).await?;
+
+    let followed: HashSet<i32> = match viewer_id {
+        Some(viewer_id) => hashtag_follows::Entity::find()
+            .select_only()
+            .column(hashtag_follows::Column::HashtagId)
+            .filter(hashtag_follows::Column::UserId.eq(viewer_id.to_vec()))
+            .filter(hashtag_follows::Column::HashtagId.is_in(hashtag_ids))
+            .into_tuple()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    let hashtags = hashtags
+        .into_iter()
+        .map(|hashtag| HashtagDetail {
+            posts_count: posts_counts.get(&hashtag.id).copied().unwrap_or_default(),
+            following: viewer_id.map(|_| followed.contains(&hashtag.id)),
+            name: hashtag.name,
+        })
+        .collect();
+
+    Ok(hashtags)
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use crate::posts::PostDetail;
+
+pub mod hashtags_service;
+pub mod hashtags_controller;
+pub mod hashtags_routes;
+
+// ---- HASHTAGS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashtagDetail {
+    pub name: String,
+    pub posts_count: u64,
+    // Only known when the request is authenticated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub following: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashtagPostsPage {
+    pub posts: Vec<PostDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF HASHTAGS STRUCTS ----
@@ -0,0 +1,144 @@
+use actix_web::{get, post, patch, put, delete, HttpResponse, web::{Data, Json, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::highlights::{AddHighlightItemsPayload, CreateHighlightPayload, ReorderHighlightItemsPayload, ReorderHighlightsPayload, UpdateHighlightPayload};
+use crate::utils::validate_data;
+use crate::Result;
+use super::highlights_service::{add_highlight_items, create_highlight, delete_highlight, get_highlight, get_highlights, remove_highlight_item, reorder_highlight_items, reorder_highlights, update_highlight};
+
+#[get("")]
+pub async fn get_highlights_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let highlights = get_highlights(&ctx.db, viewer.as_ref(), &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": highlights
+        })
+    ))
+}
+
+#[post("")]
+pub async fn create_highlight_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateHighlightPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let highlight = create_highlight(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": highlight
+        })
+    ))
+}
+
+#[put("/order")]
+pub async fn reorder_highlights_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<ReorderHighlightsPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    reorder_highlights(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("/{highlight_id}")]
+pub async fn get_highlight_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, highlight_id) = path.into_inner();
+
+    let highlight = get_highlight(&ctx.db, viewer.as_ref(), &username, &highlight_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": highlight
+        })
+    ))
+}
+
+#[patch("/{highlight_id}")]
+pub async fn update_highlight_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<UpdateHighlightPayload>) -> Result<HttpResponse> {
+    let (username, highlight_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    update_highlight(&ctx.db, &ctx.storage, &jwt_payload, &username, &highlight_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{highlight_id}")]
+pub async fn delete_highlight_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, highlight_id) = path.into_inner();
+
+    delete_highlight(&ctx.db, &ctx.storage, &jwt_payload, &username, &highlight_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[post("/{highlight_id}/items")]
+pub async fn add_highlight_items_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<AddHighlightItemsPayload>) -> Result<HttpResponse> {
+    let (username, highlight_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let highlight = add_highlight_items(&ctx.db, &jwt_payload, &username, &highlight_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": highlight
+        })
+    ))
+}
+
+#[put("/{highlight_id}/items/order")]
+pub async fn reorder_highlight_items_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<ReorderHighlightItemsPayload>) -> Result<HttpResponse> {
+    let (username, highlight_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    reorder_highlight_items(&ctx.db, &jwt_payload, &username, &highlight_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{highlight_id}/items/{item_id}")]
+pub async fn remove_highlight_item_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, highlight_id, item_id) = path.into_inner();
+
+    remove_highlight_item(&ctx.db, &ctx.storage, &jwt_payload, &username, &highlight_id, &item_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
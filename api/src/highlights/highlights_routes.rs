@@ -0,0 +1,15 @@
+use actix_web::web::ServiceConfig;
+
+use super::highlights_controller::{add_highlight_items_handler, create_highlight_handler, delete_highlight_handler, get_highlight_handler, get_highlights_handler, remove_highlight_item_handler, reorder_highlight_items_handler, reorder_highlights_handler, update_highlight_handler};
+
+pub fn get_highlights_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_highlights_handler)
+        .service(create_highlight_handler)
+        .service(reorder_highlights_handler)
+        .service(get_highlight_handler)
+        .service(update_highlight_handler)
+        .service(delete_highlight_handler)
+        .service(add_highlight_items_handler)
+        .service(reorder_highlight_items_handler)
+        .service(remove_highlight_item_handler);
+}
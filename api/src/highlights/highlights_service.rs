@@ -0,0 +1,472 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use chrono::Utc;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use uuid::Uuid;
+use entity::highlights::{Entity, Column, Model, ActiveModel};
+use entity::{highlight_items, stories};
+use crate::auth::JwtTokenPayload;
+use crate::error::HttpResponseError;
+use crate::highlights::{AddHighlightItemsPayload, CreateHighlightPayload, HighlightDetail, HighlightItemDetail, HighlightSummary, ReorderHighlightItemsPayload, ReorderHighlightsPayload, UpdateHighlightPayload};
+use crate::media::media_service::{delete_unreferenced_uploads, is_uploaded_by};
+use crate::users::users_service::{ensure_can_view_content, ensure_owner, find_user_by_username};
+use crate::utils::storage::MediaStorage;
+use crate::Result;
+
+pub const MAX_HIGHLIGHT_ITEMS: u64 = 100;
+
+const HIGHLIGHT_NOT_FOUND_ERROR: &str = "Highlight not found";
+const HIGHLIGHT_ITEM_NOT_FOUND_ERROR: &str = "Highlight item not found";
+
+fn highlight_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(HIGHLIGHT_NOT_FOUND_ERROR)
+}
+
+fn highlight_item_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(HIGHLIGHT_ITEM_NOT_FOUND_ERROR)
+}
+
+fn bad_request(message: &'static str) -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::BAD_REQUEST.as_u16())
+        .set_error_message(message)
+}
+
+pub async fn find_user_highlight<C: ConnectionTrait>(db: &C, user_id: &[u8], highlight_id: &str) -> Result<Model> {
+    let highlight_id = Uuid::from_str(highlight_id).map_err(|_| highlight_not_found())?;
+
+    let highlight = Entity::find_by_id(highlight_id)
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?;
+
+    highlight.ok_or_else(highlight_not_found)
+}
+
+// A cover is either the media of one of the highlight's stories or a file the owner uploaded
+async fn ensure_valid_cover<C: ConnectionTrait>(db: &C, user_id: &[u8], story_file_urls: &[String], cover_url: &str) -> Result<()> {
+    if story_file_urls.iter().any(|file_url| file_url == cover_url) || is_uploaded_by(db, user_id, cover_url).await? {
+        return Ok(());
+    }
+
+    Err(bad_request("The cover must be one of the highlight's stories or a file you uploaded"))
+}
+
+async fn get_highlight_items<C: ConnectionTrait>(db: &C, highlight_id: &[u8]) -> Result<Vec<highlight_items::Model>> {
+    let items = highlight_items::Entity::find()
+        .filter(highlight_items::Column::HighlightId.eq(highlight_id.to_vec()))
+        .order_by_asc(highlight_items::Column::Position)
+        .order_by_asc(highlight_items::Column::Id)
+        .all(db)
+        .await?;
+
+    Ok(items)
+}
+
+async fn build_highlight_detail<C: ConnectionTrait>(db: &C, highlight: Model) -> Result<HighlightDetail> {
+    let items = get_highlight_items(db, &highlight.id).await?
+        .into_iter()
+        .map(|item| HighlightItemDetail {
+            id: item.id,
+            story_id: Uuid::from_slice(&item.story_id).unwrap().to_string(),
+            file_url: item.file_url,
+            created_at: item.story_created_at,
+        })
+        .collect();
+
+    Ok(HighlightDetail {
+        id: Uuid::from_slice(&highlight.id).unwrap().to_string(),
+        title: highlight.title,
+        cover_url: highlight.cover_url,
+        items,
+        created_at: highlight.created_at,
+        updated_at: highlight.updated_at,
+    })
+}
+
+// Expired stories resolve too, the sweeper keeps the ones already pinned to a highlight.
+// The stories are read with a shared lock, so the expiry sweeper cannot remove them
+// (and their media) while they are being copied into the highlight
+async fn find_owner_stories<C: ConnectionTrait>(db: &C, user_id: &[u8], story_ids: &[String]) -> Result<Vec<stories::Model>> {
+    let story_ids: HashSet<Vec<u8>> = story_ids
+        .iter()
+        .filter_map(|story_id| Uuid::from_str(story_id).ok())
+        .map(Vec::from)
+        .collect();
+
+    let stories = stories::Entity::find()
+        .filter(stories::Column::Id.is_in(story_ids.clone()))
+        .filter(stories::Column::UserId.eq(user_id.to_vec()))
+        .order_by_asc(stories::Column::CreatedAt)
+        .order_by_asc(stories::Column::Id)
+        .lock_shared()
+        .all(db)
+        .await?;
+
+    if stories.len() != story_ids.len() {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::NOT_FOUND.as_u16())
+                .set_error_message("Story not found")
+        );
+    }
+
+    Ok(stories)
+}
+
+async fn next_item_position<C: ConnectionTrait>(db: &C, highlight_id: &[u8]) -> Result<i32> {
+    let max_position: Option<Option<i32>> = highlight_items::Entity::find()
+        .select_only()
+        .column_as(highlight_items::Column::Position.max(), "max_position")
+        .filter(highlight_items::Column::HighlightId.eq(highlight_id.to_vec()))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(max_position.flatten().map_or(0, |position| position + 1))
+}
+
+// Stories already in the highlight are skipped, new ones go to the end
+async fn insert_highlight_items<C: ConnectionTrait>(db: &C, highlight_id: &[u8], stories: Vec<stories::Model>) -> Result<()> {
+    let existing: HashSet<Vec<u8>> = get_highlight_items(db, highlight_id).await?
+        .into_iter()
+        .map(|item| item.story_id)
+        .collect();
+
+    let stories: Vec<stories::Model> = stories.into_iter().filter(|story| !existing.contains(&story.id)).collect();
+
+    if stories.is_empty() {
+        return Ok(());
+    }
+
+    if (existing.len() + stories.len()) as u64 > MAX_HIGHLIGHT_ITEMS {
+        return Err(bad_request("A highlight can have at most 100 stories"));
+    }
+
+    let first_position = next_item_position(db, highlight_id).await?;
+
+    let items = stories
+        .into_iter()
+        .zip(first_position..)
+        .map(|(story, position)| highlight_items::ActiveModel {
+            highlight_id: Set(highlight_id.to_vec()),
+            story_id: Set(story.id),
+            file_url: Set(story.file_url),
+            story_created_at: Set(story.created_at),
+            position: Set(position),
+            ..Default::default()
+        });
+
+    highlight_items::Entity::insert_many(items)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+async fn touch_highlight<C: ConnectionTrait>(db: &C, highlight_id: &[u8]) -> Result<()> {
+    Entity::update_many()
+        .col_expr(Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(Column::Id.eq(highlight_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn create_highlight(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: CreateHighlightPayload) -> Result<HighlightDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let txn = db.begin().await?;
+
+    let stories = find_owner_stories(&txn, &user.id, &data.story_ids.unwrap()).await?;
+
+    // New highlights go to the end of the profile
+    let max_position: Option<Option<i32>> = Entity::find()
+        .select_only()
+        .column_as(Column::Position.max(), "max_position")
+        .filter(Column::UserId.eq(user.id.clone()))
+        .into_tuple()
+        .one(&txn)
+        .await?;
+
+    let cover_url = match data.cover_url {
+        Some(cover_url) => {
+            let story_file_urls: Vec<String> = stories.iter().map(|story| story.file_url.clone()).collect();
+            ensure_valid_cover(&txn, &user.id, &story_file_urls, &cover_url).await?;
+            cover_url
+        },
+        None => stories.first().map(|story| story.file_url.clone()).unwrap_or_default(),
+    };
+
+    let highlight = ActiveModel {
+        id: Set(Vec::from(Uuid::new_v4())),
+        user_id: Set(user.id),
+        title: Set(data.title.unwrap()),
+        cover_url: Set(cover_url),
+        position: Set(max_position.flatten().map_or(0, |position| position + 1)),
+        ..Default::default()
+    }.insert(&txn).await?;
+
+    insert_highlight_items(&txn, &highlight.id, stories).await?;
+
+    txn.commit().await?;
+
+    build_highlight_detail(db, highlight).await
+}
+
+pub async fn get_highlights(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str) -> Result<Vec<HighlightSummary>> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let highlights = Entity::find()
+        .filter(Column::UserId.eq(user.id))
+        .order_by_asc(Column::Position)
+        .order_by_asc(Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let items_counts: HashMap<Vec<u8>, i64> = highlight_items::Entity::find()
+        .select_only()
+        .column(highlight_items::Column::HighlightId)
+        .column_as(Expr::col(highlight_items::Column::Id).count(), "items_count")
+        .filter(highlight_items::Column::HighlightId.is_in(highlights.iter().map(|highlight| highlight.id.clone())))
+        .group_by(highlight_items::Column::HighlightId)
+        .into_tuple::<(Vec<u8>, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let highlights = highlights
+        .into_iter()
+        .map(|highlight| HighlightSummary {
+            items_count: items_counts.get(&highlight.id).copied().unwrap_or_default().max(0) as u64,
+            id: Uuid::from_slice(&highlight.id).unwrap().to_string(),
+            title: highlight.title,
+            cover_url: highlight.cover_url,
+            created_at: highlight.created_at,
+            updated_at: highlight.updated_at,
+        })
+        .collect();
+
+    Ok(highlights)
+}
+
+pub async fn get_highlight(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, highlight_id: &str) -> Result<HighlightDetail> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    build_highlight_detail(db, highlight).await
+}
+
+pub async fn update_highlight(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, highlight_id: &str, data: UpdateHighlightPayload) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let old_cover_url = highlight.cover_url.clone();
+
+    if let Some(cover_url) = data.cover_url.as_deref().filter(|cover_url| *cover_url != old_cover_url) {
+        let story_file_urls: Vec<String> = get_highlight_items(db, &highlight.id).await?
+            .into_iter()
+            .map(|item| item.file_url)
+            .collect();
+        ensure_valid_cover(db, &user.id, &story_file_urls, cover_url).await?;
+    }
+
+    let mut highlight: ActiveModel = highlight.into();
+    if let Some(title) = data.title {
+        highlight.title = Set(title);
+    }
+    if let Some(cover_url) = data.cover_url.clone() {
+        highlight.cover_url = Set(cover_url);
+    }
+    highlight.updated_at = Set(Utc::now());
+
+    highlight.update(db).await?;
+
+    // The replaced cover may have been uploaded just for this highlight
+    if data.cover_url.is_some_and(|cover_url| cover_url != old_cover_url) {
+        delete_unreferenced_uploads(db, storage, &user.id, vec![old_cover_url]).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_highlight(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, highlight_id: &str) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let txn = db.begin().await?;
+
+    let mut file_urls: Vec<String> = get_highlight_items(&txn, &highlight.id).await?
+        .into_iter()
+        .map(|item| item.file_url)
+        .collect();
+    file_urls.push(highlight.cover_url);
+
+    highlight_items::Entity::delete_many()
+        .filter(highlight_items::Column::HighlightId.eq(highlight.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    Entity::delete_by_id(highlight.id)
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    delete_unreferenced_uploads(db, storage, &user.id, file_urls).await
+}
+
+pub async fn add_highlight_items(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, highlight_id: &str, data: AddHighlightItemsPayload) -> Result<HighlightDetail> {
+    let user = find_user_by_username(db, username).await?;
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let txn = db.begin().await?;
+
+    let stories = find_owner_stories(&txn, &user.id, &data.story_ids.unwrap()).await?;
+    insert_highlight_items(&txn, &highlight.id, stories).await?;
+    touch_highlight(&txn, &highlight.id).await?;
+
+    txn.commit().await?;
+
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    build_highlight_detail(db, highlight).await
+}
+
+pub async fn remove_highlight_item(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, highlight_id: &str, item_id: &str) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let item_id = i32::from_str(item_id).map_err(|_| highlight_item_not_found())?;
+
+    let item = highlight_items::Entity::find_by_id(item_id)
+        .filter(highlight_items::Column::HighlightId.eq(highlight.id.clone()))
+        .one(db)
+        .await?
+        .ok_or_else(highlight_item_not_found)?;
+
+    let txn = db.begin().await?;
+
+    highlight_items::Entity::delete_by_id(item.id)
+        .exec(&txn)
+        .await?;
+
+    touch_highlight(&txn, &highlight.id).await?;
+
+    txn.commit().await?;
+
+    delete_unreferenced_uploads(db, storage, &user.id, vec![item.file_url]).await
+}
+
+// Positions are rewritten from the given order, which has to list every entry exactly once
+fn ensure_complete_order<T: Eq + std::hash::Hash>(current: HashSet<T>, ordered: &[T], message: &'static str) -> Result<()> {
+    let ordered_set: HashSet<&T> = ordered.iter().collect();
+
+    if ordered.len() != current.len() || ordered_set.len() != ordered.len() || !ordered.iter().all(|id| current.contains(id)) {
+        return Err(bad_request(message));
+    }
+
+    Ok(())
+}
+
+pub async fn reorder_highlights(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: ReorderHighlightsPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let highlight_ids: Vec<Vec<u8>> = data.highlight_ids.unwrap()
+        .iter()
+        .filter_map(|highlight_id| Uuid::from_str(highlight_id).ok())
+        .map(Vec::from)
+        .collect();
+
+    let txn = db.begin().await?;
+
+    let current: HashSet<Vec<u8>> = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::UserId.eq(user.id))
+        .lock_exclusive()
+        .into_tuple()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .collect();
+
+    ensure_complete_order(current, &highlight_ids, "Every highlight has to be listed exactly once")?;
+
+    for (position, highlight_id) in (0..).zip(highlight_ids) {
+        Entity::update_many()
+            .col_expr(Column::Position, Expr::value(position))
+            .filter(Column::Id.eq(highlight_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn reorder_highlight_items(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, highlight_id: &str, data: ReorderHighlightItemsPayload) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let highlight = find_user_highlight(db, &user.id, highlight_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let item_ids = data.item_ids.unwrap();
+
+    let txn = db.begin().await?;
+
+    let current: HashSet<i32> = highlight_items::Entity::find()
+        .select_only()
+        .column(highlight_items::Column::Id)
+        .filter(highlight_items::Column::HighlightId.eq(highlight.id.clone()))
+        .lock_exclusive()
+        .into_tuple()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .collect();
+
+    ensure_complete_order(current, &item_ids, "Every item of the highlight has to be listed exactly once")?;
+
+    for (position, item_id) in (0..).zip(item_ids) {
+        highlight_items::Entity::update_many()
+            .col_expr(highlight_items::Column::Position, Expr::value(position))
+            .filter(highlight_items::Column::Id.eq(item_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    touch_highlight(&txn, &highlight.id).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::utils::check_valid_uuids;
+
+pub mod highlights_service;
+pub mod highlights_controller;
+pub mod highlights_routes;
+
+// ---- HIGHLIGHTS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightSummary {
+    pub id: String,
+    pub title: String,
+    pub cover_url: String,
+    pub items_count: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightItemDetail {
+    pub id: i32,
+    pub story_id: String,
+    pub file_url: String,
+    // When the original story was posted
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightDetail {
+    pub id: String,
+    pub title: String,
+    pub cover_url: String,
+    pub items: Vec<HighlightItemDetail>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---- END OF HIGHLIGHTS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateHighlightPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 30,
+    message = "Title must be between 1 and 30 characters"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub title: Option<String>,
+
+    // Defaults to the media of the first story
+    #[serde(rename = "coverUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "Cover URL cannot be empty"
+    )
+    )]
+    pub cover_url: Option<String>,
+
+    #[serde(rename = "storyIds")]
+    #[validate(
+    length(
+    min = 1,
+    max = 100,
+    message = "A highlight must have between 1 and 100 stories"
+    ),
+    custom(
+    function = "check_valid_uuids",
+    message = "Story ids must be valid UUIDs"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub story_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateHighlightPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 30,
+    message = "Title must be between 1 and 30 characters"
+    )
+    )]
+    pub title: Option<String>,
+
+    #[serde(rename = "coverUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "Cover URL cannot be empty"
+    )
+    )]
+    pub cover_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AddHighlightItemsPayload {
+    #[serde(rename = "storyIds")]
+    #[validate(
+    length(
+    min = 1,
+    max = 100,
+    message = "Between 1 and 100 stories can be added at once"
+    ),
+    custom(
+    function = "check_valid_uuids",
+    message = "Story ids must be valid UUIDs"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub story_ids: Option<Vec<String>>,
+}
+
+// The full list in the new order, nothing may be left out
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReorderHighlightsPayload {
+    #[serde(rename = "highlightIds")]
+    #[validate(
+    custom(
+    function = "check_valid_uuids",
+    message = "Highlight ids must be valid UUIDs"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub highlight_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReorderHighlightItemsPayload {
+    #[serde(rename = "itemIds")]
+    #[validate(required(message = "This field is required"))]
+    pub item_ids: Option<Vec<i32>>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
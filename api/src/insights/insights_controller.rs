@@ -0,0 +1,42 @@
+use actix_web::{get, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::validate_data;
+use crate::Result;
+use super::InsightsQuery;
+use super::insights_service::{get_account_insights, get_post_insights};
+
+#[get("")]
+pub async fn get_post_insights_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, query: Query<InsightsQuery>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let insights = get_post_insights(&ctx.db, &jwt_payload, &username, &post_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": insights
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_account_insights_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<InsightsQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let insights = get_account_insights(&ctx.db, &jwt_payload, &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": insights
+        })
+    ))
+}
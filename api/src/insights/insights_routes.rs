@@ -0,0 +1,11 @@
+use actix_web::web::ServiceConfig;
+
+use super::insights_controller::{get_account_insights_handler, get_post_insights_handler};
+
+pub fn get_post_insights_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_post_insights_handler);
+}
+
+pub fn get_account_insights_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_account_insights_handler);
+}
@@ -0,0 +1,319 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use futures::lock::Mutex;
+use sea_orm::{Set, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use sea_orm::sea_query::{Expr, OnConflict, Query, SelectStatement};
+use entity::{bookmarks, follows, post_comments, post_impressions, post_insights, post_likes, post_viewers, posts};
+use crate::auth::JwtTokenPayload;
+use crate::insights::{AccountInsights, HourlyInsights, Impressions, InsightsQuery, PostInsights};
+use crate::posts::posts_service::{count_post_comments, find_user_post};
+use crate::users::users_service::{ensure_owner, find_user_by_username};
+use crate::Result;
+
+// Impressions folded into the buckets by each transaction of the job
+const AGGREGATION_BATCH_SIZE: u64 = 5000;
+
+// Runs overlapping would count the same impressions twice
+static AGGREGATION_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+// A post and an account that saw it
+type PostViewer = (Vec<u8>, Vec<u8>);
+
+fn hour_of(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(Duration::hours(1)).unwrap_or(at)
+}
+
+fn posts_of(user_id: &[u8]) -> SelectStatement {
+    Query::select()
+        .column(posts::Column::Id)
+        .from(posts::Entity)
+        .and_where(posts::Column::UserId.eq(user_id.to_vec()))
+        .to_owned()
+}
+
+pub async fn record_impressions(db: &DatabaseConnection, impressions: Impressions) -> Result<()> {
+    let rows = impressions.post_ids
+        .into_iter()
+        .map(|post_id| post_impressions::ActiveModel {
+            post_id: Set(post_id),
+            viewer_id: Set(impressions.viewer_id.clone()),
+            source: Set(impressions.source.as_str().to_string()),
+            ..Default::default()
+        });
+
+    post_impressions::Entity::insert_many(rows)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Folds the oldest logged impressions into the hourly buckets and returns how many were consumed
+async fn aggregate_impressions_batch(db: &DatabaseConnection) -> Result<u64> {
+    let impressions = post_impressions::Entity::find()
+        .order_by_asc(post_impressions::Column::Id)
+        .limit(AGGREGATION_BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    let Some(last_id) = impressions.last().map(|impression| impression.id) else {
+        return Ok(0);
+    };
+    let consumed_count = impressions.len() as u64;
+
+    let post_ids: HashSet<Vec<u8>> = impressions.iter().map(|impression| impression.post_id.clone()).collect();
+    let authors: HashMap<Vec<u8>, Vec<u8>> = posts::Entity::find()
+        .select_only()
+        .column(posts::Column::Id)
+        .column(posts::Column::UserId)
+        .filter(posts::Column::Id.is_in(post_ids))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut buckets: HashMap<(Vec<u8>, DateTime<Utc>), (i32, i32)> = HashMap::new();
+    let mut first_seen: HashMap<PostViewer, DateTime<Utc>> = HashMap::new();
+
+    for impression in impressions {
+        // Deleted posts and authors looking at their own posts don't count
+        let Some(author_id) = authors.get(&impression.post_id) else {
+            continue;
+        };
+        if impression.viewer_id.as_ref() == Some(author_id) {
+            continue;
+        }
+
+        buckets.entry((impression.post_id.clone(), hour_of(impression.created_at))).or_default().0 += 1;
+
+        if let Some(viewer_id) = impression.viewer_id {
+            first_seen.entry((impression.post_id, viewer_id))
+                .and_modify(|seen_at| *seen_at = (*seen_at).min(impression.created_at))
+                .or_insert(impression.created_at);
+        }
+    }
+
+    let txn = db.begin().await?;
+
+    // Only viewers who never saw the post before add to its reach
+    if !first_seen.is_empty() {
+        let known_viewers: HashSet<PostViewer> = post_viewers::Entity::find()
+            .filter(post_viewers::Column::PostId.is_in(first_seen.keys().map(|(post_id, _)| post_id.clone())))
+            .filter(post_viewers::Column::ViewerId.is_in(first_seen.keys().map(|(_, viewer_id)| viewer_id.clone())))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|viewer| (viewer.post_id, viewer.viewer_id))
+            .collect();
+
+        let new_viewers: Vec<(PostViewer, DateTime<Utc>)> = first_seen
+            .into_iter()
+            .filter(|(key, _)| !known_viewers.contains(key))
+            .collect();
+
+        for ((post_id, _), seen_at) in &new_viewers {
+            buckets.entry((post_id.clone(), hour_of(*seen_at))).or_default().1 += 1;
+        }
+
+        if !new_viewers.is_empty() {
+            post_viewers::Entity::insert_many(
+                new_viewers
+                    .into_iter()
+                    .map(|((post_id, viewer_id), first_seen_at)| post_viewers::ActiveModel {
+                        post_id: Set(post_id),
+                        viewer_id: Set(viewer_id),
+                        first_seen_at: Set(first_seen_at),
+                    })
+            )
+                .exec_without_returning(&txn)
+                .await?;
+        }
+    }
+
+    for ((post_id, bucket_start), (impressions, reach)) in buckets {
+        post_insights::Entity::insert(post_insights::ActiveModel {
+            post_id: Set(post_id),
+            bucket_start: Set(bucket_start),
+            impressions: Set(impressions),
+            reach: Set(reach),
+        })
+            .on_conflict(
+                OnConflict::columns([post_insights::Column::PostId, post_insights::Column::BucketStart])
+                    .value(post_insights::Column::Impressions, Expr::col(post_insights::Column::Impressions).add(impressions))
+                    .value(post_insights::Column::Reach, Expr::col(post_insights::Column::Reach).add(reach))
+                    .to_owned()
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+
+    post_impressions::Entity::delete_many()
+        .filter(post_impressions::Column::Id.lte(last_id))
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(consumed_count)
+}
+
+// Empties the impressions log into the hourly buckets and returns how many impressions were consumed
+pub async fn aggregate_impressions(db: &DatabaseConnection) -> Result<u64> {
+    let _guard = AGGREGATION_LOCK.lock().await;
+
+    let mut consumed_count = 0;
+
+    loop {
+        let batch_count = aggregate_impressions_batch(db).await?;
+        consumed_count += batch_count;
+
+        if batch_count < AGGREGATION_BATCH_SIZE {
+            return Ok(consumed_count);
+        }
+    }
+}
+
+// Impressions and reach summed over the buckets matching the condition
+async fn sum_insights<C: ConnectionTrait>(db: &C, condition: Condition) -> Result<(u64, u64)> {
+    let totals: Option<(i64, i64)> = post_insights::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("CAST(COALESCE(SUM(impressions), 0) AS SIGNED)"), "impressions")
+        .column_as(Expr::cust("CAST(COALESCE(SUM(reach), 0) AS SIGNED)"), "reach")
+        .filter(condition)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    let (impressions, reach) = totals.unwrap_or_default();
+
+    Ok((impressions.max(0) as u64, reach.max(0) as u64))
+}
+
+// Lifetime totals, the hourly breakdown only covers the last `days` days
+pub async fn get_post_insights(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, query: &InsightsQuery) -> Result<PostInsights> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+    let since = hour_of(Utc::now() - Duration::days(query.get_days()));
+
+    let (impressions, reach) = sum_insights(db, Condition::all().add(post_insights::Column::PostId.eq(post.id.clone()))).await?;
+
+    let likes = post_likes::Entity::find()
+        .filter(post_likes::Column::PostId.eq(post.id.clone()))
+        .count(db)
+        .await?;
+
+    let saves = bookmarks::Entity::find()
+        .filter(bookmarks::Column::PostId.eq(post.id.clone()))
+        .count(db)
+        .await?;
+
+    let hourly = post_insights::Entity::find()
+        .filter(post_insights::Column::PostId.eq(post.id.clone()))
+        .filter(post_insights::Column::BucketStart.gte(since))
+        .order_by_asc(post_insights::Column::BucketStart)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|bucket| HourlyInsights {
+            hour: bucket.bucket_start,
+            impressions: bucket.impressions.max(0) as u64,
+            reach: bucket.reach.max(0) as u64,
+        })
+        .collect();
+
+    Ok(PostInsights {
+        impressions,
+        reach,
+        likes,
+        comments: count_post_comments(db, &post.id).await?,
+        saves,
+        hourly,
+    })
+}
+
+// Everything that happened to the account and its posts during the last `days` days
+pub async fn get_account_insights(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, query: &InsightsQuery) -> Result<AccountInsights> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let days = query.get_days();
+    let since = Utc::now() - Duration::days(days);
+
+    let (impressions, _) = sum_insights(db, Condition::all()
+        .add(post_insights::Column::PostId.in_subquery(posts_of(&user.id)))
+        .add(post_insights::Column::BucketStart.gte(hour_of(since)))
+    ).await?;
+
+    // The reach of each post can't be added up, the same account may have seen several of them
+    let reach: Option<i64> = post_viewers::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("COUNT(DISTINCT viewer_id)"), "reach")
+        .filter(post_viewers::Column::PostId.in_subquery(posts_of(&user.id)))
+        .filter(post_viewers::Column::FirstSeenAt.gte(since))
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    let likes = post_likes::Entity::find()
+        .filter(post_likes::Column::PostId.in_subquery(posts_of(&user.id)))
+        .filter(post_likes::Column::CreatedAt.gte(since))
+        .count(db)
+        .await?;
+
+    let comments = post_comments::Entity::find()
+        .filter(post_comments::Column::PostId.in_subquery(posts_of(&user.id)))
+        .filter(post_comments::Column::CreatedAt.gte(since))
+        .count(db)
+        .await?;
+
+    let saves = bookmarks::Entity::find()
+        .filter(bookmarks::Column::PostId.in_subquery(posts_of(&user.id)))
+        .filter(bookmarks::Column::CreatedAt.gte(since))
+        .count(db)
+        .await?;
+
+    let followers_count = follows::Entity::find()
+        .filter(follows::Column::FolloweeId.eq(user.id.clone()))
+        .count(db)
+        .await?;
+
+    let follower_growth = follows::Entity::find()
+        .filter(follows::Column::FolloweeId.eq(user.id.clone()))
+        .filter(follows::Column::CreatedAt.gte(since))
+        .count(db)
+        .await?;
+
+    Ok(AccountInsights {
+        days,
+        impressions,
+        reach: reach.unwrap_or_default().max(0) as u64,
+        likes,
+        comments,
+        saves,
+        followers_count,
+        follower_growth,
+    })
+}
+
+pub async fn delete_post_insights<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    post_impressions::Entity::delete_many()
+        .filter(post_impressions::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    post_viewers::Entity::delete_many()
+        .filter(post_viewers::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    post_insights::Entity::delete_many()
+        .filter(post_insights::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
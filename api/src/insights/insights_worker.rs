@@ -0,0 +1,43 @@
+use std::time::Duration;
+use actix_web::rt::time::{interval_at, Instant};
+use futures::channel::mpsc;
+use futures::StreamExt;
+use sea_orm::DatabaseConnection;
+use crate::insights::{Impressions, ImpressionsQueue};
+use super::insights_service::{aggregate_impressions, record_impressions};
+
+pub const AGGREGATION_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+// Start the background task that logs impressions and return the queue feeding it.
+// Serving posts doesn't wait for the write
+pub fn spawn_impressions_recorder(db: DatabaseConnection) -> ImpressionsQueue {
+    let (sender, mut receiver) = mpsc::unbounded::<Impressions>();
+
+    actix_web::rt::spawn(async move {
+        while let Some(impressions) = receiver.next().await {
+            if let Err(e) = record_impressions(&db, impressions).await {
+                tracing::error!("Failed to record impressions: {:?}", e);
+            }
+        }
+    });
+
+    ImpressionsQueue::new(sender)
+}
+
+// The buckets of the current hour keep growing until it is over,
+// so the insights are never more than one interval behind
+pub fn spawn_insights_aggregation(db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let mut interval = interval_at(Instant::now() + AGGREGATION_INTERVAL, AGGREGATION_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match aggregate_impressions(&db).await {
+                Ok(0) => {},
+                Ok(consumed_count) => tracing::info!("Aggregated {} post impressions", consumed_count),
+                Err(e) => tracing::error!("Failed to aggregate post impressions: {:?}", e),
+            }
+        }
+    });
+}
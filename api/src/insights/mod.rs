@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use futures::channel::mpsc::UnboundedSender;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub mod insights_service;
+pub mod insights_controller;
+pub mod insights_routes;
+pub mod insights_worker;
+
+// Days covered by the insights when the query doesn't say
+pub const DEFAULT_INSIGHTS_DAYS: i64 = 7;
+
+// ---- INSIGHTS STRUCTS ----
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImpressionSource {
+    Feed,
+    Explore,
+    Profile,
+}
+
+impl ImpressionSource {
+    // Stored in `post_impressions.source`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImpressionSource::Feed => "feed",
+            ImpressionSource::Explore => "explore",
+            ImpressionSource::Profile => "profile",
+        }
+    }
+}
+
+// Posts served together in one response
+#[derive(Debug, Clone)]
+pub struct Impressions {
+    pub viewer_id: Option<Vec<u8>>,
+    pub post_ids: Vec<Vec<u8>>,
+    pub source: ImpressionSource,
+}
+
+// Handle used by the request handlers to log impressions without waiting for the write
+#[derive(Debug, Clone)]
+pub struct ImpressionsQueue {
+    sender: UnboundedSender<Impressions>,
+}
+
+impl ImpressionsQueue {
+    pub fn new(sender: UnboundedSender<Impressions>) -> Self {
+        Self { sender }
+    }
+
+    // Insights are statistics, a lost batch is logged and not surfaced to the user
+    pub fn record(&self, viewer_id: Option<Vec<u8>>, post_ids: Vec<Vec<u8>>, source: ImpressionSource) {
+        if post_ids.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self.sender.unbounded_send(Impressions { viewer_id, post_ids, source }) {
+            tracing::error!("Failed to record impressions: {:?}", e);
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourlyInsights {
+    pub hour: DateTime<Utc>,
+    pub impressions: u64,
+    // Accounts that saw the post for the first time during that hour
+    pub reach: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostInsights {
+    pub impressions: u64,
+    pub reach: u64,
+    pub likes: u64,
+    pub comments: u64,
+    pub saves: u64,
+    // Oldest first, hours without any impression are left out
+    pub hourly: Vec<HourlyInsights>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInsights {
+    pub days: i64,
+    pub impressions: u64,
+    // Distinct accounts that saw one of the posts for the first time during the period
+    pub reach: u64,
+    pub likes: u64,
+    pub comments: u64,
+    pub saves: u64,
+    pub followers_count: u64,
+    // Followers gained during the period
+    pub follower_growth: u64,
+}
+
+// ---- END OF INSIGHTS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct InsightsQuery {
+    #[validate(range(min = 1, max = 90, message = "Days must be between 1 and 90"))]
+    pub days: Option<i64>,
+}
+
+impl InsightsQuery {
+    pub fn get_days(&self) -> i64 {
+        self.days.unwrap_or(DEFAULT_INSIGHTS_DAYS)
+    }
+}
+
+// ---- END OF REQUEST PAYLOAD ----
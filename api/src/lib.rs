@@ -1,15 +1,49 @@
+use std::sync::Arc;
 use sea_orm::DatabaseConnection;
 use crate::configuration::Settings;
 use crate::error::HttpResponseError;
+use crate::events::EventBus;
+use crate::insights::ImpressionsQueue;
+use crate::realtime::Broker;
+use crate::timeline::TimelineQueue;
+use crate::utils::storage::MediaStorage;
 
 pub mod configuration;
 pub mod app;
 pub mod db;
 pub mod utils;
 pub mod error;
+pub mod events;
 
 // ----- Domain -----
 pub mod auth;
+pub mod users;
+pub mod posts;
+pub mod post_file_tags;
+pub mod post_likes;
+pub mod post_comments;
+pub mod comment_likes;
+pub mod bookmarks;
+pub mod favorites;
+pub mod hashtags;
+pub mod mentions;
+pub mod notifications;
+pub mod conversations;
+pub mod messages;
+pub mod realtime;
+pub mod stories;
+pub mod story_views;
+pub mod highlights;
+pub mod media;
+pub mod follows;
+pub mod suggestions;
+pub mod blocks;
+pub mod mutes;
+pub mod feed;
+pub mod insights;
+pub mod explore;
+pub mod search;
+pub mod timeline;
 pub mod logging;
 mod routes;
 // ----- End Domain -----
@@ -17,7 +51,12 @@ mod routes;
 #[derive(Clone)]
 pub struct AppState {
     pub config: Settings,
-    pub db: DatabaseConnection
+    pub db: DatabaseConnection,
+    pub timeline: TimelineQueue,
+    pub events: EventBus,
+    pub impressions: ImpressionsQueue,
+    pub broker: Arc<dyn Broker>,
+    pub storage: MediaStorage
 }
 
 pub type Result<T> = std::result::Result<T, HttpResponseError>;
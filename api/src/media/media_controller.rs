@@ -0,0 +1,22 @@
+use actix_web::{post, HttpRequest, HttpResponse, web::{Bytes, Data, Path}, http::{header::CONTENT_TYPE, StatusCode}};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::Result;
+use super::media_service::upload_media;
+
+// The body is the file itself, its type comes from the Content-Type header
+#[post("")]
+pub async fn upload_media_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, req: HttpRequest, body: Bytes) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let content_type = req.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+
+    let media = upload_media(&ctx.db, &ctx.storage, &jwt_payload, &username, content_type, body).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": media
+        })
+    ))
+}
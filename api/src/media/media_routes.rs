@@ -0,0 +1,8 @@
+use actix_web::web::{PayloadConfig, ServiceConfig};
+use super::MAX_UPLOAD_SIZE;
+use super::media_controller::upload_media_handler;
+
+pub fn get_media_routes(cfg: &mut ServiceConfig) {
+    cfg.app_data(PayloadConfig::new(MAX_UPLOAD_SIZE))
+        .service(upload_media_handler);
+}
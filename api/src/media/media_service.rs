@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect};
+use uuid::Uuid;
+use entity::media_uploads::{Entity, Column, ActiveModel};
+use entity::{bookmark_collections, highlight_items, highlights, messages, post_files, stories, users};
+use crate::auth::JwtTokenPayload;
+use crate::error::HttpResponseError;
+use crate::media::UploadedMedia;
+use crate::users::users_service::{ensure_owner, find_user_by_username};
+use crate::utils::storage::MediaStorage;
+use crate::Result;
+
+// Parameters such as the charset are ignored
+fn media_extension(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next()?.trim();
+
+    match media_type {
+        "image/jpeg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "video/mp4" => Some("mp4"),
+        "video/quicktime" => Some("mov"),
+        _ => None,
+    }
+}
+
+pub async fn upload_media(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, content_type: Option<&str>, bytes: Bytes) -> Result<UploadedMedia> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let Some(extension) = content_type.and_then(media_extension) else {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("Only JPEG, PNG, GIF, WebP, MP4 and QuickTime files can be uploaded")
+        );
+    };
+
+    if bytes.is_empty() {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("The file cannot be empty")
+        );
+    }
+
+    // One directory per user, file names are never reused
+    let file_name = format!("{}/{}.{}", Uuid::from_slice(&user.id).unwrap(), Uuid::new_v4(), extension);
+
+    let file_url = match storage.save_file(&file_name, bytes).await {
+        Ok(Some(file_url)) => file_url,
+        Ok(None) => {
+            tracing::error!("Media upload refused, no storage is configured");
+            return Err(HttpResponseError::internal_server_error());
+        },
+        Err(e) => {
+            tracing::error!("Failed to save media {}: {:?}", file_name, e);
+            return Err(HttpResponseError::internal_server_error());
+        },
+    };
+
+    let result = ActiveModel {
+        user_id: Set(user.id),
+        file_url: Set(file_url.clone()),
+        ..Default::default()
+    }.insert(db).await;
+
+    // A file nobody owns could never be used nor deleted
+    if let Err(e) = result {
+        if let Err(e) = storage.delete_file(&file_url).await {
+            tracing::error!("Failed to delete media {}: {:?}", file_url, e);
+        }
+        return Err(e.into());
+    }
+
+    Ok(UploadedMedia { file_url })
+}
+
+pub async fn is_uploaded_by<C: ConnectionTrait>(db: &C, user_id: &[u8], file_url: &str) -> Result<bool> {
+    let upload = Entity::find()
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .filter(Column::FileUrl.eq(file_url))
+        .one(db)
+        .await?;
+
+    Ok(upload.is_some())
+}
+
+// Media URLs sent by clients have to point to a file the user uploaded themselves
+pub async fn ensure_uploaded_by<C: ConnectionTrait>(db: &C, user_id: &[u8], file_url: &str) -> Result<()> {
+    if !is_uploaded_by(db, user_id, file_url).await? {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You can only use media you uploaded")
+        );
+    }
+
+    Ok(())
+}
+
+async fn find_referenced_in<E, C>(db: &C, file_url_col: E::Column, file_urls: &HashSet<String>) -> Result<Vec<String>>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+{
+    let referenced = E::find()
+        .select_only()
+        .column(file_url_col)
+        .filter(file_url_col.is_in(file_urls.iter().cloned()))
+        .into_tuple::<String>()
+        .all(db)
+        .await?;
+
+    Ok(referenced)
+}
+
+// Every column that can hold the URL of an uploaded file
+async fn find_referenced_media<C: ConnectionTrait>(db: &C, file_urls: &HashSet<String>) -> Result<HashSet<String>> {
+    let mut referenced = HashSet::new();
+
+    referenced.extend(find_referenced_in::<stories::Entity, _>(db, stories::Column::FileUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<highlight_items::Entity, _>(db, highlight_items::Column::FileUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<highlights::Entity, _>(db, highlights::Column::CoverUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<post_files::Entity, _>(db, post_files::Column::FileUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<users::Entity, _>(db, users::Column::PictureUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<bookmark_collections::Entity, _>(db, bookmark_collections::Column::CoverUrl, file_urls).await?);
+    referenced.extend(find_referenced_in::<messages::Entity, _>(db, messages::Column::MediaUrl, file_urls).await?);
+
+    Ok(referenced)
+}
+
+// Deletes the files among `file_urls` that `owner_id` uploaded and that nothing points to anymore.
+// Files uploaded by somebody else are never touched, whatever they were used for.
+// Failing to remove a file must not bring back rows that are already gone,
+// so storage errors are only logged.
+pub async fn delete_unreferenced_uploads<C: ConnectionTrait>(db: &C, storage: &MediaStorage, owner_id: &[u8], file_urls: Vec<String>) -> Result<()> {
+    if file_urls.is_empty() {
+        return Ok(());
+    }
+
+    let owned: HashSet<String> = Entity::find()
+        .select_only()
+        .column(Column::FileUrl)
+        .filter(Column::UserId.eq(owner_id.to_vec()))
+        .filter(Column::FileUrl.is_in(file_urls))
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    if owned.is_empty() {
+        return Ok(());
+    }
+
+    let referenced = find_referenced_media(db, &owned).await?;
+    let unreferenced: Vec<String> = owned.difference(&referenced).cloned().collect();
+
+    if unreferenced.is_empty() {
+        return Ok(());
+    }
+
+    for file_url in &unreferenced {
+        if let Err(e) = storage.delete_file(file_url).await {
+            tracing::error!("Failed to delete media {}: {:?}", file_url, e);
+        }
+    }
+
+    Entity::delete_many()
+        .filter(Column::FileUrl.is_in(unreferenced))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+pub mod media_service;
+pub mod media_controller;
+pub mod media_routes;
+
+// Bodies above this size are refused before they reach the handler
+pub const MAX_UPLOAD_SIZE: usize = 20 * 1024 * 1024;
+
+// ---- MEDIA STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedMedia {
+    pub file_url: String,
+}
+
+// ---- END OF MEDIA STRUCTS ----
@@ -0,0 +1,26 @@
+use actix_web::{get, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::mentions_service::get_mentioned_posts;
+
+#[get("")]
+pub async fn get_mentioned_posts_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_mentioned_posts(&ctx.db, viewer.as_ref(), &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.posts,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
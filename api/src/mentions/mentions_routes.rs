@@ -0,0 +1,7 @@
+use actix_web::web::ServiceConfig;
+
+use super::mentions_controller::get_mentioned_posts_handler;
+
+pub fn get_mentions_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_mentioned_posts_handler);
+}
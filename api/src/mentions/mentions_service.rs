@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use sea_orm::{Set, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait};
+use sea_orm::sea_query::Query;
+use entity::mentions::{Entity, Column, ActiveModel};
+use entity::{post_comments, posts, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::events::DomainEvent;
+use crate::mentions::MentionsPage;
+use crate::posts::posts_service::build_post_details;
+use crate::users::users_service::{ensure_can_view_content, find_user_by_username, visible_authors_condition};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::utils::text::extract_mentions;
+use crate::Result;
+
+// Ids of the users a text mentions. Unknown usernames, the author itself and users
+// blocked either way are ignored, the text is kept as written
+async fn resolve_mentions<C: ConnectionTrait>(db: &C, author_id: &[u8], text: &str) -> Result<Vec<Vec<u8>>> {
+    let usernames = extract_mentions(text);
+
+    if usernames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user_ids = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::Username.is_in(usernames))
+        .filter(users::Column::Id.ne(author_id.to_vec()))
+        .filter(not_blocked_condition(users::Column::Id, author_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(user_ids)
+}
+
+async fn insert_mentions<C: ConnectionTrait>(db: &C, post_id: &[u8], comment_id: Option<i32>, user_ids: &[Vec<u8>]) -> Result<()> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mentions = user_ids
+        .iter()
+        .map(|user_id| ActiveModel {
+            post_id: Set(post_id.to_vec()),
+            comment_id: Set(comment_id),
+            user_id: Set(user_id.clone()),
+            ..Default::default()
+        });
+
+    Entity::insert_many(mentions)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Has to be called whenever the description of a post changes. Returns the events to publish
+// once committed, only users mentioned for the first time are notified
+pub async fn sync_post_mentions<C: ConnectionTrait>(db: &C, post: &posts::Model) -> Result<Vec<DomainEvent>> {
+    let wanted: HashSet<Vec<u8>> = resolve_mentions(db, &post.user_id, &post.description).await?
+        .into_iter()
+        .collect();
+
+    let current: HashSet<Vec<u8>> = Entity::find()
+        .select_only()
+        .column(Column::UserId)
+        .filter(Column::PostId.eq(post.id.clone()))
+        .filter(Column::CommentId.is_null())
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut events = Vec::new();
+
+    let removed: Vec<Vec<u8>> = current.difference(&wanted).cloned().collect();
+    if !removed.is_empty() {
+        Entity::delete_many()
+            .filter(Column::PostId.eq(post.id.clone()))
+            .filter(Column::CommentId.is_null())
+            .filter(Column::UserId.is_in(removed.clone()))
+            .exec(db)
+            .await?;
+
+        events.push(DomainEvent::MentionsRemoved {
+            user_ids: removed,
+            post_id: post.id.clone(),
+        });
+    }
+
+    let added: Vec<Vec<u8>> = wanted.difference(&current).cloned().collect();
+    if !added.is_empty() {
+        insert_mentions(db, &post.id, None, &added).await?;
+
+        events.push(DomainEvent::UsersMentioned {
+            actor_id: post.user_id.clone(),
+            user_ids: added,
+            post_id: post.id.clone(),
+            comment_id: None,
+        });
+    }
+
+    Ok(events)
+}
+
+// Comments cannot be edited, their mentions are only written once
+pub async fn create_comment_mentions<C: ConnectionTrait>(db: &C, comment: &post_comments::Model) -> Result<Vec<DomainEvent>> {
+    let user_ids = resolve_mentions(db, &comment.user_id, &comment.comment).await?;
+
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    insert_mentions(db, &comment.post_id, Some(comment.id), &user_ids).await?;
+
+    Ok(vec![DomainEvent::UsersMentioned {
+        actor_id: comment.user_id.clone(),
+        user_ids,
+        post_id: comment.post_id.clone(),
+        comment_id: Some(comment.id),
+    }])
+}
+
+pub async fn delete_comments_mentions<C: ConnectionTrait>(db: &C, comment_ids: Vec<i32>) -> Result<()> {
+    if comment_ids.is_empty() {
+        return Ok(());
+    }
+
+    Entity::delete_many()
+        .filter(Column::CommentId.is_in(comment_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_post_mentions<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+fn mentioned_posts_condition(user_id: &[u8]) -> Condition {
+    let mentioned_posts = Query::select()
+        .column(Column::PostId)
+        .from(Entity)
+        .and_where(Column::UserId.eq(user_id.to_vec()))
+        .to_owned();
+
+    Condition::all().add(posts::Column::Id.in_subquery(mentioned_posts))
+}
+
+// Posts mentioning the user in their description or in a comment, newest first.
+// The list follows the privacy of the mentioned user, each post the visibility of its author
+pub async fn get_mentioned_posts(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, query: &CursorQuery) -> Result<MentionsPage> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let user = find_user_by_username(db, username).await?;
+
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut posts = posts::Entity::find()
+        .filter(mentioned_posts_condition(&user.id))
+        .filter(visible_authors_condition(posts::Column::UserId, viewer_id.as_deref()))
+        .filter(not_blocked_condition(posts::Column::UserId, &user.id))
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(posts::Column::CreatedAt, posts::Column::Id)))
+        .order_by_desc(posts::Column::CreatedAt)
+        .order_by_desc(posts::Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if posts.len() as u64 > limit {
+        posts.truncate(limit as usize);
+        posts.last().map(|post| Cursor::new(post.created_at, post.id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(MentionsPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor,
+    })
+}
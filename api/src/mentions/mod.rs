@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use crate::posts::PostDetail;
+
+pub mod mentions_service;
+pub mod mentions_controller;
+pub mod mentions_routes;
+
+// ---- MENTIONS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MentionsPage {
+    pub posts: Vec<PostDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF MENTIONS STRUCTS ----
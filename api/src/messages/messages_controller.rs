@@ -0,0 +1,74 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::messages::{ReplyToStoryPayload, SendMessagePayload};
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::messages_service::{delete_message, get_messages, reply_to_story, send_message};
+
+#[get("")]
+pub async fn get_messages_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_messages(&ctx.db, &jwt_payload, &username, &conversation_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.messages,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("")]
+pub async fn send_message_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<SendMessagePayload>) -> Result<HttpResponse> {
+    let (username, conversation_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let message = send_message(&ctx.db, &ctx.events, &jwt_payload, &username, &conversation_id, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": message
+        })
+    ))
+}
+
+#[delete("/{message_id}")]
+pub async fn delete_message_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, conversation_id, message_id) = path.into_inner();
+
+    delete_message(&ctx.db, &jwt_payload, &username, &conversation_id, &message_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[post("")]
+pub async fn reply_to_story_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<ReplyToStoryPayload>) -> Result<HttpResponse> {
+    let (username, story_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let message = reply_to_story(&ctx.db, &ctx.events, &jwt_payload, &username, &story_id, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": message
+        })
+    ))
+}
@@ -0,0 +1,13 @@
+use actix_web::web::ServiceConfig;
+
+use super::messages_controller::{delete_message_handler, get_messages_handler, reply_to_story_handler, send_message_handler};
+
+pub fn get_messages_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_messages_handler)
+        .service(send_message_handler)
+        .service(delete_message_handler);
+}
+
+pub fn get_story_replies_routes(cfg: &mut ServiceConfig) {
+    cfg.service(reply_to_story_handler);
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use uuid::Uuid;
+use entity::messages::{Entity, Column, Model, ActiveModel};
+use entity::{conversations, conversation_participants, posts};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::{ensure_not_blocked, not_blocked_condition};
+use crate::conversations::PARTICIPANT_STATUS_ACCEPTED;
+use crate::conversations::conversations_service::{find_user_conversation, get_or_create_direct_conversation, get_other_participant_ids};
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::media::media_service::ensure_uploaded_by;
+use crate::messages::{MessageDetail, MessageKind, MessagesPage, ReplyToStoryPayload, SendMessagePayload};
+use crate::posts::posts_service::build_post_details;
+use crate::stories::stories_service::find_active_user_story;
+use crate::users::users_service::{ensure_can_view_content, ensure_owner, find_user_by_username, get_post_authors, visible_authors_condition};
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::Result;
+
+fn message_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Message not found")
+}
+
+fn post_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Post not found")
+}
+
+// Builds the detail view of many messages with a fixed number of queries.
+// Shared posts are only shown to viewers allowed to see them
+pub async fn build_message_details<C: ConnectionTrait>(db: &C, viewer_id: &[u8], messages: Vec<Model>) -> Result<Vec<MessageDetail>> {
+    let senders = get_post_authors(db, messages.iter().map(|message| message.sender_id.clone()).collect()).await?;
+
+    let post_ids: Vec<Vec<u8>> = messages.iter().filter_map(|message| message.post_id.clone()).collect();
+    let posts = if post_ids.is_empty() {
+        Vec::new()
+    } else {
+        posts::Entity::find()
+            .filter(posts::Column::Id.is_in(post_ids))
+            .filter(visible_authors_condition(posts::Column::UserId, Some(viewer_id)))
+            .filter(not_blocked_condition(posts::Column::UserId, viewer_id))
+            .all(db)
+            .await?
+    };
+
+    let posts: HashMap<String, _> = build_post_details(db, posts).await?
+        .into_iter()
+        .map(|post| (post.id.clone(), post))
+        .collect();
+
+    let message_details = messages
+        .into_iter()
+        .filter_map(|message| {
+            let post = message.post_id
+                .as_ref()
+                .and_then(|post_id| posts.get(&Uuid::from_slice(post_id).unwrap_or_default().to_string()).cloned());
+
+            Some(MessageDetail {
+                id: message.id,
+                conversation_id: Uuid::from_slice(&message.conversation_id).unwrap_or_default().to_string(),
+                sender: senders.get(&message.sender_id)?.clone(),
+                kind: message.kind,
+                text: message.text,
+                post,
+                story_id: message.story_id.map(|story_id| Uuid::from_slice(&story_id).unwrap_or_default().to_string()),
+                media_url: message.media_url,
+                created_at: message.created_at,
+            })
+        })
+        .collect();
+
+    Ok(message_details)
+}
+
+// Moves the conversation to the top of the inbox. Sending a message reads everything before it,
+// and answering a message request accepts it
+async fn insert_message<C: ConnectionTrait>(db: &C, participant: &conversation_participants::Model, message: ActiveModel) -> Result<Model> {
+    let message = message.insert(db).await?;
+
+    conversations::Entity::update_many()
+        .col_expr(conversations::Column::LastMessageAt, Expr::value(message.created_at))
+        .filter(conversations::Column::Id.eq(message.conversation_id.clone()))
+        .exec(db)
+        .await?;
+
+    conversation_participants::Entity::update_many()
+        .col_expr(conversation_participants::Column::LastReadMessageId, Expr::value(message.id))
+        .col_expr(conversation_participants::Column::Status, Expr::value(PARTICIPANT_STATUS_ACCEPTED))
+        .filter(conversation_participants::Column::Id.eq(participant.id))
+        .exec(db)
+        .await?;
+
+    Ok(message)
+}
+
+// Newest first. Messages of users blocked either way are left out
+pub async fn get_messages(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str, query: &CursorQuery) -> Result<MessagesPage> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, _) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_id_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut messages = Entity::find()
+        .filter(Column::ConversationId.eq(conversation.id))
+        .filter(not_blocked_condition(Column::SenderId, &viewer_id))
+        .apply_if(cursor, |query, cursor| query.filter(Column::Id.lt(cursor)))
+        .order_by_desc(Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if messages.len() as u64 > limit {
+        messages.truncate(limit as usize);
+        messages.last().map(|message| encode_id_cursor(message.id))
+    } else {
+        None
+    };
+
+    Ok(MessagesPage {
+        messages: build_message_details(db, &viewer_id, messages).await?,
+        next_cursor,
+    })
+}
+
+pub async fn send_message(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str, data: SendMessagePayload) -> Result<MessageDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, participant) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+
+    let contents = [data.text.is_some(), data.post_id.is_some(), data.media_url.is_some()];
+    if contents.iter().filter(|content| **content).count() != 1 {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("A message needs exactly one of text, postId or mediaUrl")
+        );
+    }
+
+    // Nobody can be messaged through a block, in a group the blocked users can still talk to the others
+    if !conversation.is_group {
+        for other_id in get_other_participant_ids(db, &conversation.id, &viewer_id).await? {
+            ensure_not_blocked(db, &viewer_id, &other_id).await?;
+        }
+    }
+
+    let mut message = ActiveModel {
+        conversation_id: Set(conversation.id.clone()),
+        sender_id: Set(viewer_id.clone()),
+        kind: Set(MessageKind::Text.as_str().to_string()),
+        text: Set(data.text),
+        ..Default::default()
+    };
+
+    // Only posts the sender can see can be shared
+    if let Some(post_id) = data.post_id {
+        let post_id = Uuid::from_str(&post_id).map_err(|_| post_not_found())?;
+        let post = posts::Entity::find_by_id(Vec::from(post_id))
+            .filter(visible_authors_condition(posts::Column::UserId, Some(&viewer_id)))
+            .filter(not_blocked_condition(posts::Column::UserId, &viewer_id))
+            .one(db)
+            .await?
+            .ok_or_else(post_not_found)?;
+
+        message.kind = Set(MessageKind::Post.as_str().to_string());
+        message.post_id = Set(Some(post.id));
+    }
+
+    if let Some(media_url) = data.media_url {
+        ensure_uploaded_by(db, &viewer_id, &media_url).await?;
+
+        message.kind = Set(MessageKind::Media.as_str().to_string());
+        message.media_url = Set(Some(media_url));
+    }
+
+    let txn = db.begin().await?;
+
+    let message = insert_message(&txn, &participant, message).await?;
+
+    txn.commit().await?;
+
+    events.publish(DomainEvent::MessageSent { message_id: message.id });
+
+    build_message_details(db, &viewer_id, vec![message]).await?
+        .pop()
+        .ok_or_else(message_not_found)
+}
+
+// Only the sender can delete a message, it disappears for everyone
+pub async fn delete_message(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, conversation_id: &str, message_id: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let viewer_id = jwt_payload.get_user_id();
+    let (conversation, _) = find_user_conversation(db, &viewer_id, conversation_id).await?;
+    let message_id: i32 = message_id.parse().map_err(|_| message_not_found())?;
+
+    let message = Entity::find_by_id(message_id)
+        .filter(Column::ConversationId.eq(conversation.id))
+        .one(db)
+        .await?
+        .ok_or_else(message_not_found)?;
+
+    if message.sender_id != viewer_id {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("You can only delete your own messages")
+        );
+    }
+
+    Entity::delete_by_id(message.id)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Replies land in the one-to-one conversation with the owner of the story
+pub async fn reply_to_story(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, story_id: &str, data: ReplyToStoryPayload) -> Result<MessageDetail> {
+    if jwt_payload.username == username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot reply to your own story")
+        );
+    }
+
+    let viewer = find_user_by_username(db, &jwt_payload.username).await?;
+    let owner = find_user_by_username(db, username).await?;
+
+    ensure_not_blocked(db, &viewer.id, &owner.id).await?;
+    ensure_can_view_content(db, Some(&viewer.id), &owner).await?;
+
+    let story = find_active_user_story(db, &owner.id, story_id).await?;
+
+    let txn = db.begin().await?;
+
+    let (_, participant) = get_or_create_direct_conversation(&txn, &viewer, &owner).await?;
+
+    let message = insert_message(&txn, &participant, ActiveModel {
+        conversation_id: Set(participant.conversation_id.clone()),
+        sender_id: Set(viewer.id.clone()),
+        kind: Set(MessageKind::StoryReply.as_str().to_string()),
+        text: Set(data.text),
+        story_id: Set(Some(story.id)),
+        ..Default::default()
+    }).await?;
+
+    txn.commit().await?;
+
+    events.publish(DomainEvent::StoryReplied { user_id: viewer.id.clone(), story_owner_id: owner.id });
+    events.publish(DomainEvent::MessageSent { message_id: message.id });
+
+    build_message_details(db, &viewer.id, vec![message]).await?
+        .pop()
+        .ok_or_else(message_not_found)
+}
+
+// Has to run before the post is deleted, the messages sharing it stay without their post
+pub async fn unshare_post_from_messages<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::update_many()
+        .col_expr(Column::PostId, Expr::value(Option::<Vec<u8>>::None))
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_conversation_messages<C: ConnectionTrait>(db: &C, conversation_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::ConversationId.eq(conversation_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::posts::PostDetail;
+use crate::users::PostAuthor;
+use crate::utils::check_valid_uuid;
+
+pub mod messages_service;
+pub mod messages_controller;
+pub mod messages_routes;
+
+// ---- MESSAGES STRUCTS ----
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Text,
+    Post,
+    StoryReply,
+    Media,
+}
+
+impl MessageKind {
+    // Stored in `messages.kind`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageKind::Text => "text",
+            MessageKind::Post => "post",
+            MessageKind::StoryReply => "story_reply",
+            MessageKind::Media => "media",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageDetail {
+    pub id: i32,
+    pub conversation_id: String,
+    pub sender: PostAuthor,
+    pub kind: String,
+    pub text: Option<String>,
+    // `None` for a shared post that was deleted or that the viewer cannot see
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<PostDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub story_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagesPage {
+    pub messages: Vec<MessageDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF MESSAGES STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+// Exactly one of `text`, `postId` and `mediaUrl` is expected
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SendMessagePayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 1000,
+    message = "Message must be between 1 and 1000 characters"
+    )
+    )]
+    pub text: Option<String>,
+
+    #[serde(rename = "postId")]
+    #[validate(
+    custom(
+    function = "check_valid_uuid",
+    message = "Post id must be a valid UUID"
+    )
+    )]
+    pub post_id: Option<String>,
+
+    #[serde(rename = "mediaUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "Media URL cannot be empty"
+    )
+    )]
+    pub media_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReplyToStoryPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 1000,
+    message = "Message must be between 1 and 1000 characters"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub text: Option<String>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,3 @@
+pub mod mutes_service;
+pub mod mutes_controller;
+pub mod mutes_routes;
@@ -0,0 +1,46 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::Result;
+use super::mutes_service::{get_muted_users, mute, unmute};
+
+#[get("")]
+pub async fn get_muted_users_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let muted_users = get_muted_users(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": muted_users
+        })
+    ))
+}
+
+#[post("/{target_username}")]
+pub async fn mute_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, target_username) = path.into_inner();
+
+    mute(&ctx.db, &jwt_payload, &username, &target_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{target_username}")]
+pub async fn unmute_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, target_username) = path.into_inner();
+
+    unmute(&ctx.db, &jwt_payload, &username, &target_username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::mutes_controller::{get_muted_users_handler, mute_handler, unmute_handler};
+
+pub fn get_mutes_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_muted_users_handler)
+        .service(mute_handler)
+        .service(unmute_handler);
+}
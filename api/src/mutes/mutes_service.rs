@@ -0,0 +1,94 @@
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, Condition, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait};
+use sea_orm::sea_query::Query;
+use entity::mutes::{Entity, Column, ActiveModel, Relation};
+use crate::auth::JwtTokenPayload;
+use crate::error::HttpResponseError;
+use crate::follows::Follower;
+use crate::users::users_service::{ensure_owner, find_user_by_username};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// Muting is silent, it only hides the muted user's content
+// from the muter's feed and story tray
+pub fn not_muted_condition<C: ColumnTrait>(author_col: C, viewer_id: &[u8]) -> Condition {
+    let muted_by_viewer = Query::select()
+        .column(Column::MutedId)
+        .from(Entity)
+        .and_where(Column::MuterId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    Condition::all().add(author_col.not_in_subquery(muted_by_viewer))
+}
+
+pub async fn mute(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, target_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    if username == target_username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot mute yourself")
+        );
+    }
+
+    let user = find_user_by_username(db, username).await?;
+    let target = find_user_by_username(db, target_username).await?;
+
+    let result = ActiveModel {
+        muter_id: Set(user.id),
+        muted_id: Set(target.id),
+        ..Default::default()
+    }.insert(db).await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if is_unique_violation(&e) => Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You already muted this user")
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn unmute(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, target_username: &str) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let target = find_user_by_username(db, target_username).await?;
+
+    let result = Entity::delete_many()
+        .filter(Column::MuterId.eq(user.id))
+        .filter(Column::MutedId.eq(target.id))
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You have not muted this user")
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn get_muted_users(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<Vec<Follower>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let muted_users = entity::users::Entity::find()
+        .join(JoinType::InnerJoin, Relation::Muted.def().rev())
+        .filter(Column::MuterId.eq(user.id))
+        .order_by_desc(Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Follower::from)
+        .collect();
+
+    Ok(muted_users)
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::users::PostAuthor;
+
+pub mod notifications_service;
+pub mod notifications_controller;
+pub mod notifications_routes;
+
+// ---- NOTIFICATIONS STRUCTS ----
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Follow,
+    FollowRequest,
+    Like,
+    Comment,
+    Reply,
+    Mention,
+    Tag,
+    StoryReply,
+}
+
+impl NotificationKind {
+    // Stored in `notifications.kind`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Follow => "follow",
+            NotificationKind::FollowRequest => "follow_request",
+            NotificationKind::Like => "like",
+            NotificationKind::Comment => "comment",
+            NotificationKind::Reply => "reply",
+            NotificationKind::Mention => "mention",
+            NotificationKind::Tag => "tag",
+            NotificationKind::StoryReply => "story_reply",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationDetail {
+    // The latest notification of the group, used to mark it as read
+    pub id: i32,
+    pub kind: String,
+    // The latest user who caused the notification
+    pub actor: PostAuthor,
+    // How many other users did the same, as in "X and 12 others liked your post"
+    pub others_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_id: Option<i32>,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsPage {
+    pub notifications: Vec<NotificationDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// Users whose notifications changed while an event was handled
+#[derive(Debug, Default)]
+pub struct NotificationChanges {
+    pub notified: Vec<Vec<u8>>,
+    pub withdrawn: Vec<Vec<u8>>,
+}
+
+impl NotificationChanges {
+    fn merge(mut self, other: NotificationChanges) -> Self {
+        self.notified.extend(other.notified);
+        self.withdrawn.extend(other.withdrawn);
+        self
+    }
+}
+
+// ---- END OF NOTIFICATIONS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MarkNotificationsReadPayload {
+    // Every notification is marked as read when no id is given
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 notifications can be marked at once"))]
+    pub ids: Option<Vec<i32>>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
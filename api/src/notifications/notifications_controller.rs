@@ -0,0 +1,59 @@
+use actix_web::{get, post, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::notifications::MarkNotificationsReadPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::notifications_service::{count_unread_notifications, get_notifications, mark_notifications_read};
+
+#[get("")]
+pub async fn get_notifications_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_notifications(&ctx.db, &jwt_payload, &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.notifications,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[get("/unread-count")]
+pub async fn count_unread_notifications_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let unread_count = count_unread_notifications(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": {
+                "unreadCount": unread_count
+            }
+        })
+    ))
+}
+
+#[post("/read")]
+pub async fn mark_notifications_read_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<MarkNotificationsReadPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    mark_notifications_read(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
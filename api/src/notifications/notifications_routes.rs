@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::notifications_controller::{count_unread_notifications_handler, get_notifications_handler, mark_notifications_read_handler};
+
+pub fn get_notifications_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_notifications_handler)
+        .service(count_unread_notifications_handler)
+        .service(mark_notifications_read_handler);
+}
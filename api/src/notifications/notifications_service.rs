@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use sea_orm::{Set, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use uuid::Uuid;
+use entity::notifications::{Entity, Column, Model, ActiveModel};
+use entity::{post_comments, posts};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::events::DomainEvent;
+use crate::notifications::{MarkNotificationsReadPayload, NotificationChanges, NotificationDetail, NotificationKind, NotificationsPage};
+use crate::users::users_service::{ensure_owner, find_user_by_username, get_post_authors};
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::Result;
+
+// Likes of the same post are shown as one notification
+fn group_key(kind: NotificationKind, post_id: Option<&[u8]>) -> Option<String> {
+    match (kind, post_id) {
+        (NotificationKind::Like, Some(post_id)) => Some(format!("like:{}", Uuid::from_slice(post_id).unwrap_or_default().simple())),
+        _ => None,
+    }
+}
+
+// Rows without a group key stand alone
+fn group_expr() -> SimpleExpr {
+    Expr::cust("COALESCE(group_key, id)")
+}
+
+// One notification per recipient. Users are never notified of their own actions
+async fn notify<C: ConnectionTrait>(db: &C, actor_id: &[u8], recipient_ids: &[Vec<u8>], kind: NotificationKind, post_id: Option<&[u8]>, comment_id: Option<i32>) -> Result<NotificationChanges> {
+    let recipient_ids: Vec<Vec<u8>> = recipient_ids
+        .iter()
+        .filter(|recipient_id| recipient_id.as_slice() != actor_id)
+        .cloned()
+        .collect();
+
+    let notifications: Vec<ActiveModel> = recipient_ids
+        .iter()
+        .map(|recipient_id| ActiveModel {
+            user_id: Set(recipient_id.clone()),
+            actor_id: Set(actor_id.to_vec()),
+            kind: Set(kind.as_str().to_string()),
+            post_id: Set(post_id.map(|post_id| post_id.to_vec())),
+            comment_id: Set(comment_id),
+            group_key: Set(group_key(kind, post_id)),
+            ..Default::default()
+        })
+        .collect();
+
+    if notifications.is_empty() {
+        return Ok(NotificationChanges::default());
+    }
+
+    Entity::insert_many(notifications)
+        .exec(db)
+        .await?;
+
+    Ok(NotificationChanges {
+        notified: recipient_ids,
+        ..Default::default()
+    })
+}
+
+// Removes the notifications of one kind that no longer apply, like the one of a like taken back.
+// `recipient_ids` are the users the condition can match
+async fn withdraw<C: ConnectionTrait>(db: &C, kind: NotificationKind, recipient_ids: Vec<Vec<u8>>, condition: Condition) -> Result<NotificationChanges> {
+    let result = Entity::delete_many()
+        .filter(Column::Kind.eq(kind.as_str()))
+        .filter(Column::UserId.is_in(recipient_ids.clone()))
+        .filter(condition)
+        .exec(db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Ok(NotificationChanges::default());
+    }
+
+    Ok(NotificationChanges {
+        withdrawn: recipient_ids,
+        ..Default::default()
+    })
+}
+
+// A reply notifies the author of the thread, the post owner hears about it as a comment
+async fn notify_comment<C: ConnectionTrait>(db: &C, comment_id: i32) -> Result<NotificationChanges> {
+    let Some(comment) = post_comments::Entity::find_by_id(comment_id).one(db).await? else {
+        return Ok(NotificationChanges::default());
+    };
+    let Some(post) = posts::Entity::find_by_id(comment.post_id.clone()).one(db).await? else {
+        return Ok(NotificationChanges::default());
+    };
+
+    let parent_author_id = match comment.parent_id {
+        Some(parent_id) => post_comments::Entity::find_by_id(parent_id)
+            .one(db)
+            .await?
+            .map(|parent| parent.user_id),
+        None => None,
+    };
+
+    let mut changes = NotificationChanges::default();
+
+    if let Some(parent_author_id) = &parent_author_id {
+        changes = changes.merge(
+            notify(db, &comment.user_id, std::slice::from_ref(parent_author_id), NotificationKind::Reply, Some(&post.id), Some(comment.id)).await?
+        );
+    }
+
+    if parent_author_id.as_ref() != Some(&post.user_id) {
+        changes = changes.merge(
+            notify(db, &comment.user_id, std::slice::from_ref(&post.user_id), NotificationKind::Comment, Some(&post.id), Some(comment.id)).await?
+        );
+    }
+
+    Ok(changes)
+}
+
+// The only writer of notifications, every domain event that concerns another user ends up here.
+// Returns who has to be told about it
+pub async fn handle_event(db: &DatabaseConnection, event: &DomainEvent) -> Result<NotificationChanges> {
+    match event {
+        DomainEvent::Followed { follower_id, followee_id } => {
+            notify(db, follower_id, std::slice::from_ref(followee_id), NotificationKind::Follow, None, None).await
+        },
+        DomainEvent::Unfollowed { follower_id, followee_id } => {
+            withdraw(db, NotificationKind::Follow, vec![followee_id.clone()], Condition::all()
+                .add(Column::ActorId.eq(follower_id.clone()))
+            ).await
+        },
+        DomainEvent::FollowRequested { requester_id, target_id } => {
+            notify(db, requester_id, std::slice::from_ref(target_id), NotificationKind::FollowRequest, None, None).await
+        },
+        DomainEvent::FollowRequestClosed { requester_id, target_id } => {
+            withdraw(db, NotificationKind::FollowRequest, vec![target_id.clone()], Condition::all()
+                .add(Column::ActorId.eq(requester_id.clone()))
+            ).await
+        },
+        DomainEvent::PostLiked { user_id, post_id } => {
+            let Some(post) = posts::Entity::find_by_id(post_id.clone()).one(db).await? else {
+                return Ok(NotificationChanges::default());
+            };
+
+            notify(db, user_id, std::slice::from_ref(&post.user_id), NotificationKind::Like, Some(post_id), None).await
+        },
+        DomainEvent::PostUnliked { user_id, post_id } => {
+            let Some(post) = posts::Entity::find_by_id(post_id.clone()).one(db).await? else {
+                return Ok(NotificationChanges::default());
+            };
+
+            withdraw(db, NotificationKind::Like, vec![post.user_id], Condition::all()
+                .add(Column::ActorId.eq(user_id.clone()))
+                .add(Column::PostId.eq(post_id.clone()))
+            ).await
+        },
+        DomainEvent::CommentCreated { comment_id } => notify_comment(db, *comment_id).await,
+        DomainEvent::UsersMentioned { actor_id, user_ids, post_id, comment_id } => {
+            notify(db, actor_id, user_ids, NotificationKind::Mention, Some(post_id), *comment_id).await
+        },
+        DomainEvent::MentionsRemoved { user_ids, post_id } => {
+            withdraw(db, NotificationKind::Mention, user_ids.clone(), Condition::all()
+                .add(Column::PostId.eq(post_id.clone()))
+                .add(Column::CommentId.is_null())
+            ).await
+        },
+        DomainEvent::UserTagged { actor_id, user_id, post_id } => {
+            notify(db, actor_id, std::slice::from_ref(user_id), NotificationKind::Tag, Some(post_id), None).await
+        },
+        DomainEvent::TagRemoved { user_id, post_id } => {
+            withdraw(db, NotificationKind::Tag, vec![user_id.clone()], Condition::all()
+                .add(Column::PostId.eq(post_id.clone()))
+            ).await
+        },
+        DomainEvent::StoryReplied { user_id, story_owner_id } => {
+            notify(db, user_id, std::slice::from_ref(story_owner_id), NotificationKind::StoryReply, None, None).await
+        },
+        DomainEvent::StoryPosted { .. }
+        | DomainEvent::MessageSent { .. }
+        | DomainEvent::ConversationRead { .. } => Ok(NotificationChanges::default()),
+    }
+}
+
+// Has to run before the post is deleted, the foreign key is `NO ACTION`
+pub async fn delete_post_notifications<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_comments_notifications<C: ConnectionTrait>(db: &C, comment_ids: Vec<i32>) -> Result<()> {
+    if comment_ids.is_empty() {
+        return Ok(());
+    }
+
+    Entity::delete_many()
+        .filter(Column::CommentId.is_in(comment_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Notifications of users blocked either way are hidden, not deleted
+fn received_condition(user_id: &[u8]) -> Condition {
+    Condition::all()
+        .add(Column::UserId.eq(user_id.to_vec()))
+        .add(not_blocked_condition(Column::ActorId, user_id))
+}
+
+// Newest first. Every group is listed once, at the position of its latest notification
+pub async fn list_user_notifications<C: ConnectionTrait>(db: &C, user_id: &[u8], limit: u64, cursor: Option<i32>) -> Result<NotificationsPage> {
+    // One extra row tells whether there is a next page
+    let mut groups: Vec<(i32, i64, i64)> = Entity::find()
+        .select_only()
+        .column_as(Expr::col(Column::Id).max(), "id")
+        .column_as(Expr::cust("COUNT(DISTINCT actor_id)"), "actors_count")
+        .column_as(Expr::cust("CAST(MAX(read_at IS NULL) AS SIGNED)"), "unread")
+        .filter(received_condition(user_id))
+        .group_by(group_expr())
+        .apply_if(cursor, |query, cursor| query.having(Expr::expr(Expr::col(Column::Id).max()).lt(cursor)))
+        .order_by_desc(Expr::col(Column::Id).max())
+        .limit(limit + 1)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let next_cursor = if groups.len() as u64 > limit {
+        groups.truncate(limit as usize);
+        groups.last().map(|(id, _, _)| encode_id_cursor(*id))
+    } else {
+        None
+    };
+
+    let mut latest: HashMap<i32, Model> = Entity::find()
+        .filter(Column::Id.is_in(groups.iter().map(|(id, _, _)| *id).collect::<Vec<i32>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|notification| (notification.id, notification))
+        .collect();
+
+    let actors = get_post_authors(db, latest.values().map(|notification| notification.actor_id.clone()).collect()).await?;
+
+    let notifications = groups
+        .into_iter()
+        .filter_map(|(id, actors_count, unread)| {
+            let notification = latest.remove(&id)?;
+
+            Some(NotificationDetail {
+                id,
+                kind: notification.kind,
+                actor: actors.get(&notification.actor_id)?.clone(),
+                others_count: (actors_count - 1).max(0) as u64,
+                post_id: notification.post_id.map(|post_id| Uuid::from_slice(&post_id).unwrap_or_default().to_string()),
+                comment_id: notification.comment_id,
+                read: unread == 0,
+                created_at: notification.created_at,
+            })
+        })
+        .collect();
+
+    Ok(NotificationsPage {
+        notifications,
+        next_cursor,
+    })
+}
+
+pub async fn get_notifications(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, query: &CursorQuery) -> Result<NotificationsPage> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    list_user_notifications(db, &user.id, query.get_limit(), query.get_id_cursor()?).await
+}
+
+// Counted the way they are listed, a group with unread notifications counts once
+pub async fn count_user_unread_notifications<C: ConnectionTrait>(db: &C, user_id: &[u8]) -> Result<u64> {
+    let unread_count = Entity::find()
+        .select_only()
+        .column_as(Expr::col(Column::Id).max(), "id")
+        .filter(received_condition(user_id))
+        .filter(Column::ReadAt.is_null())
+        .group_by(group_expr())
+        .count(db)
+        .await?;
+
+    Ok(unread_count)
+}
+
+pub async fn count_unread_notifications(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<u64> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    count_user_unread_notifications(db, &user.id).await
+}
+
+// Marking a group reads every notification it had when it was listed
+pub async fn mark_notifications_read(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: MarkNotificationsReadPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let mut condition = Condition::all()
+        .add(Column::UserId.eq(user.id.clone()))
+        .add(Column::ReadAt.is_null());
+
+    if let Some(ids) = data.ids {
+        let groups = Entity::find()
+            .filter(Column::UserId.eq(user.id.clone()))
+            .filter(Column::Id.is_in(ids))
+            .all(db)
+            .await?;
+
+        let mut selected = Condition::any();
+        for notification in groups {
+            selected = match notification.group_key {
+                Some(group_key) => selected.add(
+                    Condition::all()
+                        .add(Column::GroupKey.eq(group_key))
+                        .add(Column::Id.lte(notification.id))
+                ),
+                None => selected.add(Column::Id.eq(notification.id)),
+            };
+        }
+
+        // Unknown ids are ignored
+        if selected.is_empty() {
+            return Ok(());
+        }
+
+        condition = condition.add(selected);
+    }
+
+    Entity::update_many()
+        .col_expr(Column::ReadAt, Expr::value(Utc::now()))
+        .filter(condition)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::users::PostAuthor;
+
+pub mod post_comments_service;
+pub mod post_comments_controller;
+pub mod post_comments_routes;
+
+// ---- POST COMMENTS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentDetail {
+    pub id: i32,
+    pub comment: String,
+    // `None` for top level comments
+    pub parent_id: Option<i32>,
+    pub user: PostAuthor,
+    pub likes_count: u64,
+    pub replies_count: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentsPage {
+    pub comments: Vec<CommentDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF POST COMMENTS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateCommentPayload {
+    #[validate(
+    length(
+    min = 1,
+    max = 2200,
+    message = "Comment must be between 1 and 2200 characters"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub comment: Option<String>,
+
+    #[serde(rename = "parentCommentId")]
+    pub parent_comment_id: Option<i32>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
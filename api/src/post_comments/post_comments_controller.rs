@@ -0,0 +1,75 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::post_comments::CreateCommentPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::post_comments_service::{create_comment, delete_comment, get_comment_replies, get_comments};
+
+#[get("")]
+pub async fn get_comments_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String)>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_comments(&ctx.db, viewer.as_ref(), &username, &post_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.comments,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("")]
+pub async fn create_comment_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<CreateCommentPayload>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let comment = create_comment(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": comment
+        })
+    ))
+}
+
+#[get("/{comment_id}/replies")]
+pub async fn get_comment_replies_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String, String)>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let (username, post_id, comment_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_comment_replies(&ctx.db, viewer.as_ref(), &username, &post_id, &comment_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.comments,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[delete("/{comment_id}")]
+pub async fn delete_comment_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, post_id, comment_id) = path.into_inner();
+
+    delete_comment(&ctx.db, &jwt_payload, &username, &post_id, &comment_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
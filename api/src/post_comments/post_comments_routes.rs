@@ -0,0 +1,12 @@
+use actix_web::web::{ServiceConfig, scope};
+use crate::comment_likes::comment_likes_routes::get_comment_likes_routes;
+
+use super::post_comments_controller::{get_comments_handler, create_comment_handler, get_comment_replies_handler, delete_comment_handler};
+
+pub fn get_post_comments_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_comments_handler)
+        .service(create_comment_handler)
+        .service(get_comment_replies_handler)
+        .service(delete_comment_handler)
+        .service(scope("/{comment_id}/likes").configure(get_comment_likes_routes));
+}
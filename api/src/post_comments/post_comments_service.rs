@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait};
+use entity::post_comments::{Entity, Column, Model, ActiveModel};
+use entity::posts;
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::comment_likes::comment_likes_service::delete_comments_likes;
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::hashtags::hashtags_service::sync_post_hashtags;
+use crate::mentions::mentions_service::{create_comment_mentions, delete_comments_mentions};
+use crate::notifications::notifications_service::delete_comments_notifications;
+use crate::post_comments::{CommentDetail, CommentsPage, CreateCommentPayload};
+use crate::posts::posts_service::find_user_post;
+use crate::users::users_service::{ensure_can_view_content, find_user_by_username, get_post_authors};
+use crate::utils::cursor::{encode_id_cursor, CursorQuery};
+use crate::Result;
+
+fn comment_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Comment not found")
+}
+
+pub async fn find_post_comment<C: ConnectionTrait>(db: &C, post_id: &[u8], comment_id: &str) -> Result<Model> {
+    let comment_id: i32 = comment_id.parse().map_err(|_| comment_not_found())?;
+
+    let comment = Entity::find_by_id(comment_id)
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .one(db)
+        .await?;
+
+    comment.ok_or_else(comment_not_found)
+}
+
+// The post behind `/users/{username}/posts/{postId}`, as long as the viewer is allowed to see it
+pub async fn find_visible_post(db: &DatabaseConnection, viewer_id: Option<&[u8]>, username: &str, post_id: &str) -> Result<posts::Model> {
+    let user = find_user_by_username(db, username).await?;
+
+    ensure_can_view_content(db, viewer_id, &user).await?;
+
+    find_user_post(db, &user.id, post_id).await
+}
+
+// Builds the detail view of many comments with a fixed number of queries
+async fn build_comment_details<C: ConnectionTrait>(db: &C, comments: Vec<Model>) -> Result<Vec<CommentDetail>> {
+    if comments.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let comment_ids: Vec<i32> = comments.iter().map(|comment| comment.id).collect();
+    let author_ids: Vec<Vec<u8>> = comments.iter().map(|comment| comment.user_id.clone()).collect();
+
+    let authors = get_post_authors(db, author_ids).await?;
+
+    let replies_counts: HashMap<i32, u64> = Entity::find()
+        .select_only()
+        .column(Column::ParentId)
+        .column_as(Column::Id.count(), "count")
+        .filter(Column::ParentId.is_in(comment_ids))
+        .group_by(Column::ParentId)
+        .into_tuple::<(i32, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(parent_id, count)| (parent_id, count as u64))
+        .collect();
+
+    let comment_details = comments
+        .into_iter()
+        .filter_map(|comment| {
+            let author = authors.get(&comment.user_id)?.clone();
+
+            Some(CommentDetail {
+                id: comment.id,
+                comment: comment.comment,
+                parent_id: comment.parent_id,
+                user: author,
+                likes_count: comment.likes_count.max(0) as u64,
+                replies_count: replies_counts.get(&comment.id).copied().unwrap_or_default(),
+                created_at: comment.created_at,
+            })
+        })
+        .collect();
+
+    Ok(comment_details)
+}
+
+// Comments are listed oldest first, so a thread reads like a conversation
+async fn get_comments_page(db: &DatabaseConnection, viewer_id: Option<Vec<u8>>, post_id: &[u8], parent_id: Option<i32>, query: &CursorQuery) -> Result<CommentsPage> {
+    let limit = query.get_limit();
+    let cursor = query.get_id_cursor()?;
+
+    let parent_condition = match parent_id {
+        Some(parent_id) => Column::ParentId.eq(parent_id),
+        None => Column::ParentId.is_null(),
+    };
+
+    // One extra row tells whether there is a next page
+    let mut comments = Entity::find()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .filter(parent_condition)
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(Column::UserId, &viewer_id)))
+        .apply_if(cursor, |query, cursor| query.filter(Column::Id.gt(cursor)))
+        .order_by_asc(Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if comments.len() as u64 > limit {
+        comments.truncate(limit as usize);
+        comments.last().map(|comment| encode_id_cursor(comment.id))
+    } else {
+        None
+    };
+
+    Ok(CommentsPage {
+        comments: build_comment_details(db, comments).await?,
+        next_cursor,
+    })
+}
+
+pub async fn get_comments(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, post_id: &str, query: &CursorQuery) -> Result<CommentsPage> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let post = find_visible_post(db, viewer_id.as_deref(), username, post_id).await?;
+
+    get_comments_page(db, viewer_id, &post.id, None, query).await
+}
+
+pub async fn get_comment_replies(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, post_id: &str, comment_id: &str, query: &CursorQuery) -> Result<CommentsPage> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let post = find_visible_post(db, viewer_id.as_deref(), username, post_id).await?;
+    let comment = find_post_comment(db, &post.id, comment_id).await?;
+
+    get_comments_page(db, viewer_id, &post.id, Some(comment.id), query).await
+}
+
+pub async fn create_comment(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, data: CreateCommentPayload) -> Result<CommentDetail> {
+    let viewer_id = jwt_payload.get_user_id();
+    let post = find_visible_post(db, Some(&viewer_id), username, post_id).await?;
+
+    if !post.comments_enabled {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("Comments are turned off for this post")
+        );
+    }
+
+    // Threads are one level deep, a reply to a reply joins the thread of the top level comment
+    let parent_id = match data.parent_comment_id {
+        Some(parent_id) => {
+            let parent = find_post_comment(db, &post.id, &parent_id.to_string()).await?;
+            Some(parent.parent_id.unwrap_or(parent.id))
+        },
+        None => None,
+    };
+
+    let txn = db.begin().await?;
+
+    let comment = ActiveModel {
+        user_id: Set(viewer_id),
+        post_id: Set(post.id.clone()),
+        parent_id: Set(parent_id),
+        comment: Set(data.comment.unwrap()),
+        ..Default::default()
+    }.insert(&txn).await?;
+
+    let mention_events = create_comment_mentions(&txn, &comment).await?;
+
+    // The author's own comments can tag the post
+    if comment.user_id == post.user_id {
+        sync_post_hashtags(&txn, &post).await?;
+    }
+
+    txn.commit().await?;
+
+    events.publish(DomainEvent::CommentCreated { comment_id: comment.id });
+    for event in mention_events {
+        events.publish(event);
+    }
+
+    let comment_detail = build_comment_details(db, vec![comment]).await?
+        .pop()
+        .ok_or_else(comment_not_found)?;
+
+    Ok(comment_detail)
+}
+
+// Deleting a comment takes its replies with it
+pub async fn delete_comment(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, comment_id: &str) -> Result<()> {
+    let viewer_id = jwt_payload.get_user_id();
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+    let comment = find_post_comment(db, &post.id, comment_id).await?;
+
+    // Both the author of the comment and the owner of the post can remove it
+    if comment.user_id != viewer_id && post.user_id != viewer_id {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("You are not allowed to delete this comment")
+        );
+    }
+
+    let txn = db.begin().await?;
+
+    let reply_ids: Vec<i32> = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::ParentId.eq(comment.id))
+        .into_tuple()
+        .all(&txn)
+        .await?;
+
+    let comment_ids: Vec<i32> = reply_ids.into_iter().chain([comment.id]).collect();
+
+    delete_comments_likes(&txn, comment_ids.clone()).await?;
+    delete_comments_mentions(&txn, comment_ids.clone()).await?;
+    delete_comments_notifications(&txn, comment_ids).await?;
+
+    Entity::delete_many()
+        .filter(Column::ParentId.eq(comment.id))
+        .exec(&txn)
+        .await?;
+
+    Entity::delete_by_id(comment.id)
+        .exec(&txn)
+        .await?;
+
+    // Replies of the post author may have gone with the comment
+    sync_post_hashtags(&txn, &post).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+// Removes every comment of a post. Replies go first, the parent key is `NO ACTION`
+pub async fn delete_post_comments<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    let comment_ids: Vec<i32> = Entity::find()
+        .select_only()
+        .column(Column::Id)
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    delete_comments_likes(db, comment_ids).await?;
+
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .filter(Column::ParentId.is_not_null())
+        .exec(db)
+        .await?;
+
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
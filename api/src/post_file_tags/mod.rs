@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::posts::PostDetail;
+use crate::users::PostAuthor;
+
+pub mod post_file_tags_service;
+pub mod post_file_tags_controller;
+pub mod post_file_tags_routes;
+
+// ---- POST FILE TAGS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostFileTagDetail {
+    pub id: i32,
+    // Position of the file in the `files` of the post
+    pub file_index: usize,
+    pub user: PostAuthor,
+    pub x: f64,
+    pub y: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaggedPostsPage {
+    pub posts: Vec<PostDetail>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF POST FILE TAGS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreatePostFileTagPayload {
+    #[serde(rename = "fileIndex")]
+    #[validate(required(message = "This field is required"))]
+    pub file_index: Option<usize>,
+
+    #[validate(required(message = "This field is required"))]
+    pub username: Option<String>,
+
+    #[validate(
+    range(
+    min = 0.0,
+    max = 1.0,
+    message = "Positions are relative to the file, between 0 and 1"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub x: Option<f64>,
+
+    #[validate(
+    range(
+    min = 0.0,
+    max = 1.0,
+    message = "Positions are relative to the file, between 0 and 1"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub y: Option<f64>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
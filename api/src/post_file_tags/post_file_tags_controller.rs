@@ -0,0 +1,71 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::post_file_tags::CreatePostFileTagPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::post_file_tags_service::{create_post_tag, delete_post_tag, get_post_tags, get_tagged_posts};
+
+#[get("")]
+pub async fn get_post_tags_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    let tags = get_post_tags(&ctx.db, viewer.as_ref(), &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": tags
+        })
+    ))
+}
+
+#[post("")]
+pub async fn create_post_tag_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<CreatePostFileTagPayload>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let tag = create_post_tag(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": tag
+        })
+    ))
+}
+
+#[delete("/{tag_id}")]
+pub async fn delete_post_tag_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String, String)>) -> Result<HttpResponse> {
+    let (username, post_id, tag_id) = path.into_inner();
+
+    delete_post_tag(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id, &tag_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_tagged_posts_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_tagged_posts(&ctx.db, viewer.as_ref(), &username, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.posts,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
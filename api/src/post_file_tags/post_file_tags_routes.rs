@@ -0,0 +1,13 @@
+use actix_web::web::ServiceConfig;
+
+use super::post_file_tags_controller::{create_post_tag_handler, delete_post_tag_handler, get_post_tags_handler, get_tagged_posts_handler};
+
+pub fn get_post_file_tags_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_post_tags_handler)
+        .service(create_post_tag_handler)
+        .service(delete_post_tag_handler);
+}
+
+pub fn get_tagged_posts_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_tagged_posts_handler);
+}
@@ -0,0 +1,296 @@
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait};
+use sea_orm::sea_query::Query;
+use entity::post_file_tags::{Entity, Column, Model, ActiveModel};
+use entity::{post_files, posts};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::{ensure_not_blocked, not_blocked_condition};
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::post_comments::post_comments_service::find_visible_post;
+use crate::post_file_tags::{CreatePostFileTagPayload, PostFileTagDetail, TaggedPostsPage};
+use crate::posts::posts_service::{build_post_details, find_user_post};
+use crate::users::users_service::{ensure_can_tag, ensure_can_view_content, ensure_owner, find_user_by_username, get_post_authors, visible_authors_condition};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// Past this many people a photo is unreadable
+pub const MAX_TAGS_PER_FILE: u64 = 20;
+
+fn tag_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message("Tag not found")
+}
+
+// Ids of the files of a post, in the order of `files`
+async fn get_post_file_ids<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<Vec<i32>> {
+    let file_ids = post_files::Entity::find()
+        .select_only()
+        .column(post_files::Column::Id)
+        .filter(post_files::Column::PostId.eq(post_id.to_vec()))
+        .order_by_asc(post_files::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(file_ids)
+}
+
+fn post_files_condition(post_id: &[u8]) -> Condition {
+    let post_files = Query::select()
+        .column(post_files::Column::Id)
+        .from(post_files::Entity)
+        .and_where(post_files::Column::PostId.eq(post_id.to_vec()))
+        .to_owned();
+
+    Condition::all().add(Column::PostFileId.in_subquery(post_files))
+}
+
+async fn build_tag_details<C: ConnectionTrait>(db: &C, file_ids: &[i32], tags: Vec<Model>) -> Result<Vec<PostFileTagDetail>> {
+    let user_ids: Vec<Vec<u8>> = tags.iter().map(|tag| tag.user_id.clone()).collect();
+    let users = get_post_authors(db, user_ids).await?;
+
+    let tag_details = tags
+        .into_iter()
+        .filter_map(|tag| {
+            Some(PostFileTagDetail {
+                id: tag.id,
+                file_index: file_ids.iter().position(|file_id| *file_id == tag.post_file_id)?,
+                user: users.get(&tag.user_id)?.clone(),
+                x: tag.x,
+                y: tag.y,
+                created_at: tag.created_at,
+            })
+        })
+        .collect();
+
+    Ok(tag_details)
+}
+
+// Tags of every file of a post, people blocked either way with the viewer are left out
+pub async fn get_post_tags(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, post_id: &str) -> Result<Vec<PostFileTagDetail>> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let post = find_visible_post(db, viewer_id.as_deref(), username, post_id).await?;
+    let file_ids = get_post_file_ids(db, &post.id).await?;
+
+    let tags = Entity::find()
+        .filter(Column::PostFileId.is_in(file_ids.clone()))
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(Column::UserId, &viewer_id)))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?;
+
+    build_tag_details(db, &file_ids, tags).await
+}
+
+// Only the owner of the post can tag people, as the tagged user allows.
+// Tagging someone again on the same file moves the tag
+pub async fn create_post_tag(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, data: CreatePostFileTagPayload) -> Result<PostFileTagDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let owner = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &owner.id, post_id).await?;
+    let file_ids = get_post_file_ids(db, &post.id).await?;
+
+    let file_id = *file_ids.get(data.file_index.unwrap())
+        .ok_or_else(|| {
+            HttpResponseError::default()
+                .set_code(StatusCode::NOT_FOUND.as_u16())
+                .set_error_message("File not found")
+        })?;
+
+    let user = find_user_by_username(db, &data.username.unwrap()).await?;
+
+    ensure_not_blocked(db, &owner.id, &user.id).await?;
+    ensure_can_tag(db, &owner.id, &user).await?;
+
+    let txn = db.begin().await?;
+
+    // Tags on the same file wait for each other, so the limit below counts a snapshot
+    // taken once the concurrent ones are written
+    post_files::Entity::find_by_id(file_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?;
+
+    let existing = Entity::find()
+        .filter(Column::PostFileId.eq(file_id))
+        .filter(Column::UserId.eq(user.id.clone()))
+        .one(&txn)
+        .await?;
+
+    let (tag, newly_tagged) = match existing {
+        Some(tag) => {
+            let mut tag: ActiveModel = tag.into();
+            tag.x = Set(data.x.unwrap());
+            tag.y = Set(data.y.unwrap());
+            (tag.update(&txn).await?, false)
+        },
+        None => {
+            let tags_count = Entity::find()
+                .filter(Column::PostFileId.eq(file_id))
+                .count(&txn)
+                .await?;
+
+            if tags_count >= MAX_TAGS_PER_FILE {
+                return Err(
+                    HttpResponseError::default()
+                        .set_code(StatusCode::BAD_REQUEST.as_u16())
+                        .set_error_message("This file cannot have more tags")
+                );
+            }
+
+            // A user tagged on several files of a post is only notified once
+            let already_tagged = Entity::find()
+                .filter(post_files_condition(&post.id))
+                .filter(Column::UserId.eq(user.id.clone()))
+                .one(&txn)
+                .await?
+                .is_some();
+
+            let result = ActiveModel {
+                post_file_id: Set(file_id),
+                user_id: Set(user.id.clone()),
+                x: Set(data.x.unwrap()),
+                y: Set(data.y.unwrap()),
+                ..Default::default()
+            }.insert(&txn).await;
+
+            let tag = match result {
+                Ok(tag) => tag,
+                Err(e) if is_unique_violation(&e) => return Err(
+                    HttpResponseError::default()
+                        .set_code(StatusCode::BAD_REQUEST.as_u16())
+                        .set_error_message("This user is already tagged on this file")
+                ),
+                Err(e) => return Err(e.into()),
+            };
+
+            (tag, !already_tagged)
+        },
+    };
+
+    txn.commit().await?;
+
+    if newly_tagged {
+        events.publish(DomainEvent::UserTagged {
+            actor_id: owner.id,
+            user_id: user.id,
+            post_id: post.id,
+        });
+    }
+
+    build_tag_details(db, &file_ids, vec![tag]).await?
+        .pop()
+        .ok_or_else(tag_not_found)
+}
+
+// Both the owner of the post and the tagged user can remove a tag
+pub async fn delete_post_tag(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, tag_id: &str) -> Result<()> {
+    let viewer_id = jwt_payload.get_user_id();
+    let owner = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &owner.id, post_id).await?;
+    let tag_id: i32 = tag_id.parse().map_err(|_| tag_not_found())?;
+
+    let tag = Entity::find_by_id(tag_id)
+        .filter(post_files_condition(&post.id))
+        .one(db)
+        .await?
+        .ok_or_else(tag_not_found)?;
+
+    if viewer_id != post.user_id && viewer_id != tag.user_id {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("You are not allowed to remove this tag")
+        );
+    }
+
+    let txn = db.begin().await?;
+
+    Entity::delete_by_id(tag.id)
+        .exec(&txn)
+        .await?;
+
+    let still_tagged = Entity::find()
+        .filter(post_files_condition(&post.id))
+        .filter(Column::UserId.eq(tag.user_id.clone()))
+        .one(&txn)
+        .await?
+        .is_some();
+
+    txn.commit().await?;
+
+    if !still_tagged {
+        events.publish(DomainEvent::TagRemoved {
+            user_id: tag.user_id,
+            post_id: post.id,
+        });
+    }
+
+    Ok(())
+}
+
+// Has to run before the files of the post are deleted
+pub async fn delete_post_tags<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(post_files_condition(post_id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+fn tagged_posts_condition(user_id: &[u8]) -> Condition {
+    let tagged_files = Query::select()
+        .column(Column::PostFileId)
+        .from(Entity)
+        .and_where(Column::UserId.eq(user_id.to_vec()))
+        .to_owned();
+
+    let tagged_posts = Query::select()
+        .column(post_files::Column::PostId)
+        .from(post_files::Entity)
+        .and_where(post_files::Column::Id.in_subquery(tagged_files))
+        .to_owned();
+
+    Condition::all().add(posts::Column::Id.in_subquery(tagged_posts))
+}
+
+// The "tagged" tab of a profile, newest posts first.
+// The list follows the privacy of the tagged user, each post the visibility of its author
+pub async fn get_tagged_posts(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, query: &CursorQuery) -> Result<TaggedPostsPage> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let user = find_user_by_username(db, username).await?;
+
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut posts = posts::Entity::find()
+        .filter(tagged_posts_condition(&user.id))
+        .filter(visible_authors_condition(posts::Column::UserId, viewer_id.as_deref()))
+        .filter(not_blocked_condition(posts::Column::UserId, &user.id))
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(posts::Column::CreatedAt, posts::Column::Id)))
+        .order_by_desc(posts::Column::CreatedAt)
+        .order_by_desc(posts::Column::Id)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if posts.len() as u64 > limit {
+        posts.truncate(limit as usize);
+        posts.last().map(|post| Cursor::new(post.created_at, post.id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(TaggedPostsPage {
+        posts: build_post_details(db, posts).await?,
+        next_cursor,
+    })
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use crate::follows::Follower;
+
+pub mod post_likes_service;
+pub mod post_likes_controller;
+pub mod post_likes_routes;
+
+// ---- POST LIKES STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostLikeStatus {
+    pub liked: bool,
+    pub likes_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LikersPage {
+    pub likers: Vec<Follower>,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF POST LIKES STRUCTS ----
@@ -0,0 +1,54 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::post_likes_service::{get_post_likers, like_post, unlike_post};
+
+#[get("")]
+pub async fn get_post_likers_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String)>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_post_likers(&ctx.db, viewer.as_ref(), &username, &post_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.likers,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
+
+#[post("")]
+pub async fn like_post_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    let status = like_post(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": status
+        })
+    ))
+}
+
+#[delete("")]
+pub async fn unlike_post_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    let status = unlike_post(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": status
+        })
+    ))
+}
@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::post_likes_controller::{get_post_likers_handler, like_post_handler, unlike_post_handler};
+
+pub fn get_post_likes_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_post_likers_handler)
+        .service(like_post_handler)
+        .service(unlike_post_handler);
+}
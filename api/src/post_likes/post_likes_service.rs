@@ -0,0 +1,142 @@
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, TransactionTrait};
+use sea_orm::sea_query::Expr;
+use entity::post_likes::{Entity, Column, ActiveModel};
+use entity::{posts, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::events::{DomainEvent, EventBus};
+use crate::follows::Follower;
+use crate::post_likes::{LikersPage, PostLikeStatus};
+use crate::posts::posts_service::find_user_post;
+use crate::users::users_service::{ensure_can_view_content, find_user_by_username};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+// The counter is only ever moved by one in the same transaction as the like itself,
+// so concurrent likes on the same post serialize on the post's row
+async fn add_to_likes_count<C: ConnectionTrait>(db: &C, post_id: &[u8], delta: i32) -> Result<()> {
+    posts::Entity::update_many()
+        .col_expr(posts::Column::LikesCount, Expr::col(posts::Column::LikesCount).add(delta))
+        .filter(posts::Column::Id.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+async fn get_likes_count<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<u64> {
+    let likes_count: Option<i32> = posts::Entity::find_by_id(post_id.to_vec())
+        .select_only()
+        .column(posts::Column::LikesCount)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(likes_count.unwrap_or_default().max(0) as u64)
+}
+
+// Liking twice is not an error, the post simply stays liked
+pub async fn like_post(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str) -> Result<PostLikeStatus> {
+    let viewer_id = jwt_payload.get_user_id();
+    let user = find_user_by_username(db, username).await?;
+
+    ensure_can_view_content(db, Some(&viewer_id), &user).await?;
+
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    let txn = db.begin().await?;
+
+    let result = ActiveModel {
+        user_id: Set(viewer_id.clone()),
+        post_id: Set(post.id.clone()),
+        ..Default::default()
+    }.insert(&txn).await;
+
+    let liked_now = match result {
+        Ok(_) => {
+            add_to_likes_count(&txn, &post.id, 1).await?;
+            true
+        },
+        Err(e) if is_unique_violation(&e) => false,
+        Err(e) => return Err(e.into()),
+    };
+
+    txn.commit().await?;
+
+    if liked_now {
+        events.publish(DomainEvent::PostLiked { user_id: viewer_id, post_id: post.id.clone() });
+    }
+
+    Ok(PostLikeStatus {
+        liked: true,
+        likes_count: get_likes_count(db, &post.id).await?,
+    })
+}
+
+// Unliking works even when the post is no longer visible to the user,
+// so a like can always be taken back
+pub async fn unlike_post(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str) -> Result<PostLikeStatus> {
+    let viewer_id = jwt_payload.get_user_id();
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    let txn = db.begin().await?;
+
+    let result = Entity::delete_many()
+        .filter(Column::PostId.eq(post.id.clone()))
+        .filter(Column::UserId.eq(viewer_id.clone()))
+        .exec(&txn)
+        .await?;
+
+    if result.rows_affected > 0 {
+        add_to_likes_count(&txn, &post.id, -1).await?;
+    }
+
+    txn.commit().await?;
+
+    if result.rows_affected > 0 {
+        events.publish(DomainEvent::PostUnliked { user_id: viewer_id, post_id: post.id.clone() });
+    }
+
+    Ok(PostLikeStatus {
+        liked: false,
+        likes_count: get_likes_count(db, &post.id).await?,
+    })
+}
+
+pub async fn get_post_likers(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str, post_id: &str, query: &CursorQuery) -> Result<LikersPage> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut likes = Entity::find()
+        .find_also_related(users::Entity)
+        .filter(Column::PostId.eq(post.id))
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(Column::UserId, &viewer_id)))
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(Column::CreatedAt, Column::UserId)))
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::UserId)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if likes.len() as u64 > limit {
+        likes.truncate(limit as usize);
+        likes.last().map(|(like, _)| Cursor::new(like.created_at, like.user_id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(LikersPage {
+        likers: likes.into_iter().filter_map(|(_, user)| user.map(Follower::from)).collect(),
+        next_cursor,
+    })
+}
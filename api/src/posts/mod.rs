@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::users::PostAuthor;
+
+pub mod posts_service;
+pub mod posts_controller;
+pub mod posts_routes;
+
+// ---- POSTS STRUCTS ----
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostDetail {
+    pub id: String,
+    pub description: String,
+    pub files: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub user: PostAuthor,
+    pub likes_count: u64,
+    pub comments_count: u64,
+    pub comments_enabled: bool,
+}
+
+// The short form of a post shown in grids
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostSummary {
+    pub id: String,
+    pub likes_count: u64,
+    pub comments_count: u64,
+    pub first_file_url: Option<String>,
+}
+
+// ---- END OF POSTS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreatePostPayload {
+    #[validate(
+    length(
+    max = 2200,
+    message = "Description must be at most 2200 characters"
+    )
+    )]
+    pub description: Option<String>,
+
+    #[validate(
+    length(
+    min = 1,
+    max = 10,
+    message = "A post must have between 1 and 10 files"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub files: Option<Vec<String>>,
+
+    #[serde(rename = "commentsEnabled")]
+    pub comments_enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdatePostPayload {
+    #[validate(
+    length(
+    max = 2200,
+    message = "Description must be at most 2200 characters"
+    )
+    )]
+    pub description: Option<String>,
+
+    // Turning comments off keeps the existing ones but stops new ones
+    #[serde(rename = "commentsEnabled")]
+    pub comments_enabled: Option<bool>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,69 @@
+use actix_web::{get, post, delete, route, HttpResponse, web::{Data, Json, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::posts::{CreatePostPayload, UpdatePostPayload};
+use crate::utils::validate_data;
+use crate::Result;
+use super::posts_service::{create_post, delete_post, get_post, update_post};
+
+#[post("")]
+pub async fn create_post_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreatePostPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let post = create_post(&ctx.db, &ctx.timeline, &ctx.events, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": post
+        })
+    ))
+}
+
+#[get("/{post_id}")]
+pub async fn get_post_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    let post = get_post(&ctx.db, &ctx.impressions, viewer.as_ref(), &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": post
+        })
+    ))
+}
+
+// The spec documents PATCH, PUT is accepted as well for older clients
+#[route("/{post_id}", method = "PUT", method = "PATCH")]
+pub async fn update_post_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, payload: Json<UpdatePostPayload>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    update_post(&ctx.db, &ctx.events, &jwt_payload, &username, &post_id, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[delete("/{post_id}")]
+pub async fn delete_post_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, post_id) = path.into_inner();
+
+    delete_post(&ctx.db, &jwt_payload, &username, &post_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,18 @@
+use actix_web::web::{ServiceConfig, scope};
+use crate::insights::insights_routes::get_post_insights_routes;
+use crate::post_comments::post_comments_routes::get_post_comments_routes;
+use crate::post_file_tags::post_file_tags_routes::get_post_file_tags_routes;
+use crate::post_likes::post_likes_routes::get_post_likes_routes;
+
+use super::posts_controller::{create_post_handler, get_post_handler, update_post_handler, delete_post_handler};
+
+pub fn get_posts_routes(cfg: &mut ServiceConfig) {
+    cfg.service(create_post_handler)
+        .service(get_post_handler)
+        .service(update_post_handler)
+        .service(delete_post_handler)
+        .service(scope("/{post_id}/likes").configure(get_post_likes_routes))
+        .service(scope("/{post_id}/comments").configure(get_post_comments_routes))
+        .service(scope("/{post_id}/tags").configure(get_post_file_tags_routes))
+        .service(scope("/{post_id}/insights").configure(get_post_insights_routes));
+}
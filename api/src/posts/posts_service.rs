@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use chrono::Utc;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use uuid::Uuid;
+use entity::posts::{Entity, Column, Model, ActiveModel};
+use entity::{favorites, post_comments, post_files, post_likes};
+use crate::auth::JwtTokenPayload;
+use crate::bookmarks::bookmarks_service::delete_post_bookmarks;
+use crate::error::HttpResponseError;
+use crate::events::EventBus;
+use crate::explore::explore_service::remove_post_from_explore;
+use crate::hashtags::hashtags_service::{delete_post_hashtags, sync_post_hashtags};
+use crate::insights::{ImpressionSource, ImpressionsQueue};
+use crate::insights::insights_service::delete_post_insights;
+use crate::mentions::mentions_service::{delete_post_mentions, sync_post_mentions};
+use crate::messages::messages_service::unshare_post_from_messages;
+use crate::notifications::notifications_service::delete_post_notifications;
+use crate::post_file_tags::post_file_tags_service::delete_post_tags;
+use crate::posts::{CreatePostPayload, PostDetail, PostSummary, UpdatePostPayload};
+use crate::post_comments::post_comments_service::delete_post_comments;
+use crate::timeline::{TimelineJob, TimelineQueue};
+use crate::timeline::timeline_service::remove_post_from_timelines;
+use crate::users::users_service::{ensure_can_view_content, ensure_owner, find_user_by_username, get_post_author, get_post_authors, visible_authors_condition};
+use crate::Result;
+
+const POST_NOT_FOUND_ERROR: &str = "Post not found";
+
+fn post_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(POST_NOT_FOUND_ERROR)
+}
+
+// Find a post that belongs to the given user. An invalid uuid can never
+// match a post, so it is reported as not found as well
+pub async fn find_user_post<C: ConnectionTrait>(db: &C, user_id: &[u8], post_id: &str) -> Result<Model> {
+    let post_id = Uuid::from_str(post_id).map_err(|_| post_not_found())?;
+
+    let post = Entity::find_by_id(post_id)
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?;
+
+    post.ok_or_else(post_not_found)
+}
+
+// Find a post by id alone, as long as the viewer is allowed to see its author.
+// Hidden posts are reported as not found
+pub async fn find_visible_post_by_id<C: ConnectionTrait>(db: &C, viewer_id: &[u8], post_id: &str) -> Result<Model> {
+    let post_id = Uuid::from_str(post_id).map_err(|_| post_not_found())?;
+
+    let post = Entity::find_by_id(post_id)
+        .filter(visible_authors_condition(Column::UserId, Some(viewer_id)))
+        .one(db)
+        .await?;
+
+    post.ok_or_else(post_not_found)
+}
+
+pub async fn get_post_files<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<Vec<String>> {
+    let files = post_files::Entity::find()
+        .filter(post_files::Column::PostId.eq(post_id.to_vec()))
+        .order_by_asc(post_files::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|file| file.file_url)
+        .collect();
+
+    Ok(files)
+}
+
+pub async fn count_post_comments<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<u64> {
+    let comments_count = post_comments::Entity::find()
+        .filter(post_comments::Column::PostId.eq(post_id.to_vec()))
+        .count(db)
+        .await?;
+
+    Ok(comments_count)
+}
+
+async fn count_by_post<E, C>(db: &C, post_id_col: E::Column, post_ids: &[Vec<u8>]) -> Result<HashMap<Vec<u8>, u64>>
+    where
+        E: EntityTrait,
+        C: ConnectionTrait,
+{
+    let counts = E::find()
+        .select_only()
+        .column(post_id_col)
+        .column_as(post_id_col.count(), "count")
+        .filter(post_id_col.is_in(post_ids.to_vec()))
+        .group_by(post_id_col)
+        .into_tuple::<(Vec<u8>, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(post_id, count)| (post_id, count as u64))
+        .collect();
+
+    Ok(counts)
+}
+
+// Builds the detail view of many posts with a fixed number of queries,
+// used by every endpoint that returns a list of posts
+pub async fn build_post_details<C: ConnectionTrait>(db: &C, posts: Vec<Model>) -> Result<Vec<PostDetail>> {
+    if posts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let post_ids: Vec<Vec<u8>> = posts.iter().map(|post| post.id.clone()).collect();
+    let author_ids: Vec<Vec<u8>> = posts.iter().map(|post| post.user_id.clone()).collect::<HashSet<_>>().into_iter().collect();
+
+    let mut files: HashMap<Vec<u8>, Vec<String>> = HashMap::new();
+    post_files::Entity::find()
+        .filter(post_files::Column::PostId.is_in(post_ids.clone()))
+        .order_by_asc(post_files::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .for_each(|file| files.entry(file.post_id).or_default().push(file.file_url));
+
+    let authors = get_post_authors(db, author_ids).await?;
+
+    let comments_counts = count_by_post::<post_comments::Entity, _>(db, post_comments::Column::PostId, &post_ids).await?;
+
+    let post_details = posts
+        .into_iter()
+        .filter_map(|post| {
+            // An author can have several posts in the same page
+            let author = authors.get(&post.user_id)?.clone();
+
+            Some(PostDetail {
+                id: Uuid::from_slice(&post.id).unwrap().to_string(),
+                files: files.remove(&post.id).unwrap_or_default(),
+                user: author,
+                likes_count: post.likes_count.max(0) as u64,
+                comments_count: comments_counts.get(&post.id).copied().unwrap_or_default(),
+                comments_enabled: post.comments_enabled,
+                description: post.description,
+                created_at: post.created_at,
+                updated_at: post.updated_at,
+            })
+        })
+        .collect();
+
+    Ok(post_details)
+}
+
+// Same as `build_post_details` for the grid view, which only needs the first file of each post
+pub async fn build_post_summaries<C: ConnectionTrait>(db: &C, posts: Vec<Model>) -> Result<Vec<PostSummary>> {
+    if posts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let post_ids: Vec<Vec<u8>> = posts.iter().map(|post| post.id.clone()).collect();
+
+    let mut first_files: HashMap<Vec<u8>, String> = HashMap::new();
+    post_files::Entity::find()
+        .filter(post_files::Column::PostId.is_in(post_ids.clone()))
+        .order_by_asc(post_files::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .for_each(|file| {
+            first_files.entry(file.post_id).or_insert(file.file_url);
+        });
+
+    let comments_counts = count_by_post::<post_comments::Entity, _>(db, post_comments::Column::PostId, &post_ids).await?;
+
+    let post_summaries = posts
+        .into_iter()
+        .map(|post| PostSummary {
+            id: Uuid::from_slice(&post.id).unwrap().to_string(),
+            likes_count: post.likes_count.max(0) as u64,
+            comments_count: comments_counts.get(&post.id).copied().unwrap_or_default(),
+            first_file_url: first_files.remove(&post.id),
+        })
+        .collect();
+
+    Ok(post_summaries)
+}
+
+pub async fn get_post(db: &DatabaseConnection, impressions: &ImpressionsQueue, viewer: Option<&JwtTokenPayload>, username: &str, post_id: &str) -> Result<PostDetail> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    impressions.record(viewer_id, vec![post.id.clone()], ImpressionSource::Profile);
+
+    Ok(PostDetail {
+        id: Uuid::from_slice(&post.id).unwrap().to_string(),
+        files: get_post_files(db, &post.id).await?,
+        user: get_post_author(db, &user).await?,
+        likes_count: post.likes_count.max(0) as u64,
+        comments_count: count_post_comments(db, &post.id).await?,
+        comments_enabled: post.comments_enabled,
+        description: post.description,
+        created_at: post.created_at,
+        updated_at: post.updated_at,
+    })
+}
+
+pub async fn create_post(db: &DatabaseConnection, timeline: &TimelineQueue, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, data: CreatePostPayload) -> Result<PostDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    let txn = db.begin().await?;
+
+    let post = ActiveModel {
+        id: Set(Vec::from(Uuid::new_v4())),
+        user_id: Set(user.id),
+        description: Set(data.description.unwrap_or_default()),
+        comments_enabled: Set(data.comments_enabled.unwrap_or(true)),
+        ..Default::default()
+    }.insert(&txn).await?;
+
+    let files = data.files.unwrap()
+        .into_iter()
+        .map(|file_url| post_files::ActiveModel {
+            post_id: Set(post.id.clone()),
+            file_url: Set(file_url),
+            ..Default::default()
+        });
+
+    post_files::Entity::insert_many(files)
+        .exec(&txn)
+        .await?;
+
+    sync_post_hashtags(&txn, &post).await?;
+    let mention_events = sync_post_mentions(&txn, &post).await?;
+
+    txn.commit().await?;
+
+    for event in mention_events {
+        events.publish(event);
+    }
+
+    // Followers' timelines are filled in the background
+    timeline.enqueue(TimelineJob::FanOutPost { post_id: post.id.clone() });
+
+    let post_detail = build_post_details(db, vec![post]).await?
+        .pop()
+        .ok_or_else(post_not_found)?;
+
+    Ok(post_detail)
+}
+
+pub async fn update_post(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str, data: UpdatePostPayload) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let description_changed = data.description.is_some();
+
+    let mut post: ActiveModel = post.into();
+    if let Some(description) = data.description {
+        post.description = Set(description);
+    }
+    if let Some(comments_enabled) = data.comments_enabled {
+        post.comments_enabled = Set(comments_enabled);
+    }
+    post.updated_at = Set(Utc::now());
+
+    let txn = db.begin().await?;
+
+    let post = post.update(&txn).await?;
+
+    let mut mention_events = Vec::new();
+    if description_changed {
+        sync_post_hashtags(&txn, &post).await?;
+        mention_events = sync_post_mentions(&txn, &post).await?;
+    }
+
+    txn.commit().await?;
+
+    for event in mention_events {
+        events.publish(event);
+    }
+
+    Ok(())
+}
+
+pub async fn delete_post(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, post_id: &str) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let post = find_user_post(db, &user.id, post_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    // The foreign keys to posts are all `NO ACTION`,
+    // so the dependent rows have to go first
+    let txn = db.begin().await?;
+
+    remove_post_from_timelines(&txn, &post.id).await?;
+    remove_post_from_explore(&txn, &post.id).await?;
+    delete_post_insights(&txn, &post.id).await?;
+
+    delete_post_tags(&txn, &post.id).await?;
+
+    post_files::Entity::delete_many()
+        .filter(post_files::Column::PostId.eq(post.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    // Mentions and notifications point to comments as well
+    delete_post_mentions(&txn, &post.id).await?;
+    delete_post_notifications(&txn, &post.id).await?;
+
+    delete_post_comments(&txn, &post.id).await?;
+
+    post_likes::Entity::delete_many()
+        .filter(post_likes::Column::PostId.eq(post.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    delete_post_bookmarks(&txn, &post.id).await?;
+
+    delete_post_hashtags(&txn, &post.id).await?;
+
+    unshare_post_from_messages(&txn, &post.id).await?;
+
+    favorites::Entity::delete_many()
+        .filter(favorites::Column::PostId.eq(post.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    Entity::delete_by_id(post.id)
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use futures::channel::mpsc::Receiver;
+use serde::{Deserialize, Serialize};
+use crate::messages::MessageDetail;
+use crate::notifications::NotificationDetail;
+use crate::users::PostAuthor;
+
+pub mod realtime_broker;
+pub mod realtime_service;
+pub mod realtime_controller;
+pub mod realtime_routes;
+
+// ---- REALTIME STRUCTS ----
+
+// How many events can wait for a slow client before it is disconnected
+pub const SUBSCRIBER_BUFFER: usize = 64;
+
+// What connected clients are pushed, sent as the `data` of an event named by `name`.
+// Chat events go to the chat socket, the others to the server-sent events stream
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RealtimeEvent {
+    Notification(NotificationDetail),
+    UnreadCount(UnreadCount),
+    Story(StoryUpdate),
+    Message(Box<MessageDetail>),
+    Typing(TypingUpdate),
+    Read(ReadReceipt),
+    // Too many messages were missed to replay them, the client reloads its conversations instead
+    Resync,
+}
+
+impl RealtimeEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RealtimeEvent::Notification(_) => "notification",
+            RealtimeEvent::UnreadCount(_) => "unread_count",
+            RealtimeEvent::Story(_) => "story",
+            RealtimeEvent::Message(_) => "message",
+            RealtimeEvent::Typing(_) => "typing",
+            RealtimeEvent::Read(_) => "read",
+            RealtimeEvent::Resync => "resync",
+        }
+    }
+
+    pub fn is_chat(&self) -> bool {
+        matches!(self, RealtimeEvent::Message(_) | RealtimeEvent::Typing(_) | RealtimeEvent::Read(_) | RealtimeEvent::Resync)
+    }
+
+    // Worthless once late, dropped rather than queued for a slow client
+    pub fn is_ephemeral(&self) -> bool {
+        matches!(self, RealtimeEvent::Typing(_))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreadCount {
+    pub unread_count: u64,
+}
+
+// A followed user posted a story
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryUpdate {
+    pub user: PostAuthor,
+    pub story_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypingUpdate {
+    pub conversation_id: String,
+    pub username: String,
+}
+
+// The user has read every message of the conversation up to `message_id`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadReceipt {
+    pub conversation_id: String,
+    pub username: String,
+    pub message_id: i32,
+}
+
+// Routes realtime events to the streams of the users they are meant for.
+// The in-process broker only reaches clients connected to this instance,
+// running several instances needs a broker backed by an external pub/sub system
+pub trait Broker: Send + Sync {
+    // The receiver is dropped when the client disconnects, which unsubscribes it.
+    // It ends when the client falls more than `SUBSCRIBER_BUFFER` events behind
+    fn subscribe(&self, user_id: &[u8]) -> Receiver<RealtimeEvent>;
+
+    // Users without connected clients simply miss the event
+    fn publish(&self, user_id: &[u8], event: RealtimeEvent);
+}
+
+// ---- END OF REALTIME STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatQuery {
+    // Browsers cannot set headers on a WebSocket handshake
+    pub access_token: Option<String>,
+    // The latest message the client received, the ones after it are replayed
+    pub last_message_id: Option<i32>,
+}
+
+// What the client sends over the chat socket
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum ChatCommand {
+    Typing { conversation_id: String },
+    Read { conversation_id: String, message_id: i32 },
+}
+
+// ---- END OF REQUEST PAYLOAD ----
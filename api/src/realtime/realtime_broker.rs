@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use futures::channel::mpsc::{self, Receiver, Sender};
+use crate::realtime::{Broker, RealtimeEvent, SUBSCRIBER_BUFFER};
+
+// Keeps the subscribers in memory, a user can be connected from several clients
+#[derive(Debug, Default)]
+pub struct InProcessBroker {
+    subscribers: Mutex<HashMap<Vec<u8>, Vec<Sender<RealtimeEvent>>>>,
+}
+
+impl Broker for InProcessBroker {
+    fn subscribe(&self, user_id: &[u8]) -> Receiver<RealtimeEvent> {
+        let (sender, receiver) = mpsc::channel(SUBSCRIBER_BUFFER);
+
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(user_id.to_vec())
+            .or_default()
+            .push(sender);
+
+        receiver
+    }
+
+    fn publish(&self, user_id: &[u8], event: RealtimeEvent) {
+        let mut subscribers = self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let Some(senders) = subscribers.get_mut(user_id) else {
+            return;
+        };
+
+        // Disconnected clients are dropped on the way. So are the clients too slow to keep up,
+        // dropping their sender ends their stream once they read what is already queued
+        senders.retain_mut(|sender| match sender.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(e) => e.is_full() && event.is_ephemeral(),
+        });
+
+        if senders.is_empty() {
+            subscribers.remove(user_id);
+        }
+    }
+}
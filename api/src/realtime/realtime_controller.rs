@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+use actix_web::{get, FromRequest, HttpRequest, HttpResponse, web::{Bytes, Data, Path, Payload, Query}, http::StatusCode};
+use actix_ws::{CloseCode, CloseReason, Message, ProtocolError, Session};
+use futures::{stream, Stream, StreamExt};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::conversations::MarkConversationReadPayload;
+use crate::conversations::conversations_service::mark_conversation_read;
+use crate::error::HttpResponseError;
+use crate::realtime::{ChatCommand, ChatQuery, RealtimeEvent};
+use crate::utils::jwt;
+use crate::Result;
+use super::realtime_service::{open_chat, open_stream, publish_typing};
+
+// Proxies close connections that stay quiet for too long
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+// A chat client that answers none of the pings in this time is gone
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(45);
+
+fn to_server_sent_event(event: RealtimeEvent) -> Bytes {
+    let data = serde_json::to_string(&event).unwrap_or_default();
+
+    Bytes::from(format!("event: {}\ndata: {}\n\n", event.name(), data))
+}
+
+#[get("")]
+pub async fn stream_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let events = open_stream(&ctx.db, ctx.broker.as_ref(), &jwt_payload, &username).await?
+        .map(to_server_sent_event);
+
+    let keep_alive = stream::unfold((), |_| async {
+        actix_web::rt::time::sleep(KEEP_ALIVE_INTERVAL).await;
+        Some((Bytes::from_static(b": keep-alive\n\n"), ()))
+    });
+
+    let body = stream::select(events, keep_alive)
+        .map(Ok::<_, actix_web::Error>);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(body))
+}
+
+enum ChatInput {
+    Event(RealtimeEvent),
+    // The broker dropped the subscription of a client that could not keep up
+    Lagged,
+    Client(std::result::Result<Message, ProtocolError>),
+    Disconnected,
+    Heartbeat,
+}
+
+fn to_chat_frame(name: &str, data: impl serde::Serialize) -> String {
+    json!({ "type": name, "data": data }).to_string()
+}
+
+// The token goes in the Authorization header like on every other endpoint,
+// or in the `accessToken` query parameter for clients that cannot set headers
+async fn authenticate_chat(req: &HttpRequest, ctx: &AppState, access_token: Option<&str>) -> Result<JwtTokenPayload> {
+    match access_token {
+        Some(access_token) if !req.headers().contains_key("Authorization") => jwt::verify(access_token, &ctx.config.jwt),
+        _ => JwtTokenPayload::extract(req).await,
+    }
+}
+
+async fn handle_chat_command(ctx: &AppState, jwt_payload: &JwtTokenPayload, username: &str, text: &str) -> Result<()> {
+    let command: ChatCommand = serde_json::from_str(text).map_err(|_| {
+        HttpResponseError::default()
+            .set_code(StatusCode::BAD_REQUEST.as_u16())
+            .set_error_message("Unknown chat command")
+    })?;
+
+    match command {
+        ChatCommand::Typing { conversation_id } => {
+            publish_typing(&ctx.db, ctx.broker.as_ref(), jwt_payload, &conversation_id).await
+        },
+        ChatCommand::Read { conversation_id, message_id } => {
+            let payload = MarkConversationReadPayload { message_id: Some(message_id) };
+            mark_conversation_read(&ctx.db, &ctx.events, jwt_payload, username, &conversation_id, payload).await
+        },
+    }
+}
+
+// Runs until either side closes the socket. Pushing to a slow client waits for it,
+// which leaves the events to pile up in the broker until it drops the subscription
+async fn run_chat_session(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, username: String, mut session: Session, inputs: impl Stream<Item = ChatInput>) {
+    let mut inputs = Box::pin(inputs);
+    let mut last_seen = Instant::now();
+
+    let reason = loop {
+        let Some(input) = inputs.next().await else {
+            break None;
+        };
+
+        let sent = match input {
+            ChatInput::Event(event) => session.text(to_chat_frame(event.name(), &event)).await,
+            ChatInput::Client(Ok(Message::Text(text))) => {
+                last_seen = Instant::now();
+
+                match handle_chat_command(&ctx, &jwt_payload, &username, &text).await {
+                    Ok(()) => Ok(()),
+                    Err(e) => session.text(to_chat_frame("error", &e)).await,
+                }
+            },
+            ChatInput::Client(Ok(Message::Ping(bytes))) => {
+                last_seen = Instant::now();
+                session.pong(&bytes).await
+            },
+            ChatInput::Client(Ok(Message::Close(reason))) => break reason,
+            ChatInput::Client(Ok(_)) => {
+                last_seen = Instant::now();
+                Ok(())
+            },
+            ChatInput::Client(Err(e)) => {
+                tracing::error!("Chat protocol error: {:?}", e);
+                break Some(CloseReason::from(CloseCode::Protocol));
+            },
+            ChatInput::Disconnected => return,
+            ChatInput::Heartbeat if last_seen.elapsed() > CLIENT_TIMEOUT => break Some(CloseReason::from(CloseCode::Away)),
+            ChatInput::Heartbeat => session.ping(b"").await,
+            ChatInput::Lagged => break Some(CloseReason {
+                code: CloseCode::Again,
+                description: Some(String::from("Too far behind, reconnect with the last message id")),
+            }),
+        };
+
+        if sent.is_err() {
+            return;
+        }
+    };
+
+    let _ = session.close(reason).await;
+}
+
+#[get("")]
+pub async fn chat_handler(ctx: Data<AppState>, req: HttpRequest, body: Payload, path: Path<String>, query: Query<ChatQuery>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let query = query.into_inner();
+
+    let jwt_payload = authenticate_chat(&req, &ctx, query.access_token.as_deref()).await?;
+
+    let events = open_chat(&ctx.db, ctx.broker.as_ref(), &jwt_payload, &username, query.last_message_id).await?
+        .map(ChatInput::Event)
+        .chain(stream::once(async { ChatInput::Lagged }));
+
+    let (response, session, messages) = actix_ws::handle(&req, body).map_err(|_| {
+        HttpResponseError::default()
+            .set_code(StatusCode::BAD_REQUEST.as_u16())
+            .set_error_message("Expected a WebSocket handshake")
+    })?;
+
+    let messages = messages
+        .map(ChatInput::Client)
+        .chain(stream::once(async { ChatInput::Disconnected }));
+
+    let heartbeats = stream::unfold((), |_| async {
+        actix_web::rt::time::sleep(KEEP_ALIVE_INTERVAL).await;
+        Some((ChatInput::Heartbeat, ()))
+    });
+
+    let inputs = stream::select(events, stream::select(messages, heartbeats));
+
+    actix_web::rt::spawn(run_chat_session(ctx, jwt_payload, username, session, inputs));
+
+    Ok(response)
+}
@@ -0,0 +1,11 @@
+use actix_web::web::ServiceConfig;
+
+use super::realtime_controller::{chat_handler, stream_handler};
+
+pub fn get_stream_routes(cfg: &mut ServiceConfig) {
+    cfg.service(stream_handler);
+}
+
+pub fn get_chat_routes(cfg: &mut ServiceConfig) {
+    cfg.service(chat_handler);
+}
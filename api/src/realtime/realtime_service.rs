@@ -0,0 +1,225 @@
+use futures::{future, stream, Stream, StreamExt};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::sea_query::Query;
+use uuid::Uuid;
+use entity::{conversation_participants, follows, messages, mutes, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::conversations::conversations_service::find_user_conversation;
+use crate::events::DomainEvent;
+use crate::messages::messages_service::build_message_details;
+use crate::notifications::NotificationChanges;
+use crate::notifications::notifications_service::{count_user_unread_notifications, list_user_notifications};
+use crate::realtime::{Broker, ReadReceipt, RealtimeEvent, StoryUpdate, TypingUpdate, UnreadCount};
+use crate::users::users_service::{ensure_owner, find_user_by_username, get_post_author};
+use crate::Result;
+
+// A client further behind reloads its conversations instead of having them replayed
+const MAX_REPLAYED_MESSAGES: u64 = 100;
+
+async fn push_unread_count<C: ConnectionTrait>(db: &C, broker: &dyn Broker, user_id: &[u8]) -> Result<()> {
+    let unread_count = count_user_unread_notifications(db, user_id).await?;
+
+    broker.publish(user_id, RealtimeEvent::UnreadCount(UnreadCount { unread_count }));
+
+    Ok(())
+}
+
+// Followers see the new story in their tray, unless they muted its author
+async fn push_story<C: ConnectionTrait>(db: &C, broker: &dyn Broker, user_id: &[u8], story_id: &[u8]) -> Result<()> {
+    let Some(user) = users::Entity::find_by_id(user_id.to_vec()).one(db).await? else {
+        return Ok(());
+    };
+
+    let muters = Query::select()
+        .column(mutes::Column::MuterId)
+        .from(mutes::Entity)
+        .and_where(mutes::Column::MutedId.eq(user_id.to_vec()))
+        .to_owned();
+
+    let follower_ids: Vec<Vec<u8>> = follows::Entity::find()
+        .select_only()
+        .column(follows::Column::FollowerId)
+        .filter(follows::Column::FolloweeId.eq(user_id.to_vec()))
+        .filter(follows::Column::FollowerId.not_in_subquery(muters))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let update = StoryUpdate {
+        user: get_post_author(db, &user).await?,
+        story_id: Uuid::from_slice(story_id).unwrap_or_default().to_string(),
+    };
+
+    for follower_id in follower_ids {
+        broker.publish(&follower_id, RealtimeEvent::Story(update.clone()));
+    }
+
+    Ok(())
+}
+
+// Participants of the conversation other than the user, leaving out the ones blocked either way
+async fn get_reachable_participant_ids<C: ConnectionTrait>(db: &C, conversation_id: &[u8], user_id: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let user_ids = conversation_participants::Entity::find()
+        .select_only()
+        .column(conversation_participants::Column::UserId)
+        .filter(conversation_participants::Column::ConversationId.eq(conversation_id.to_vec()))
+        .filter(conversation_participants::Column::UserId.ne(user_id.to_vec()))
+        .filter(not_blocked_condition(conversation_participants::Column::UserId, user_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(user_ids)
+}
+
+// The sender's other clients get the message as well. Each participant sees it
+// as they would through the API, a shared post they cannot see is left out
+async fn push_message<C: ConnectionTrait>(db: &C, broker: &dyn Broker, message_id: i32) -> Result<()> {
+    // Deleted before it could be delivered
+    let Some(message) = messages::Entity::find_by_id(message_id).one(db).await? else {
+        return Ok(());
+    };
+
+    let mut recipient_ids = get_reachable_participant_ids(db, &message.conversation_id, &message.sender_id).await?;
+    recipient_ids.push(message.sender_id.clone());
+
+    for recipient_id in recipient_ids {
+        let message_detail = build_message_details(db, &recipient_id, vec![message.clone()]).await?.pop();
+
+        if let Some(message_detail) = message_detail {
+            broker.publish(&recipient_id, RealtimeEvent::Message(Box::new(message_detail)));
+        }
+    }
+
+    Ok(())
+}
+
+async fn push_read_receipt<C: ConnectionTrait>(db: &C, broker: &dyn Broker, conversation_id: &[u8], user_id: &[u8], message_id: i32) -> Result<()> {
+    let Some(user) = users::Entity::find_by_id(user_id.to_vec()).one(db).await? else {
+        return Ok(());
+    };
+
+    let receipt = ReadReceipt {
+        conversation_id: Uuid::from_slice(conversation_id).unwrap_or_default().to_string(),
+        username: user.username,
+        message_id,
+    };
+
+    for participant_id in get_reachable_participant_ids(db, conversation_id, user_id).await? {
+        broker.publish(&participant_id, RealtimeEvent::Read(receipt.clone()));
+    }
+
+    Ok(())
+}
+
+// Called by the events worker once the notifications of the event are written
+pub async fn push_event(db: &DatabaseConnection, broker: &dyn Broker, event: &DomainEvent, changes: NotificationChanges) -> Result<()> {
+    for user_id in changes.notified {
+        // The latest group holds the new notification, grouped with the ones before it
+        let latest = list_user_notifications(db, &user_id, 1, None).await?
+            .notifications
+            .pop();
+
+        if let Some(notification) = latest {
+            broker.publish(&user_id, RealtimeEvent::Notification(notification));
+        }
+
+        push_unread_count(db, broker, &user_id).await?;
+    }
+
+    for user_id in changes.withdrawn {
+        push_unread_count(db, broker, &user_id).await?;
+    }
+
+    match event {
+        DomainEvent::StoryPosted { user_id, story_id } => push_story(db, broker, user_id, story_id).await?,
+        DomainEvent::MessageSent { message_id } => push_message(db, broker, *message_id).await?,
+        DomainEvent::ConversationRead { conversation_id, user_id, message_id } => {
+            push_read_receipt(db, broker, conversation_id, user_id, *message_id).await?
+        },
+        _ => {},
+    }
+
+    Ok(())
+}
+
+// Events pushed to the user from now on, starting with the current unread count
+pub async fn open_stream(db: &DatabaseConnection, broker: &dyn Broker, jwt_payload: &JwtTokenPayload, username: &str) -> Result<impl Stream<Item = RealtimeEvent>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    // Subscribing first, nothing published while counting is missed
+    let receiver = broker.subscribe(&user.id);
+    let unread_count = count_user_unread_notifications(db, &user.id).await?;
+
+    let events = receiver.filter(|event| future::ready(!event.is_chat()));
+
+    Ok(stream::once(async move { RealtimeEvent::UnreadCount(UnreadCount { unread_count }) }).chain(events))
+}
+
+// Chat events pushed to the user from now on. A reconnecting client first gets
+// the messages it missed since `last_message_id`, oldest first
+pub async fn open_chat(db: &DatabaseConnection, broker: &dyn Broker, jwt_payload: &JwtTokenPayload, username: &str, last_message_id: Option<i32>) -> Result<impl Stream<Item = RealtimeEvent>> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    // Subscribing first, nothing sent while replaying is missed
+    let receiver = broker.subscribe(&user.id);
+
+    let mut replayed = Vec::new();
+    let mut replayed_up_to = 0;
+
+    if let Some(last_message_id) = last_message_id {
+        let conversation_ids = Query::select()
+            .column(conversation_participants::Column::ConversationId)
+            .from(conversation_participants::Entity)
+            .and_where(conversation_participants::Column::UserId.eq(user.id.clone()))
+            .to_owned();
+
+        // One extra row tells whether the client is too far behind
+        let missed = messages::Entity::find()
+            .filter(messages::Column::ConversationId.in_subquery(conversation_ids))
+            .filter(messages::Column::Id.gt(last_message_id))
+            .filter(not_blocked_condition(messages::Column::SenderId, &user.id))
+            .order_by_asc(messages::Column::Id)
+            .limit(MAX_REPLAYED_MESSAGES + 1)
+            .all(db)
+            .await?;
+
+        if missed.len() as u64 > MAX_REPLAYED_MESSAGES {
+            replayed_up_to = last_message_id;
+            replayed.push(RealtimeEvent::Resync);
+        } else {
+            replayed_up_to = missed.last().map_or(last_message_id, |message| message.id);
+            replayed.extend(build_message_details(db, &user.id, missed).await?.into_iter().map(|message| RealtimeEvent::Message(Box::new(message))));
+        }
+    }
+
+    // A message sent while replaying is both replayed and pushed
+    let events = receiver.filter(move |event| future::ready(match event {
+        RealtimeEvent::Message(message) => message.id > replayed_up_to,
+        event => event.is_chat(),
+    }));
+
+    Ok(stream::iter(replayed).chain(events))
+}
+
+// Tells the other participants the user is typing, nothing is stored
+pub async fn publish_typing(db: &DatabaseConnection, broker: &dyn Broker, jwt_payload: &JwtTokenPayload, conversation_id: &str) -> Result<()> {
+    let user_id = jwt_payload.get_user_id();
+    let (conversation, _) = find_user_conversation(db, &user_id, conversation_id).await?;
+
+    let update = TypingUpdate {
+        conversation_id: Uuid::from_slice(&conversation.id).unwrap_or_default().to_string(),
+        username: jwt_payload.username.clone(),
+    };
+
+    for participant_id in get_reachable_participant_ids(db, &conversation.id, &user_id).await? {
+        broker.publish(&participant_id, RealtimeEvent::Typing(update.clone()));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,18 @@
 use actix_web::web::{ServiceConfig, scope};
 use super::auth::auth_routes::get_auth_routes;
+use super::explore::explore_routes::get_explore_routes;
+use super::feed::feed_routes::get_feed_routes;
+use super::hashtags::hashtags_routes::get_hashtags_routes;
+use super::search::search_routes::get_search_routes;
+use super::story_views::story_views_routes::get_story_views_routes;
+use super::users::users_routes::get_users_routes;
 
 pub fn get_v1_routes(cfg: &mut ServiceConfig) {
-    cfg.service(scope("/auth").configure(get_auth_routes));
+    cfg.service(scope("/auth").configure(get_auth_routes))
+        .service(scope("/users").configure(get_users_routes))
+        .service(scope("/feed").configure(get_feed_routes))
+        .service(scope("/explore").configure(get_explore_routes))
+        .service(scope("/stories").configure(get_story_views_routes))
+        .service(scope("/hashtags").configure(get_hashtags_routes))
+        .service(scope("/search").configure(get_search_routes));
 }
\ No newline at end of file
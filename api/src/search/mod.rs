@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+use crate::hashtags::HashtagDetail;
+use crate::posts::PostDetail;
+
+pub mod search_service;
+pub mod search_controller;
+pub mod search_routes;
+
+// What a search can be narrowed to with `type`
+pub const SEARCH_TYPE_USERS: &str = "users";
+pub const SEARCH_TYPE_POSTS: &str = "posts";
+pub const SEARCH_TYPE_HASHTAGS: &str = "hashtags";
+
+// Results of each type when no limit is given
+pub const DEFAULT_SEARCH_LIMIT: u64 = 10;
+
+fn check_search_type(value: &str) -> Result<(), ValidationError> {
+    if ![SEARCH_TYPE_USERS, SEARCH_TYPE_POSTS, SEARCH_TYPE_HASHTAGS].contains(&value) {
+        let mut val_error = ValidationError::new("invalid_search_type");
+        val_error.message = Some(Cow::from("Must be one of users, posts or hashtags"));
+        return Err(val_error);
+    }
+
+    Ok(())
+}
+
+// ---- SEARCH STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSearchResult {
+    pub username: String,
+    pub name: String,
+    pub picture_url: String,
+    pub is_private: bool,
+    pub has_stories: bool,
+    // Only known when the request is authenticated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub following: Option<bool>,
+}
+
+// Best matches first. A type left out by `type` is not in the response
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<UserSearchResult>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub posts: Option<Vec<PostDetail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hashtags: Option<Vec<HashtagDetail>>,
+}
+
+// ---- END OF SEARCH STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SearchQuery {
+    #[validate(
+    length(
+    min = 1,
+    max = 100,
+    message = "Search must be between 1 and 100 characters"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub q: Option<String>,
+
+    #[serde(rename = "type")]
+    #[validate(custom = "check_search_type")]
+    pub search_type: Option<String>,
+
+    #[validate(range(min = 1, max = 50, message = "Limit must be between 1 and 50"))]
+    pub limit: Option<u64>,
+}
+
+impl SearchQuery {
+    pub fn includes(&self, search_type: &str) -> bool {
+        self.search_type.as_deref().is_none_or(|wanted| wanted == search_type)
+    }
+
+    pub fn get_limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT)
+    }
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,24 @@
+use actix_web::{get, HttpResponse, web::{Data, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::search::SearchQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::search_service::search;
+
+#[get("")]
+pub async fn search_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, query: Query<SearchQuery>) -> Result<HttpResponse> {
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let results = search(&ctx.db, viewer.as_ref(), &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": results
+        })
+    ))
+}
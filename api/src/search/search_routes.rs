@@ -0,0 +1,7 @@
+use actix_web::web::ServiceConfig;
+
+use super::search_controller::search_handler;
+
+pub fn get_search_routes(cfg: &mut ServiceConfig) {
+    cfg.service(search_handler);
+}
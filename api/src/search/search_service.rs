@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Value};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use entity::{follows, hashtags, posts, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::hashtags::HashtagDetail;
+use crate::hashtags::hashtags_service::build_hashtag_details;
+use crate::posts::PostDetail;
+use crate::posts::posts_service::build_post_details;
+use crate::search::{SearchQuery, SearchResults, UserSearchResult, SEARCH_TYPE_HASHTAGS, SEARCH_TYPE_POSTS, SEARCH_TYPE_USERS};
+use crate::users::users_service::{get_post_authors, visible_authors_condition};
+use crate::utils::text::{to_fulltext_query, to_prefix_pattern};
+use crate::Result;
+
+// Relevance of a row, the sum of the SQL terms added to it. The full-text relevance is
+// a small number, so the boosts rank exact names and what the viewer follows first
+#[derive(Default)]
+struct Score {
+    terms: Vec<&'static str>,
+    values: Vec<Value>,
+}
+
+impl Score {
+    fn add(mut self, term: &'static str, values: impl IntoIterator<Item = Value>) -> Self {
+        self.terms.push(term);
+        self.values.extend(values);
+        self
+    }
+
+    fn into_expr(self) -> SimpleExpr {
+        if self.terms.is_empty() {
+            return Expr::cust("0");
+        }
+
+        Expr::cust_with_values(self.terms.join(" + "), self.values)
+    }
+}
+
+// Usernames match as a prefix of the whole search, names word by word
+async fn search_users<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, search: &str, limit: u64) -> Result<Vec<UserSearchResult>> {
+    let username = search.trim().trim_start_matches('@');
+    let fulltext = to_fulltext_query(search);
+
+    let mut matched = Condition::any();
+    let mut score = Score::default();
+
+    if !username.is_empty() {
+        let prefix = to_prefix_pattern(username);
+        matched = matched.add(users::Column::Username.like(prefix.clone()));
+        score = score
+            .add("(users.username = ?) * 10", [username.into()])
+            .add("(users.username LIKE ?) * 2", [prefix.into()]);
+    }
+
+    if let Some(fulltext) = fulltext {
+        matched = matched.add(Expr::cust_with_values("MATCH (users.username, users.name) AGAINST (? IN BOOLEAN MODE)", [fulltext.clone()]));
+        score = score.add("MATCH (users.username, users.name) AGAINST (? IN BOOLEAN MODE)", [fulltext.into()]);
+    }
+
+    if matched.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(viewer_id) = viewer_id {
+        score = score.add("(users.id IN (SELECT followee_id FROM follows WHERE follower_id = ?)) * 5", [viewer_id.to_vec().into()]);
+    }
+
+    let users = users::Entity::find()
+        .filter(matched)
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(users::Column::Id, viewer_id)))
+        .order_by_desc(score.into_expr())
+        .order_by_asc(users::Column::Username)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    let user_ids: Vec<Vec<u8>> = users.iter().map(|user| user.id.clone()).collect();
+    let authors = get_post_authors(db, user_ids.clone()).await?;
+
+    let followed: HashSet<Vec<u8>> = match viewer_id {
+        Some(viewer_id) => follows::Entity::find()
+            .select_only()
+            .column(follows::Column::FolloweeId)
+            .filter(follows::Column::FollowerId.eq(viewer_id.to_vec()))
+            .filter(follows::Column::FolloweeId.is_in(user_ids))
+            .into_tuple()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    let results = users
+        .into_iter()
+        .map(|user| UserSearchResult {
+            has_stories: authors.get(&user.id).is_some_and(|author| author.has_stories),
+            following: viewer_id.map(|_| followed.contains(&user.id)),
+            username: user.username,
+            name: user.name,
+            picture_url: user.picture_url,
+            is_private: user.is_private,
+        })
+        .collect();
+
+    Ok(results)
+}
+
+// Only posts the viewer is allowed to see, newer posts win ties
+async fn search_posts<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, search: &str, limit: u64) -> Result<Vec<PostDetail>> {
+    let Some(fulltext) = to_fulltext_query(search) else {
+        return Ok(Vec::new());
+    };
+
+    let mut score = Score::default()
+        .add("MATCH (posts.description) AGAINST (? IN BOOLEAN MODE)", [fulltext.clone().into()]);
+
+    if let Some(viewer_id) = viewer_id {
+        score = score.add("(posts.user_id IN (SELECT followee_id FROM follows WHERE follower_id = ?)) * 5", [viewer_id.to_vec().into()]);
+    }
+
+    let posts = posts::Entity::find()
+        .filter(Expr::cust_with_values("MATCH (posts.description) AGAINST (? IN BOOLEAN MODE)", [fulltext]))
+        .filter(visible_authors_condition(posts::Column::UserId, viewer_id))
+        .apply_if(viewer_id, |query, viewer_id| query.filter(not_blocked_condition(posts::Column::UserId, viewer_id)))
+        .order_by_desc(score.into_expr())
+        .order_by_desc(posts::Column::CreatedAt)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    build_post_details(db, posts).await
+}
+
+async fn search_hashtags<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, search: &str, limit: u64) -> Result<Vec<HashtagDetail>> {
+    let name = search.trim().trim_start_matches('#').to_lowercase();
+    let fulltext = to_fulltext_query(search);
+
+    let mut matched = Condition::any();
+    let mut score = Score::default();
+
+    if !name.is_empty() {
+        let prefix = to_prefix_pattern(&name);
+        matched = matched.add(hashtags::Column::Name.like(prefix));
+        score = score.add("(hashtags.name = ?) * 10", [name.into()]);
+    }
+
+    if let Some(fulltext) = fulltext {
+        matched = matched.add(Expr::cust_with_values("MATCH (hashtags.name) AGAINST (? IN BOOLEAN MODE)", [fulltext.clone()]));
+        score = score.add("MATCH (hashtags.name) AGAINST (? IN BOOLEAN MODE)", [fulltext.into()]);
+    }
+
+    if matched.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(viewer_id) = viewer_id {
+        score = score.add("(hashtags.id IN (SELECT hashtag_id FROM hashtag_follows WHERE user_id = ?)) * 5", [viewer_id.to_vec().into()]);
+    }
+
+    let hashtags = hashtags::Entity::find()
+        .filter(matched)
+        .order_by_desc(score.into_expr())
+        .order_by_asc(hashtags::Column::Name)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    build_hashtag_details(db, viewer_id, hashtags).await
+}
+
+// Blocked users and their posts are left out, so are the posts of private accounts the viewer does not follow
+pub async fn search(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, query: &SearchQuery) -> Result<SearchResults> {
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    let search = query.q.as_deref().unwrap_or_default();
+    let limit = query.get_limit();
+
+    let mut results = SearchResults::default();
+
+    if query.includes(SEARCH_TYPE_USERS) {
+        results.users = Some(search_users(db, viewer_id.as_deref(), search, limit).await?);
+    }
+
+    if query.includes(SEARCH_TYPE_POSTS) {
+        results.posts = Some(search_posts(db, viewer_id.as_deref(), search, limit).await?);
+    }
+
+    if query.includes(SEARCH_TYPE_HASHTAGS) {
+        results.hashtags = Some(search_hashtags(db, viewer_id.as_deref(), search, limit).await?);
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::users::PostAuthor;
+
+pub mod stories_service;
+pub mod stories_controller;
+pub mod stories_routes;
+pub mod stories_worker;
+
+// ---- STORIES STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryDetail {
+    pub id: String,
+    pub file_url: String,
+    pub created_at: DateTime<Utc>,
+    pub user: PostAuthor,
+    // Only shown to the story owner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub views_count: Option<u64>,
+}
+
+// One entry of the story tray, a followed user with active stories
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryTrayItem {
+    pub user: PostAuthor,
+    pub stories_count: u64,
+    pub latest_story_at: DateTime<Utc>,
+    // `true` once the viewer has seen every active story of the user
+    pub seen: bool,
+}
+
+// ---- END OF STORIES STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateStoryPayload {
+    #[serde(rename = "fileUrl")]
+    #[validate(
+    length(
+    min = 1,
+    message = "File URL cannot be empty"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub file_url: Option<String>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,64 @@
+use actix_web::{get, post, delete, HttpResponse, web::{Data, Json, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::stories::CreateStoryPayload;
+use crate::utils::validate_data;
+use crate::Result;
+use super::stories_service::{create_story, delete_story, get_stories, get_story_tray};
+
+#[post("")]
+pub async fn create_story_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<CreateStoryPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    let story = create_story(&ctx.db, &ctx.events, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Created().json(
+        json!({
+            "code": StatusCode::CREATED.as_u16(),
+            "data": story
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_stories_handler(ctx: Data<AppState>, viewer: Option<JwtTokenPayload>, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    let stories = get_stories(&ctx.db, viewer.as_ref(), &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": stories
+        })
+    ))
+}
+
+#[delete("/{story_id}")]
+pub async fn delete_story_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>) -> Result<HttpResponse> {
+    let (username, story_id) = path.into_inner();
+
+    delete_story(&ctx.db, &ctx.storage, &jwt_payload, &username, &story_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("/stories")]
+pub async fn get_story_tray_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload) -> Result<HttpResponse> {
+    let tray = get_story_tray(&ctx.db, &jwt_payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": tray
+        })
+    ))
+}
@@ -0,0 +1,12 @@
+use actix_web::web::{ServiceConfig, scope};
+use crate::messages::messages_routes::get_story_replies_routes;
+use crate::story_views::story_views_routes::get_story_viewers_routes;
+use super::stories_controller::{create_story_handler, delete_story_handler, get_stories_handler};
+
+pub fn get_stories_routes(cfg: &mut ServiceConfig) {
+    cfg.service(create_story_handler)
+        .service(get_stories_handler)
+        .service(delete_story_handler)
+        .service(scope("/{story_id}/views").configure(get_story_viewers_routes))
+        .service(scope("/{story_id}/replies").configure(get_story_replies_routes));
+}
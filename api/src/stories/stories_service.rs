@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use sea_orm::sea_query::Query;
+use uuid::Uuid;
+use entity::stories::{Entity, Column, Model, ActiveModel};
+use entity::{follows, highlight_items, story_views};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::media::media_service::{delete_unreferenced_uploads, ensure_uploaded_by};
+use crate::mutes::mutes_service::not_muted_condition;
+use crate::stories::{CreateStoryPayload, StoryDetail, StoryTrayItem};
+use crate::story_views::story_views_service::count_stories_views;
+use crate::users::PostAuthor;
+use crate::users::users_service::{ensure_can_view_content, ensure_owner, find_user_by_username, get_post_author, get_post_authors};
+use crate::utils::storage::MediaStorage;
+use crate::Result;
+
+pub const STORY_LIFETIME_HOURS: i64 = 24;
+// Keeps each sweeper transaction small when a lot of stories expire at once
+pub const SWEEP_BATCH_SIZE: u64 = 500;
+
+const STORY_NOT_FOUND_ERROR: &str = "Story not found";
+
+fn story_not_found() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::NOT_FOUND.as_u16())
+        .set_error_message(STORY_NOT_FOUND_ERROR)
+}
+
+// Stories created at or before this instant have expired
+pub fn active_stories_threshold() -> DateTime<Utc> {
+    Utc::now() - Duration::hours(STORY_LIFETIME_HOURS)
+}
+
+fn to_story_detail(story: Model, user: PostAuthor, views_count: Option<u64>) -> StoryDetail {
+    StoryDetail {
+        id: Uuid::from_slice(&story.id).unwrap().to_string(),
+        file_url: story.file_url,
+        created_at: story.created_at,
+        user,
+        views_count,
+    }
+}
+
+pub async fn find_user_story<C: ConnectionTrait>(db: &C, user_id: &[u8], story_id: &str) -> Result<Model> {
+    let story_id = Uuid::from_str(story_id).map_err(|_| story_not_found())?;
+
+    let story = Entity::find_by_id(story_id)
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .one(db)
+        .await?;
+
+    story.ok_or_else(story_not_found)
+}
+
+// Expired stories are treated as gone, whether the sweeper has not reached them yet
+// or a highlight keeps them around
+pub async fn find_active_user_story<C: ConnectionTrait>(db: &C, user_id: &[u8], story_id: &str) -> Result<Model> {
+    let story = find_user_story(db, user_id, story_id).await?;
+
+    if story.created_at <= active_stories_threshold() {
+        return Err(story_not_found());
+    }
+
+    Ok(story)
+}
+
+pub async fn create_story(db: &DatabaseConnection, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, data: CreateStoryPayload) -> Result<StoryDetail> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let file_url = data.file_url.unwrap();
+
+    ensure_uploaded_by(db, &user.id, &file_url).await?;
+
+    let story = ActiveModel {
+        id: Set(Vec::from(Uuid::new_v4())),
+        user_id: Set(user.id.clone()),
+        file_url: Set(file_url),
+        ..Default::default()
+    }.insert(db).await?;
+
+    events.publish(DomainEvent::StoryPosted { user_id: user.id.clone(), story_id: story.id.clone() });
+
+    Ok(to_story_detail(story, get_post_author(db, &user).await?, Some(0)))
+}
+
+// Active stories of the user, oldest first so they play in order
+pub async fn get_stories(db: &DatabaseConnection, viewer: Option<&JwtTokenPayload>, username: &str) -> Result<Vec<StoryDetail>> {
+    let user = find_user_by_username(db, username).await?;
+
+    let viewer_id = viewer.map(|v| v.get_user_id());
+    ensure_can_view_content(db, viewer_id.as_deref(), &user).await?;
+
+    let stories = Entity::find()
+        .filter(Column::UserId.eq(user.id.clone()))
+        .filter(Column::CreatedAt.gt(active_stories_threshold()))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?;
+
+    let views_counts = match viewer_id {
+        Some(viewer_id) if viewer_id == user.id => {
+            Some(count_stories_views(db, stories.iter().map(|story| story.id.clone()).collect()).await?)
+        },
+        _ => None,
+    };
+
+    let author = get_post_author(db, &user).await?;
+
+    let stories = stories
+        .into_iter()
+        .map(|story| {
+            let views_count = views_counts.as_ref().map(|views_counts| views_counts.get(&story.id).copied().unwrap_or_default());
+            to_story_detail(story, author.clone(), views_count)
+        })
+        .collect();
+
+    Ok(stories)
+}
+
+async fn delete_stories_rows<C: ConnectionTrait>(db: &C, story_ids: Vec<Vec<u8>>) -> Result<()> {
+    story_views::Entity::delete_many()
+        .filter(story_views::Column::StoryId.is_in(story_ids.clone()))
+        .exec(db)
+        .await?;
+
+    Entity::delete_many()
+        .filter(Column::Id.is_in(story_ids))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_story(db: &DatabaseConnection, storage: &MediaStorage, jwt_payload: &JwtTokenPayload, username: &str, story_id: &str) -> Result<()> {
+    let user = find_user_by_username(db, username).await?;
+    let story = find_user_story(db, &user.id, story_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let txn = db.begin().await?;
+    delete_stories_rows(&txn, vec![story.id]).await?;
+    txn.commit().await?;
+
+    delete_unreferenced_uploads(db, storage, &user.id, vec![story.file_url]).await?;
+
+    Ok(())
+}
+
+// Removes expired stories with their views, then their media unless a highlight uses it.
+// Returns how many stories were removed.
+pub async fn delete_expired_stories(db: &DatabaseConnection, storage: &MediaStorage) -> Result<u64> {
+    let threshold = active_stories_threshold();
+    let mut deleted_count = 0;
+
+    loop {
+        // Highlights still point at their stories, so pinned ones stay around once expired
+        let highlighted_stories = Query::select()
+            .column(highlight_items::Column::StoryId)
+            .from(highlight_items::Entity)
+            .to_owned();
+
+        let txn = db.begin().await?;
+        let expired = Entity::find()
+            .filter(Column::CreatedAt.lte(threshold))
+            .filter(Column::Id.not_in_subquery(highlighted_stories))
+            .order_by_asc(Column::CreatedAt)
+            .limit(SWEEP_BATCH_SIZE)
+            .lock_exclusive()
+            .all(&txn)
+            .await?;
+
+        if expired.is_empty() {
+            txn.commit().await?;
+            return Ok(deleted_count);
+        }
+
+        delete_stories_rows(&txn, expired.iter().map(|story| story.id.clone()).collect()).await?;
+        txn.commit().await?;
+
+        deleted_count += expired.len() as u64;
+
+        let mut file_urls_by_owner: HashMap<Vec<u8>, Vec<String>> = HashMap::new();
+        for story in expired {
+            file_urls_by_owner.entry(story.user_id).or_default().push(story.file_url);
+        }
+
+        // Highlighted media outlives the story
+        for (owner_id, file_urls) in file_urls_by_owner {
+            delete_unreferenced_uploads(db, storage, &owner_id, file_urls).await?;
+        }
+    }
+}
+
+// Followed users with active stories, most recently updated first
+pub async fn get_story_tray(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload) -> Result<Vec<StoryTrayItem>> {
+    let viewer_id = jwt_payload.get_user_id();
+
+    let followees = Query::select()
+        .column(follows::Column::FolloweeId)
+        .from(follows::Entity)
+        .and_where(follows::Column::FollowerId.eq(viewer_id.clone()))
+        .to_owned();
+
+    let stories = Entity::find()
+        .filter(Column::UserId.in_subquery(followees))
+        .filter(Column::CreatedAt.gt(active_stories_threshold()))
+        .filter(not_blocked_condition(Column::UserId, &viewer_id))
+        .filter(not_muted_condition(Column::UserId, &viewer_id))
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::Id)
+        .all(db)
+        .await?;
+
+    if stories.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let seen_story_ids: HashSet<Vec<u8>> = story_views::Entity::find()
+        .select_only()
+        .column(story_views::Column::StoryId)
+        .filter(story_views::Column::ViewerId.eq(viewer_id))
+        .filter(story_views::Column::StoryId.is_in(stories.iter().map(|story| story.id.clone())))
+        .into_tuple::<Vec<u8>>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    // Stories are sorted newest first, so the first story of each user is their latest
+    let mut user_ids: Vec<Vec<u8>> = Vec::new();
+    let mut groups: HashMap<Vec<u8>, (u64, DateTime<Utc>, bool)> = HashMap::new();
+
+    for story in stories {
+        let seen = seen_story_ids.contains(&story.id);
+
+        match groups.get_mut(&story.user_id) {
+            Some((stories_count, _, all_seen)) => {
+                *stories_count += 1;
+                *all_seen &= seen;
+            },
+            None => {
+                user_ids.push(story.user_id.clone());
+                groups.insert(story.user_id, (1, story.created_at, seen));
+            },
+        }
+    }
+
+    let mut authors = get_post_authors(db, user_ids.clone()).await?;
+
+    let tray = user_ids
+        .into_iter()
+        .filter_map(|user_id| {
+            let (stories_count, latest_story_at, seen) = groups.remove(&user_id)?;
+
+            Some(StoryTrayItem {
+                user: authors.remove(&user_id)?,
+                stories_count,
+                latest_story_at,
+                seen,
+            })
+        })
+        .collect();
+
+    Ok(tray)
+}
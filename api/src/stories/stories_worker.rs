@@ -0,0 +1,25 @@
+use std::time::Duration;
+use actix_web::rt::time::{interval_at, Instant};
+use sea_orm::DatabaseConnection;
+use crate::utils::storage::MediaStorage;
+use super::stories_service::delete_expired_stories;
+
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+// Expired stories are already hidden by every query,
+// the sweeper only reclaims their rows and media
+pub fn spawn_story_sweeper(db: DatabaseConnection, storage: MediaStorage) {
+    actix_web::rt::spawn(async move {
+        let mut interval = interval_at(Instant::now() + SWEEP_INTERVAL, SWEEP_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match delete_expired_stories(&db, &storage).await {
+                Ok(0) => {},
+                Ok(deleted_count) => tracing::info!("Deleted {} expired stories", deleted_count),
+                Err(e) => tracing::error!("Failed to delete expired stories: {:?}", e),
+            }
+        }
+    });
+}
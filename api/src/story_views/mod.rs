@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+use crate::utils::check_valid_uuids;
+
+pub mod story_views_service;
+pub mod story_views_controller;
+pub mod story_views_routes;
+
+// ---- STORY VIEWS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryViewer {
+    pub id: String,
+    pub full_name: String,
+    pub username: String,
+    pub viewed_at: DateTime<Utc>,
+}
+
+impl StoryViewer {
+    pub fn new(user: entity::users::Model, viewed_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::from_slice(&user.id).unwrap().to_string(),
+            full_name: user.name,
+            username: user.username,
+            viewed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryViewersPage {
+    pub viewers: Vec<StoryViewer>,
+    pub views_count: u64,
+    // `None` once the end of the list is reached
+    pub next_cursor: Option<String>,
+}
+
+// ---- END OF STORY VIEWS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+// Several stories at once, so a client can report a whole tray swipe in one request
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MarkStoriesViewedPayload {
+    #[serde(rename = "storyIds")]
+    #[validate(
+    length(
+    min = 1,
+    max = 100,
+    message = "Between 1 and 100 stories can be marked at once"
+    ),
+    custom(
+    function = "check_valid_uuids",
+    message = "Story ids must be valid UUIDs"
+    ),
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub story_ids: Option<Vec<String>>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,43 @@
+use actix_web::{get, post, HttpResponse, web::{Data, Json, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::story_views::MarkStoriesViewedPayload;
+use crate::utils::cursor::CursorQuery;
+use crate::utils::validate_data;
+use crate::Result;
+use super::story_views_service::{get_story_viewers, mark_stories_viewed};
+
+#[post("/views")]
+pub async fn mark_stories_viewed_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, payload: Json<MarkStoriesViewedPayload>) -> Result<HttpResponse> {
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    mark_stories_viewed(&ctx.db, &jwt_payload, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[get("")]
+pub async fn get_story_viewers_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<(String, String)>, query: Query<CursorQuery>) -> Result<HttpResponse> {
+    let (username, story_id) = path.into_inner();
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let page = get_story_viewers(&ctx.db, &jwt_payload, &username, &story_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": page.viewers,
+            "viewsCount": page.views_count,
+            "nextCursor": page.next_cursor
+        })
+    ))
+}
@@ -0,0 +1,11 @@
+use actix_web::web::ServiceConfig;
+
+use super::story_views_controller::{get_story_viewers_handler, mark_stories_viewed_handler};
+
+pub fn get_story_views_routes(cfg: &mut ServiceConfig) {
+    cfg.service(mark_stories_viewed_handler);
+}
+
+pub fn get_story_viewers_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_story_viewers_handler);
+}
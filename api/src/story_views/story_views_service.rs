@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Statement, Value};
+use sea_orm::sea_query::Expr;
+use uuid::Uuid;
+use entity::story_views::{Entity, Column};
+use entity::{stories, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::stories::stories_service::{active_stories_threshold, find_active_user_story};
+use crate::story_views::{MarkStoriesViewedPayload, StoryViewer, StoryViewersPage};
+use crate::users::users_service::{ensure_owner, find_user_by_username, visible_authors_condition};
+use crate::utils::cursor::{Cursor, CursorQuery};
+use crate::Result;
+
+// Ids that are not visible to the viewer, expired or the viewer's own are skipped,
+// a tray swipe can easily race with a story expiring or being deleted
+pub async fn mark_stories_viewed(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, data: MarkStoriesViewedPayload) -> Result<()> {
+    let viewer_id = jwt_payload.get_user_id();
+
+    let story_ids: HashSet<Vec<u8>> = data.story_ids.unwrap()
+        .iter()
+        .filter_map(|story_id| Uuid::from_str(story_id).ok())
+        .map(Vec::from)
+        .collect();
+
+    let viewable_story_ids: Vec<Vec<u8>> = stories::Entity::find()
+        .select_only()
+        .column(stories::Column::Id)
+        .filter(stories::Column::Id.is_in(story_ids))
+        .filter(stories::Column::CreatedAt.gt(active_stories_threshold()))
+        .filter(stories::Column::UserId.ne(viewer_id.clone()))
+        .filter(visible_authors_condition(stories::Column::UserId, Some(&viewer_id)))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    if viewable_story_ids.is_empty() {
+        return Ok(());
+    }
+
+    // Each viewer is recorded once per story, later views keep the first timestamp
+    let placeholders = vec!["(?, ?)"; viewable_story_ids.len()].join(", ");
+    let values: Vec<Value> = viewable_story_ids
+        .into_iter()
+        .flat_map(|story_id| [story_id.into(), viewer_id.clone().into()])
+        .collect();
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        format!("INSERT IGNORE INTO story_views (story_id, viewer_id) VALUES {}", placeholders),
+        values,
+    )).await?;
+
+    Ok(())
+}
+
+// Views per story, only meant for the story owner
+pub async fn count_stories_views<C: ConnectionTrait>(db: &C, story_ids: Vec<Vec<u8>>) -> Result<HashMap<Vec<u8>, u64>> {
+    if story_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let views_counts: Vec<(Vec<u8>, i64)> = Entity::find()
+        .select_only()
+        .column(Column::StoryId)
+        .column_as(Expr::col(Column::Id).count(), "views_count")
+        .filter(Column::StoryId.is_in(story_ids))
+        .group_by(Column::StoryId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(views_counts.into_iter().map(|(story_id, views_count)| (story_id, views_count.max(0) as u64)).collect())
+}
+
+// Viewers disappear together with the story once it expires
+pub async fn get_story_viewers(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, story_id: &str, query: &CursorQuery) -> Result<StoryViewersPage> {
+    let user = find_user_by_username(db, username).await?;
+    let story = find_active_user_story(db, &user.id, story_id).await?;
+
+    ensure_owner(jwt_payload, &user.username)?;
+
+    let views_count = Entity::find()
+        .filter(Column::StoryId.eq(story.id.clone()))
+        .filter(not_blocked_condition(Column::ViewerId, &user.id))
+        .count(db)
+        .await?;
+
+    let limit = query.get_limit();
+    let cursor = query.get_cursor()?;
+
+    // One extra row tells whether there is a next page
+    let mut views = Entity::find()
+        .find_also_related(users::Entity)
+        .filter(Column::StoryId.eq(story.id))
+        .filter(not_blocked_condition(Column::ViewerId, &user.id))
+        .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(Column::CreatedAt, Column::ViewerId)))
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::ViewerId)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+
+    let next_cursor = if views.len() as u64 > limit {
+        views.truncate(limit as usize);
+        views.last().map(|(view, _)| Cursor::new(view.created_at, view.viewer_id.clone()).encode())
+    } else {
+        None
+    };
+
+    Ok(StoryViewersPage {
+        viewers: views.into_iter().filter_map(|(view, user)| Some(StoryViewer::new(user?, view.created_at))).collect(),
+        views_count,
+        next_cursor,
+    })
+}
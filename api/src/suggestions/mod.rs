@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub mod suggestions_service;
+pub mod suggestions_controller;
+pub mod suggestions_routes;
+
+// Suggestions returned when no limit is given
+pub const DEFAULT_SUGGESTIONS_LIMIT: u64 = 20;
+
+// ---- SUGGESTIONS STRUCTS ----
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedUser {
+    pub username: String,
+    pub name: String,
+    pub picture_url: String,
+    pub is_private: bool,
+    pub has_stories: bool,
+    // Accounts followed by the viewer that follow this one, 0 for popular accounts
+    pub mutual_count: u64,
+    // One of those accounts, shown in the explanation
+    pub followed_by: Option<String>,
+    // "Followed by X and N others", ready to display
+    pub explanation: String,
+}
+
+// ---- END OF SUGGESTIONS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SuggestionsQuery {
+    #[validate(range(min = 1, max = 50, message = "Limit must be between 1 and 50"))]
+    pub limit: Option<u64>,
+}
+
+impl SuggestionsQuery {
+    pub fn get_limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_SUGGESTIONS_LIMIT)
+    }
+}
+
+// ---- END OF REQUEST PAYLOAD ----
@@ -0,0 +1,37 @@
+use actix_web::{get, delete, HttpResponse, web::{Data, Path, Query}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::utils::validate_data;
+use crate::Result;
+use super::SuggestionsQuery;
+use super::suggestions_service::{dismiss_suggestion, get_suggestions};
+
+#[get("/suggestions")]
+pub async fn get_suggestions_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, query: Query<SuggestionsQuery>) -> Result<HttpResponse> {
+    let query = query.into_inner();
+
+    validate_data(&query)?;
+
+    let suggestions = get_suggestions(&ctx.db, &jwt_payload, &query).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16(),
+            "data": suggestions
+        })
+    ))
+}
+
+#[delete("/suggestions/{username}")]
+pub async fn dismiss_suggestion_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+
+    dismiss_suggestion(&ctx.db, &jwt_payload, &username).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,9 @@
+use actix_web::web::ServiceConfig;
+
+use super::suggestions_controller::{dismiss_suggestion_handler, get_suggestions_handler};
+
+// Plain resources rather than a scope, so that a user named "suggestions" keeps their own routes
+pub fn get_suggestions_routes(cfg: &mut ServiceConfig) {
+    cfg.service(get_suggestions_handler)
+        .service(dismiss_suggestion_handler);
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use actix_web::http::StatusCode;
+use sea_orm::{Set, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::sea_query::{Expr, Query, SelectStatement};
+use entity::suggestion_dismissals::{Entity, Column, ActiveModel};
+use entity::{follow_requests, follows, users};
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::error::HttpResponseError;
+use crate::suggestions::{SuggestedUser, SuggestionsQuery};
+use crate::users::users_service::{find_user_by_username, get_post_authors};
+use crate::utils::is_unique_violation;
+use crate::Result;
+
+fn followees_of(user_id: &[u8]) -> SelectStatement {
+    Query::select()
+        .column(follows::Column::FolloweeId)
+        .from(follows::Entity)
+        .and_where(follows::Column::FollowerId.eq(user_id.to_vec()))
+        .to_owned()
+}
+
+// Leaves out the viewer, the accounts they follow or asked to follow,
+// the ones they dismissed and anyone blocked either way
+fn suggestable_condition<C: ColumnTrait>(user_col: C, viewer_id: &[u8]) -> Condition {
+    let requested = Query::select()
+        .column(follow_requests::Column::TargetId)
+        .from(follow_requests::Entity)
+        .and_where(follow_requests::Column::RequesterId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    let dismissed = Query::select()
+        .column(Column::DismissedId)
+        .from(Entity)
+        .and_where(Column::UserId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    Condition::all()
+        .add(user_col.ne(viewer_id.to_vec()))
+        .add(user_col.not_in_subquery(followees_of(viewer_id)))
+        .add(user_col.not_in_subquery(requested))
+        .add(user_col.not_in_subquery(dismissed))
+        .add(not_blocked_condition(user_col, viewer_id))
+}
+
+// Accounts followed by the people the viewer follows, the more of them the better
+async fn get_mutual_suggestions<C: ConnectionTrait>(db: &C, viewer_id: &[u8], limit: u64) -> Result<Vec<(Vec<u8>, i64)>> {
+    let suggestions = follows::Entity::find()
+        .select_only()
+        .column(follows::Column::FolloweeId)
+        .column_as(follows::Column::FollowerId.count(), "mutual_count")
+        .filter(follows::Column::FollowerId.in_subquery(followees_of(viewer_id)))
+        .filter(suggestable_condition(follows::Column::FolloweeId, viewer_id))
+        .group_by(follows::Column::FolloweeId)
+        .order_by(Expr::cust("mutual_count"), Order::Desc)
+        .order_by_asc(follows::Column::FolloweeId)
+        .limit(limit)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(suggestions)
+}
+
+// The most followed accounts, for viewers whose follows don't lead anywhere yet
+async fn get_popular_suggestions<C: ConnectionTrait>(db: &C, viewer_id: &[u8], excluded_ids: Vec<Vec<u8>>, limit: u64) -> Result<Vec<Vec<u8>>> {
+    let suggestions = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::FollowersCount.gt(0))
+        .filter(suggestable_condition(users::Column::Id, viewer_id))
+        .filter(users::Column::Id.is_not_in(excluded_ids))
+        .order_by_desc(users::Column::FollowersCount)
+        .order_by_asc(users::Column::Id)
+        .limit(limit)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(suggestions)
+}
+
+// For each suggested account, the username of the account followed by the viewer
+// that followed it most recently
+async fn get_followed_by<C: ConnectionTrait>(db: &C, viewer_id: &[u8], user_ids: Vec<Vec<u8>>) -> Result<HashMap<Vec<u8>, String>> {
+    let mut followers: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    follows::Entity::find()
+        .filter(follows::Column::FollowerId.in_subquery(followees_of(viewer_id)))
+        .filter(follows::Column::FolloweeId.is_in(user_ids))
+        .order_by_desc(follows::Column::CreatedAt)
+        .order_by_desc(follows::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .for_each(|follow| {
+            followers.entry(follow.followee_id).or_insert(follow.follower_id);
+        });
+
+    let usernames: HashMap<Vec<u8>, String> = users::Entity::find()
+        .filter(users::Column::Id.is_in(followers.values().cloned()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let followed_by = followers
+        .into_iter()
+        .filter_map(|(user_id, follower_id)| Some((user_id, usernames.get(&follower_id)?.clone())))
+        .collect();
+
+    Ok(followed_by)
+}
+
+fn explain(followed_by: Option<&str>, mutual_count: u64) -> String {
+    match (followed_by, mutual_count) {
+        (None, _) | (_, 0) => "Popular account".to_string(),
+        (Some(username), 1) => format!("Followed by {}", username),
+        (Some(username), 2) => format!("Followed by {} and 1 other", username),
+        (Some(username), mutual_count) => format!("Followed by {} and {} others", username, mutual_count - 1),
+    }
+}
+
+// Friends of friends first, then popular accounts to fill the rest of the list
+pub async fn get_suggestions(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, query: &SuggestionsQuery) -> Result<Vec<SuggestedUser>> {
+    let viewer_id = jwt_payload.get_user_id();
+    let limit = query.get_limit();
+
+    let mut ranked: Vec<(Vec<u8>, u64)> = get_mutual_suggestions(db, &viewer_id, limit).await?
+        .into_iter()
+        .map(|(user_id, mutual_count)| (user_id, mutual_count as u64))
+        .collect();
+
+    let remaining = limit - ranked.len() as u64;
+    if remaining > 0 {
+        let excluded_ids = ranked.iter().map(|(user_id, _)| user_id.clone()).collect();
+        get_popular_suggestions(db, &viewer_id, excluded_ids, remaining).await?
+            .into_iter()
+            .for_each(|user_id| ranked.push((user_id, 0)));
+    }
+
+    if ranked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user_ids: Vec<Vec<u8>> = ranked.iter().map(|(user_id, _)| user_id.clone()).collect();
+    let mutual_ids = ranked.iter().filter(|(_, mutual_count)| *mutual_count > 0).map(|(user_id, _)| user_id.clone()).collect();
+
+    let followed_by = get_followed_by(db, &viewer_id, mutual_ids).await?;
+    let authors = get_post_authors(db, user_ids.clone()).await?;
+    let mut users: HashMap<Vec<u8>, users::Model> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id.clone(), user))
+        .collect();
+
+    let suggestions = ranked
+        .into_iter()
+        .filter_map(|(user_id, mutual_count)| {
+            let user = users.remove(&user_id)?;
+            let followed_by = followed_by.get(&user_id).cloned();
+
+            Some(SuggestedUser {
+                has_stories: authors.get(&user_id).is_some_and(|author| author.has_stories),
+                explanation: explain(followed_by.as_deref(), mutual_count),
+                followed_by,
+                mutual_count,
+                username: user.username,
+                name: user.name,
+                picture_url: user.picture_url,
+                is_private: user.is_private,
+            })
+        })
+        .collect();
+
+    Ok(suggestions)
+}
+
+// The account won't be suggested to the viewer again, dismissing it twice is harmless
+pub async fn dismiss_suggestion(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str) -> Result<()> {
+    let viewer_id = jwt_payload.get_user_id();
+    let user = find_user_by_username(db, username).await?;
+
+    if user.id == viewer_id {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::BAD_REQUEST.as_u16())
+                .set_error_message("You cannot dismiss yourself")
+        );
+    }
+
+    let result = ActiveModel {
+        user_id: Set(viewer_id),
+        dismissed_id: Set(user.id),
+        ..Default::default()
+    }.insert(db).await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if is_unique_violation(&e) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,38 @@
+use futures::channel::mpsc::UnboundedSender;
+
+pub mod timeline_service;
+pub mod timeline_worker;
+
+// ---- TIMELINE STRUCTS ----
+
+#[derive(Debug, Clone)]
+pub enum TimelineJob {
+    // Push a freshly created post into the timelines of the author's followers
+    FanOutPost { post_id: Vec<u8> },
+    // Pull the recent posts of a newly followed user into the follower's timeline
+    Backfill { user_id: Vec<u8>, author_id: Vec<u8> },
+    // Push the recent posts of an author who dropped back under the fan out limit
+    // into the timelines of all their followers
+    FanOutAuthor { author_id: Vec<u8> },
+}
+
+// Handle used by the request handlers to hand work over to the timeline worker
+#[derive(Debug, Clone)]
+pub struct TimelineQueue {
+    sender: UnboundedSender<TimelineJob>,
+}
+
+impl TimelineQueue {
+    pub fn new(sender: UnboundedSender<TimelineJob>) -> Self {
+        Self { sender }
+    }
+
+    // Timelines can always be regenerated, so a lost job is logged and not surfaced to the user
+    pub fn enqueue(&self, job: TimelineJob) {
+        if let Err(e) = self.sender.unbounded_send(job) {
+            tracing::error!("Failed to enqueue timeline job: {:?}", e);
+        }
+    }
+}
+
+// ---- END OF TIMELINE STRUCTS ----
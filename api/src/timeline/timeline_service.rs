@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Statement, TransactionTrait};
+use sea_orm::sea_query::Query;
+use entity::timeline_entries::{Entity, Column};
+use entity::{follows, posts, users};
+use crate::blocks::blocks_service::not_blocked_condition;
+use crate::hashtags::hashtags_service::{followed_hashtags_condition, follows_any_hashtag};
+use crate::mutes::mutes_service::not_muted_condition;
+use crate::users::users_service::visible_authors_condition;
+use crate::utils::cursor::Cursor;
+use crate::Result;
+
+// Above this many followers a post is not copied into every follower's timeline,
+// the followers pick it up from the posts table when they read their feed instead
+pub const FAN_OUT_FOLLOWER_LIMIT: u64 = 10_000;
+
+// How many of the latest posts of a user are copied when somebody starts following them
+pub const BACKFILL_POSTS_LIMIT: u64 = 100;
+
+async fn is_fanned_out_on_read<C: ConnectionTrait>(db: &C, author_id: &[u8]) -> Result<bool> {
+    let followers_count: Option<i32> = users::Entity::find_by_id(author_id.to_vec())
+        .select_only()
+        .column(users::Column::FollowersCount)
+        .into_tuple()
+        .one(db)
+        .await?;
+
+    Ok(followers_count.unwrap_or_default().max(0) as u64 > FAN_OUT_FOLLOWER_LIMIT)
+}
+
+pub async fn fan_out_post(db: &DatabaseConnection, post_id: &[u8]) -> Result<()> {
+    // The post may have been deleted before the job got picked up
+    let Some(post) = posts::Entity::find_by_id(post_id.to_vec()).one(db).await? else {
+        return Ok(());
+    };
+
+    if is_fanned_out_on_read(db, &post.user_id).await? {
+        return Ok(());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        "INSERT IGNORE INTO timeline_entries (user_id, post_id, author_id, created_at) \
+         SELECT follower_id, ?, ?, ? FROM follows WHERE followee_id = ?",
+        [post.id.into(), post.user_id.clone().into(), post.created_at.into(), post.user_id.into()],
+    )).await?;
+
+    Ok(())
+}
+
+pub async fn backfill_timeline<C: ConnectionTrait>(db: &C, user_id: &[u8], author_id: &[u8]) -> Result<()> {
+    if is_fanned_out_on_read(db, author_id).await? {
+        return Ok(());
+    }
+
+    // The follow may already be gone again by the time the job runs
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        "INSERT IGNORE INTO timeline_entries (user_id, post_id, author_id, created_at) \
+         SELECT ?, id, user_id, created_at FROM posts \
+         WHERE user_id = ? AND EXISTS (SELECT 1 FROM follows WHERE follower_id = ? AND followee_id = ?) \
+         ORDER BY created_at DESC, id DESC LIMIT ?",
+        [
+            user_id.to_vec().into(),
+            author_id.to_vec().into(),
+            user_id.to_vec().into(),
+            author_id.to_vec().into(),
+            BACKFILL_POSTS_LIMIT.into(),
+        ],
+    )).await?;
+
+    Ok(())
+}
+
+// Posts made while the author was above the fan out limit are only in the posts table,
+// once the author is back under it they are copied like a backfill for every follower
+pub async fn fan_out_author_posts(db: &DatabaseConnection, author_id: &[u8]) -> Result<()> {
+    // The author may have gone back above the limit by the time the job runs
+    if is_fanned_out_on_read(db, author_id).await? {
+        return Ok(());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::MySql,
+        "INSERT IGNORE INTO timeline_entries (user_id, post_id, author_id, created_at) \
+         SELECT f.follower_id, p.id, p.user_id, p.created_at FROM follows f \
+         JOIN (SELECT id, user_id, created_at FROM posts WHERE user_id = ? ORDER BY created_at DESC, id DESC LIMIT ?) p \
+         WHERE f.followee_id = ?",
+        [
+            author_id.to_vec().into(),
+            BACKFILL_POSTS_LIMIT.into(),
+            author_id.to_vec().into(),
+        ],
+    )).await?;
+
+    Ok(())
+}
+
+pub async fn remove_post_from_timelines<C: ConnectionTrait>(db: &C, post_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn remove_author_from_timeline<C: ConnectionTrait>(db: &C, user_id: &[u8], author_id: &[u8]) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .filter(Column::AuthorId.eq(author_id.to_vec()))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+// Throw away a user's timeline and regenerate it from the accounts they follow.
+// Returns the number of entries in the new timeline
+pub async fn rebuild_timeline(db: &DatabaseConnection, user_id: &[u8]) -> Result<u64> {
+    let txn = db.begin().await?;
+
+    Entity::delete_many()
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .exec(&txn)
+        .await?;
+
+    let followee_ids: Vec<Vec<u8>> = follows::Entity::find()
+        .select_only()
+        .column(follows::Column::FolloweeId)
+        .filter(follows::Column::FollowerId.eq(user_id.to_vec()))
+        .into_tuple()
+        .all(&txn)
+        .await?;
+
+    for followee_id in followee_ids {
+        backfill_timeline(&txn, user_id, &followee_id).await?;
+    }
+
+    let entries_count = Entity::find()
+        .filter(Column::UserId.eq(user_id.to_vec()))
+        .count(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(entries_count)
+}
+
+// Followed accounts whose posts are not materialized and have to be read from the posts table
+async fn get_fanned_out_on_read_followees(db: &DatabaseConnection, viewer_id: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let followees = Query::select()
+        .column(follows::Column::FolloweeId)
+        .from(follows::Entity)
+        .and_where(follows::Column::FollowerId.eq(viewer_id.to_vec()))
+        .to_owned();
+
+    let followee_ids = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::Id.in_subquery(followees))
+        .filter(users::Column::FollowersCount.gt(FAN_OUT_FOLLOWER_LIMIT))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(followee_ids)
+}
+
+// A page of the viewer's timeline, newest first, along with the cursor of the next page.
+// Materialized entries are merged with the posts of followed accounts that are fanned out on read
+// and with the posts of followed hashtags, which are never materialized
+pub async fn get_timeline_page(db: &DatabaseConnection, viewer_id: &[u8], limit: u64, cursor: Option<Cursor>) -> Result<(Vec<posts::Model>, Option<Cursor>)> {
+    // One extra row tells whether there is a next page
+    let mut candidates: Vec<(DateTime<Utc>, Vec<u8>)> = Entity::find()
+        .select_only()
+        .column(Column::CreatedAt)
+        .column(Column::PostId)
+        .filter(Column::UserId.eq(viewer_id.to_vec()))
+        .filter(not_blocked_condition(Column::AuthorId, viewer_id))
+        .filter(not_muted_condition(Column::AuthorId, viewer_id))
+        .apply_if(cursor.clone(), |query, cursor| query.filter(cursor.after_condition(Column::CreatedAt, Column::PostId)))
+        .order_by_desc(Column::CreatedAt)
+        .order_by_desc(Column::PostId)
+        .limit(limit + 1)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let author_ids = get_fanned_out_on_read_followees(db, viewer_id).await?;
+
+    if !author_ids.is_empty() {
+        let author_posts: Vec<(DateTime<Utc>, Vec<u8>)> = posts::Entity::find()
+            .select_only()
+            .column(posts::Column::CreatedAt)
+            .column(posts::Column::Id)
+            .filter(posts::Column::UserId.is_in(author_ids))
+            .filter(not_blocked_condition(posts::Column::UserId, viewer_id))
+            .filter(not_muted_condition(posts::Column::UserId, viewer_id))
+            .apply_if(cursor.clone(), |query, cursor| query.filter(cursor.after_condition(posts::Column::CreatedAt, posts::Column::Id)))
+            .order_by_desc(posts::Column::CreatedAt)
+            .order_by_desc(posts::Column::Id)
+            .limit(limit + 1)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        candidates.extend(author_posts);
+    }
+
+    if follows_any_hashtag(db, viewer_id).await? {
+        let hashtag_posts: Vec<(DateTime<Utc>, Vec<u8>)> = posts::Entity::find()
+            .select_only()
+            .column(posts::Column::CreatedAt)
+            .column(posts::Column::Id)
+            .filter(followed_hashtags_condition(posts::Column::Id, viewer_id))
+            .filter(visible_authors_condition(posts::Column::UserId, Some(viewer_id)))
+            .filter(not_muted_condition(posts::Column::UserId, viewer_id))
+            .apply_if(cursor, |query, cursor| query.filter(cursor.after_condition(posts::Column::CreatedAt, posts::Column::Id)))
+            .order_by_desc(posts::Column::CreatedAt)
+            .order_by_desc(posts::Column::Id)
+            .limit(limit + 1)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        candidates.extend(hashtag_posts);
+    }
+
+    // Binary ids compare the same way in Rust and in MySQL
+    candidates.sort_unstable_by(|a, b| b.cmp(a));
+    candidates.dedup();
+
+    let next_cursor = if candidates.len() as u64 > limit {
+        candidates.truncate(limit as usize);
+        candidates.last().map(|(created_at, id)| Cursor::new(*created_at, id.clone()))
+    } else {
+        None
+    };
+
+    let post_ids: Vec<Vec<u8>> = candidates.into_iter().map(|(_, id)| id).collect();
+
+    let mut posts_by_id: HashMap<Vec<u8>, posts::Model> = posts::Entity::find()
+        .filter(posts::Column::Id.is_in(post_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|post| (post.id.clone(), post))
+        .collect();
+
+    let posts = post_ids
+        .into_iter()
+        .filter_map(|id| posts_by_id.remove(&id))
+        .collect();
+
+    Ok((posts, next_cursor))
+}
@@ -0,0 +1,27 @@
+use futures::channel::mpsc;
+use futures::StreamExt;
+use sea_orm::DatabaseConnection;
+use crate::timeline::{TimelineJob, TimelineQueue};
+use super::timeline_service::{backfill_timeline, fan_out_author_posts, fan_out_post};
+
+// Start the background task that materializes timelines and return the queue feeding it.
+// Jobs are processed one at a time, in the order they were enqueued
+pub fn spawn_timeline_worker(db: DatabaseConnection) -> TimelineQueue {
+    let (sender, mut receiver) = mpsc::unbounded::<TimelineJob>();
+
+    actix_web::rt::spawn(async move {
+        while let Some(job) = receiver.next().await {
+            let result = match &job {
+                TimelineJob::FanOutPost { post_id } => fan_out_post(&db, post_id).await,
+                TimelineJob::Backfill { user_id, author_id } => backfill_timeline(&db, user_id, author_id).await,
+                TimelineJob::FanOutAuthor { author_id } => fan_out_author_posts(&db, author_id).await,
+            };
+
+            if let Err(e) = result {
+                tracing::error!("Failed to process timeline job {:?}: {:?}", job, e);
+            }
+        }
+    });
+
+    TimelineQueue::new(sender)
+}
@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+pub mod users_service;
+pub mod users_controller;
+pub mod users_routes;
+
+// Who can tag a user on photos, stored in `users.allow_tags_from`
+pub const ALLOW_TAGS_FROM_EVERYONE: &str = "everyone";
+// Only the people the user follows
+pub const ALLOW_TAGS_FROM_FOLLOWING: &str = "following";
+pub const ALLOW_TAGS_FROM_NOBODY: &str = "nobody";
+
+fn check_allow_tags_from(value: &str) -> Result<(), ValidationError> {
+    if ![ALLOW_TAGS_FROM_EVERYONE, ALLOW_TAGS_FROM_FOLLOWING, ALLOW_TAGS_FROM_NOBODY].contains(&value) {
+        let mut val_error = ValidationError::new("invalid_allow_tags_from");
+        val_error.message = Some(Cow::from("Must be one of everyone, following or nobody"));
+        return Err(val_error);
+    }
+
+    Ok(())
+}
+
+// ---- USERS STRUCTS ----
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostAuthor {
+    pub username: String,
+    pub picture_url: String,
+    pub has_stories: bool,
+}
+
+// ---- END OF USERS STRUCTS ----
+
+
+
+// ---- REQUEST PAYLOAD ----
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdatePrivacyPayload {
+    #[serde(rename = "isPrivate")]
+    #[validate(required(message = "This field is required"))]
+    pub is_private: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateTagSettingsPayload {
+    #[serde(rename = "allowTagsFrom")]
+    #[validate(
+    custom = "check_allow_tags_from",
+    required(
+    message = "This field is required"
+    )
+    )]
+    pub allow_tags_from: Option<String>,
+}
+
+// ---- END OF REQUEST PAYLOAD ----
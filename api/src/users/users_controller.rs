@@ -0,0 +1,40 @@
+use actix_web::{put, HttpResponse, web::{Data, Json, Path}, http::StatusCode};
+use serde_json::json;
+use crate::AppState;
+use crate::auth::JwtTokenPayload;
+use crate::users::{UpdatePrivacyPayload, UpdateTagSettingsPayload};
+use crate::utils::validate_data;
+use crate::Result;
+use super::users_service::{update_privacy, update_tag_settings};
+
+#[put("/{username}/privacy")]
+pub async fn update_privacy_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<UpdatePrivacyPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    update_privacy(&ctx.db, &ctx.timeline, &ctx.events, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
+
+#[put("/{username}/tag-settings")]
+pub async fn update_tag_settings_handler(ctx: Data<AppState>, jwt_payload: JwtTokenPayload, path: Path<String>, payload: Json<UpdateTagSettingsPayload>) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    let payload = payload.into_inner();
+
+    validate_data(&payload)?;
+
+    update_tag_settings(&ctx.db, &jwt_payload, &username, payload).await?;
+
+    Ok(HttpResponse::Ok().json(
+        json!({
+            "code": StatusCode::OK.as_u16()
+        })
+    ))
+}
@@ -0,0 +1,44 @@
+use actix_web::web::{ServiceConfig, scope};
+use crate::blocks::blocks_routes::get_blocks_routes;
+use crate::conversations::conversations_routes::get_conversations_routes;
+use crate::bookmarks::bookmarks_routes::get_bookmarks_routes;
+use crate::favorites::favorites_routes::get_favorites_routes;
+use crate::follows::follows_routes::{get_follow_requests_routes, get_followers_routes, get_following_routes};
+use crate::hashtags::hashtags_routes::get_followed_hashtags_routes;
+use crate::highlights::highlights_routes::get_highlights_routes;
+use crate::insights::insights_routes::get_account_insights_routes;
+use crate::media::media_routes::get_media_routes;
+use crate::mentions::mentions_routes::get_mentions_routes;
+use crate::mutes::mutes_routes::get_mutes_routes;
+use crate::notifications::notifications_routes::get_notifications_routes;
+use crate::realtime::realtime_routes::{get_chat_routes, get_stream_routes};
+use crate::post_file_tags::post_file_tags_routes::get_tagged_posts_routes;
+use crate::posts::posts_routes::get_posts_routes;
+use crate::stories::stories_routes::get_stories_routes;
+use crate::suggestions::suggestions_routes::get_suggestions_routes;
+use super::users_controller::{update_privacy_handler, update_tag_settings_handler};
+
+pub fn get_users_routes(cfg: &mut ServiceConfig) {
+    cfg.configure(get_suggestions_routes)
+        .service(update_privacy_handler)
+        .service(update_tag_settings_handler)
+        .service(scope("/{username}/posts").configure(get_posts_routes))
+        .service(scope("/{username}/media").configure(get_media_routes))
+        .service(scope("/{username}/stories").configure(get_stories_routes))
+        .service(scope("/{username}/highlights").configure(get_highlights_routes))
+        .service(scope("/{username}/bookmarks").configure(get_bookmarks_routes))
+        .service(scope("/{username}/favorites").configure(get_favorites_routes))
+        .service(scope("/{username}/mentions").configure(get_mentions_routes))
+        .service(scope("/{username}/tagged").configure(get_tagged_posts_routes))
+        .service(scope("/{username}/followers").configure(get_followers_routes))
+        .service(scope("/{username}/following").configure(get_following_routes))
+        .service(scope("/{username}/follow-requests").configure(get_follow_requests_routes))
+        .service(scope("/{username}/blocks").configure(get_blocks_routes))
+        .service(scope("/{username}/mutes").configure(get_mutes_routes))
+        .service(scope("/{username}/hashtags").configure(get_followed_hashtags_routes))
+        .service(scope("/{username}/notifications").configure(get_notifications_routes))
+        .service(scope("/{username}/conversations").configure(get_conversations_routes))
+        .service(scope("/{username}/stream").configure(get_stream_routes))
+        .service(scope("/{username}/chat").configure(get_chat_routes))
+        .service(scope("/{username}/insights").configure(get_account_insights_routes));
+}
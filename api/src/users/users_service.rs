@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+use actix_web::http::StatusCode;
+use sea_orm::{Condition, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect, Statement, TransactionTrait};
+use sea_orm::sea_query::Query;
+use entity::users::{Entity, Column, Model};
+use entity::follows;
+use crate::auth::JwtTokenPayload;
+use crate::blocks::blocks_service::{is_blocked_between, not_blocked_condition};
+use crate::error::HttpResponseError;
+use crate::events::{DomainEvent, EventBus};
+use crate::follows::follows_service::{add_to_followers_count, is_following};
+use crate::stories::stories_service::active_stories_threshold;
+use crate::timeline::{TimelineJob, TimelineQueue};
+use crate::Result;
+use super::{PostAuthor, UpdatePrivacyPayload, UpdateTagSettingsPayload, ALLOW_TAGS_FROM_EVERYONE, ALLOW_TAGS_FROM_FOLLOWING};
+
+pub async fn find_user_by_username<C: ConnectionTrait>(db: &C, username: &str) -> Result<Model> {
+    let user = Entity::find()
+        .filter(Column::Username.eq(username))
+        .one(db)
+        .await?;
+
+    match user {
+        Some(user) => Ok(user),
+        None => Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::NOT_FOUND.as_u16())
+                .set_error_message("User not found")
+        )
+    }
+}
+
+// Make sure the logged-in user is the owner of the `{username}` resource
+pub fn ensure_owner(jwt_payload: &JwtTokenPayload, username: &str) -> Result<()> {
+    if jwt_payload.username != username {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("You are not allowed to access this resource")
+        );
+    }
+
+    Ok(())
+}
+
+// Private accounts are only visible to themselves and their followers
+async fn passes_privacy<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, owner: &Model) -> Result<bool> {
+    if !owner.is_private {
+        return Ok(true);
+    }
+
+    match viewer_id {
+        None => Ok(false),
+        Some(viewer_id) if viewer_id == owner.id.as_slice() => Ok(true),
+        Some(viewer_id) => is_following(db, viewer_id, &owner.id).await,
+    }
+}
+
+// Whether the viewer may see the owner's posts, stories and follower lists
+pub async fn can_view_content<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, owner: &Model) -> Result<bool> {
+    if let Some(viewer_id) = viewer_id {
+        if is_blocked_between(db, viewer_id, &owner.id).await? {
+            return Ok(false);
+        }
+    }
+
+    passes_privacy(db, viewer_id, owner).await
+}
+
+pub async fn ensure_can_view_content<C: ConnectionTrait>(db: &C, viewer_id: Option<&[u8]>, owner: &Model) -> Result<()> {
+    // Blocked users should not even know the account exists
+    if let Some(viewer_id) = viewer_id {
+        if is_blocked_between(db, viewer_id, &owner.id).await? {
+            return Err(
+                HttpResponseError::default()
+                    .set_code(StatusCode::NOT_FOUND.as_u16())
+                    .set_error_message("User not found")
+            );
+        }
+    }
+
+    if !passes_privacy(db, viewer_id, owner).await? {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("This account is private")
+        );
+    }
+
+    Ok(())
+}
+
+// Whether the tagger may tag the user on a photo, following the user's tag settings.
+// Users can always tag themselves
+pub async fn ensure_can_tag<C: ConnectionTrait>(db: &C, tagger_id: &[u8], user: &Model) -> Result<()> {
+    if tagger_id == user.id.as_slice() {
+        return Ok(());
+    }
+
+    let allowed = match user.allow_tags_from.as_str() {
+        ALLOW_TAGS_FROM_EVERYONE => true,
+        ALLOW_TAGS_FROM_FOLLOWING => is_following(db, &user.id, tagger_id).await?,
+        _ => false,
+    };
+
+    if !allowed {
+        return Err(
+            HttpResponseError::default()
+                .set_code(StatusCode::FORBIDDEN.as_u16())
+                .set_error_message("This user does not allow you to tag them")
+        );
+    }
+
+    Ok(())
+}
+
+// The same rule as `can_view_content`, as a filter on the author column
+// of any query that reads posts or stories
+pub fn visible_authors_condition<C: ColumnTrait>(author_col: C, viewer_id: Option<&[u8]>) -> Condition {
+    let public_users = Query::select()
+        .column(Column::Id)
+        .from(Entity)
+        .and_where(Column::IsPrivate.eq(false))
+        .to_owned();
+
+    let mut condition = Condition::any().add(author_col.in_subquery(public_users));
+
+    if let Some(viewer_id) = viewer_id {
+        let followees = Query::select()
+            .column(follows::Column::FolloweeId)
+            .from(follows::Entity)
+            .and_where(follows::Column::FollowerId.eq(viewer_id.to_vec()))
+            .to_owned();
+
+        condition = condition
+            .add(author_col.eq(viewer_id.to_vec()))
+            .add(author_col.in_subquery(followees));
+
+        return Condition::all()
+            .add(condition)
+            .add(not_blocked_condition(author_col, viewer_id));
+    }
+
+    condition
+}
+
+pub async fn has_active_stories<C: ConnectionTrait>(db: &C, user_id: &[u8]) -> Result<bool> {
+    let stories_count = entity::stories::Entity::find()
+        .filter(entity::stories::Column::UserId.eq(user_id.to_vec()))
+        .filter(entity::stories::Column::CreatedAt.gt(active_stories_threshold()))
+        .count(db)
+        .await?;
+
+    Ok(stories_count > 0)
+}
+
+// Batched version of `get_post_author`, keyed by user id
+pub async fn get_post_authors<C: ConnectionTrait>(db: &C, user_ids: Vec<Vec<u8>>) -> Result<HashMap<Vec<u8>, PostAuthor>> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let authors_with_stories: HashSet<Vec<u8>> = entity::stories::Entity::find()
+        .select_only()
+        .column(entity::stories::Column::UserId)
+        .distinct()
+        .filter(entity::stories::Column::UserId.is_in(user_ids.clone()))
+        .filter(entity::stories::Column::CreatedAt.gt(active_stories_threshold()))
+        .into_tuple::<Vec<u8>>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let authors = Entity::find()
+        .filter(Column::Id.is_in(user_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| {
+            let author = PostAuthor {
+                has_stories: authors_with_stories.contains(&user.id),
+                username: user.username,
+                picture_url: user.picture_url,
+            };
+
+            (user.id, author)
+        })
+        .collect();
+
+    Ok(authors)
+}
+
+pub async fn get_post_author<C: ConnectionTrait>(db: &C, user: &Model) -> Result<PostAuthor> {
+    Ok(PostAuthor {
+        username: user.username.clone(),
+        picture_url: user.picture_url.clone(),
+        has_stories: has_active_stories(db, &user.id).await?,
+    })
+}
+
+pub async fn update_privacy(db: &DatabaseConnection, timeline: &TimelineQueue, events: &EventBus, jwt_payload: &JwtTokenPayload, username: &str, data: UpdatePrivacyPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+    let is_private = data.is_private.unwrap();
+
+    let txn = db.begin().await?;
+
+    Entity::update_many()
+        .col_expr(Column::IsPrivate, is_private.into())
+        .filter(Column::Id.eq(user.id.clone()))
+        .exec(&txn)
+        .await?;
+
+    // Going public accepts every pending follow request
+    let mut requester_ids: Vec<Vec<u8>> = vec![];
+    let mut new_follower_ids: Vec<Vec<u8>> = vec![];
+
+    if !is_private {
+        requester_ids = entity::follow_requests::Entity::find()
+            .select_only()
+            .column(entity::follow_requests::Column::RequesterId)
+            .filter(entity::follow_requests::Column::TargetId.eq(user.id.clone()))
+            .into_tuple()
+            .all(&txn)
+            .await?;
+
+        // Stale requests of users who already follow do not create a follow
+        let already_following: HashSet<Vec<u8>> = entity::follows::Entity::find()
+            .select_only()
+            .column(entity::follows::Column::FollowerId)
+            .filter(entity::follows::Column::FolloweeId.eq(user.id.clone()))
+            .filter(entity::follows::Column::FollowerId.is_in(requester_ids.clone()))
+            .into_tuple::<Vec<u8>>()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+
+        new_follower_ids = requester_ids
+            .iter()
+            .filter(|requester_id| !already_following.contains(*requester_id))
+            .cloned()
+            .collect();
+
+        let result = txn.execute(Statement::from_sql_and_values(
+            DbBackend::MySql,
+            "INSERT IGNORE INTO follows (follower_id, followee_id) \
+             SELECT requester_id, target_id FROM follow_requests WHERE target_id = ?",
+            [user.id.clone().into()],
+        )).await?;
+
+        add_to_followers_count(&txn, &user.id, result.rows_affected() as i32).await?;
+
+        entity::follow_requests::Entity::delete_many()
+            .filter(entity::follow_requests::Column::TargetId.eq(user.id.clone()))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    for requester_id in requester_ids {
+        events.publish(DomainEvent::FollowRequestClosed { requester_id: requester_id.clone(), target_id: user.id.clone() });
+        timeline.enqueue(TimelineJob::Backfill { user_id: requester_id, author_id: user.id.clone() });
+    }
+
+    for follower_id in new_follower_ids {
+        events.publish(DomainEvent::Followed { follower_id, followee_id: user.id.clone() });
+    }
+
+    Ok(())
+}
+
+pub async fn update_tag_settings(db: &DatabaseConnection, jwt_payload: &JwtTokenPayload, username: &str, data: UpdateTagSettingsPayload) -> Result<()> {
+    ensure_owner(jwt_payload, username)?;
+
+    let user = find_user_by_username(db, username).await?;
+
+    // Existing tags stay, the setting only applies to new ones
+    Entity::update_many()
+        .col_expr(Column::AllowTagsFrom, data.allow_tags_from.unwrap().into())
+        .filter(Column::Id.eq(user.id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
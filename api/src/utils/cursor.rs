@@ -0,0 +1,126 @@
+use std::str::FromStr;
+use actix_web::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, TimeZone, Utc};
+use sea_orm::{Condition, ColumnTrait};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+use crate::error::HttpResponseError;
+use crate::Result;
+
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+
+fn invalid_cursor() -> HttpResponseError {
+    HttpResponseError::default()
+        .set_code(StatusCode::BAD_REQUEST.as_u16())
+        .set_error_message("Invalid cursor")
+}
+
+// A position in a list ordered by `(created_at DESC, id DESC)`.
+// Unlike an offset it stays stable while new rows are being inserted on top
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Vec<u8>,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, id: Vec<u8>) -> Self {
+        Self { created_at, id }
+    }
+
+    pub fn encode(&self) -> String {
+        let id = Uuid::from_slice(&self.id).unwrap_or_default();
+        let raw = format!("{}:{}", self.created_at.timestamp_micros(), id.simple());
+        general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(value: &str) -> Result<Self> {
+        let raw = general_purpose::URL_SAFE_NO_PAD.decode(value).map_err(|_| invalid_cursor())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid_cursor())?;
+        let (micros, id) = raw.split_once(':').ok_or_else(invalid_cursor)?;
+
+        let micros: i64 = micros.parse().map_err(|_| invalid_cursor())?;
+        let created_at = Utc.timestamp_micros(micros).single().ok_or_else(invalid_cursor)?;
+        let id = Uuid::from_str(id).map_err(|_| invalid_cursor())?;
+
+        Ok(Self { created_at, id: Vec::from(id) })
+    }
+
+    // Rows that come strictly after this cursor in `(created_at DESC, id DESC)` order
+    pub fn after_condition<C: ColumnTrait>(&self, created_at_col: C, id_col: C) -> Condition {
+        Condition::any()
+            .add(created_at_col.lt(self.created_at))
+            .add(
+                Condition::all()
+                    .add(created_at_col.eq(self.created_at))
+                    .add(id_col.lt(self.id.clone()))
+            )
+    }
+}
+
+// Cursor for tables with an auto increment key, where the key alone follows insertion order
+pub fn encode_id_cursor(id: i32) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(id.to_string())
+}
+
+pub fn decode_id_cursor(value: &str) -> Result<i32> {
+    let raw = general_purpose::URL_SAFE_NO_PAD.decode(value).map_err(|_| invalid_cursor())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid_cursor())?;
+
+    raw.parse().map_err(|_| invalid_cursor())
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CursorQuery {
+    pub cursor: Option<String>,
+
+    #[validate(range(min = 1, max = 50, message = "Limit must be between 1 and 50"))]
+    pub limit: Option<u64>,
+}
+
+impl CursorQuery {
+    pub fn get_limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn get_cursor(&self) -> Result<Option<Cursor>> {
+        self.cursor.as_deref().map(Cursor::decode).transpose()
+    }
+
+    pub fn get_id_cursor(&self) -> Result<Option<i32>> {
+        self.cursor.as_deref().map(decode_id_cursor).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_id_cursor, encode_id_cursor, Cursor};
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn should_encode_and_decode_cursor() {
+        let cursor = Cursor::new(
+            Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            Uuid::new_v4().into(),
+        );
+
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn should_encode_and_decode_id_cursor() {
+        assert_eq!(decode_id_cursor(&encode_id_cursor(42)).unwrap(), 42);
+        assert!(decode_id_cursor("not a cursor").is_err());
+    }
+
+    #[test]
+    fn should_reject_invalid_cursor() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode("MTIzOnp6").is_err());
+    }
+}
@@ -1,9 +1,13 @@
+pub mod cursor;
 pub mod jwt;
 pub mod password;
+pub mod storage;
+pub mod text;
 
 use std::str::FromStr;
 use actix_web::http::StatusCode;
 
+use sea_orm::{DbErr, SqlErr};
 use uuid::Uuid;
 use validator::ValidationError;
 use crate::error::{HttpResponseError, ResponseError};
@@ -15,6 +19,10 @@ pub fn check_valid_uuid(value: &str) -> Result<(), ValidationError> {
     }
 }
 
+pub fn check_valid_uuids(values: &[String]) -> Result<(), ValidationError> {
+    values.iter().try_for_each(|value| check_valid_uuid(value))
+}
+
 pub fn parse_validation_errors(validation_errors: validator::ValidationErrors) -> Vec<ResponseError> {
     let field_errors: Vec<ResponseError> = validation_errors
         .field_errors()
@@ -58,6 +66,11 @@ pub fn validate_data<T>(data: &T) -> crate::Result<()>
     }
 }
 
+// Used to turn a lost insert race on a unique key into a proper client error
+pub fn is_unique_violation(db_err: &DbErr) -> bool {
+    matches!(db_err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+}
+
 // Caution when using this function
 // No error handler here...
 // Make sure you know what you are doing!
@@ -0,0 +1,127 @@
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use actix_web::web::{self, Bytes};
+use crate::configuration::StorageSettings;
+
+// Stores uploaded media and removes it once nothing references it anymore.
+// Only files served from the configured `base_url` are ours to delete,
+// anything else (external URLs, no storage configured) is left alone.
+// Disk access runs on the blocking thread pool to keep the workers free.
+#[derive(Debug, Clone, Default)]
+pub struct MediaStorage {
+    settings: Option<StorageSettings>,
+}
+
+impl MediaStorage {
+    pub fn new(settings: Option<StorageSettings>) -> Self {
+        Self { settings }
+    }
+
+    // Maps a public file URL to its path on disk, refusing anything
+    // that could point outside of the storage directory
+    fn local_path(&self, file_url: &str) -> Option<PathBuf> {
+        let settings = self.settings.as_ref()?;
+        let relative = file_url.strip_prefix(settings.base_url.trim_end_matches('/'))?;
+        let relative = Path::new(relative.strip_prefix('/')?);
+
+        let mut components = relative.components().peekable();
+        components.peek()?;
+
+        if !components.all(|component| matches!(component, Component::Normal(_))) {
+            return None;
+        }
+
+        Some(Path::new(&settings.path).join(relative))
+    }
+
+    // Writes the file under `file_name` and returns the URL it is served from,
+    // `None` when no storage is configured
+    pub async fn save_file(&self, file_name: &str, bytes: Bytes) -> std::io::Result<Option<String>> {
+        let Some(settings) = self.settings.as_ref() else {
+            return Ok(None);
+        };
+
+        let file_url = format!("{}/{}", settings.base_url.trim_end_matches('/'), file_name);
+        let Some(path) = self.local_path(&file_url) else {
+            return Err(std::io::Error::new(ErrorKind::InvalidInput, format!("Invalid media file name: {}", file_name)));
+        };
+
+        web::block(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, bytes)
+        }).await.map_err(std::io::Error::other)??;
+
+        Ok(Some(file_url))
+    }
+
+    pub async fn delete_file(&self, file_url: &str) -> std::io::Result<()> {
+        let Some(path) = self.local_path(file_url) else {
+            tracing::info!("Skipping deletion of media not managed by this server: {}", file_url);
+            return Ok(());
+        };
+
+        // Already gone is as good as deleted
+        match web::block(move || std::fs::remove_file(path)).await.map_err(std::io::Error::other)? {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use actix_web::web::Bytes;
+    use crate::configuration::StorageSettings;
+    use super::MediaStorage;
+
+    fn storage() -> MediaStorage {
+        MediaStorage::new(Some(StorageSettings {
+            base_url: String::from("https://cdn.example.com/media/"),
+            path: String::from("/var/media"),
+        }))
+    }
+
+    #[test]
+    fn should_map_managed_urls_to_local_paths() {
+        assert_eq!(
+            storage().local_path("https://cdn.example.com/media/stories/a.jpg"),
+            Some(PathBuf::from("/var/media/stories/a.jpg"))
+        );
+    }
+
+    #[test]
+    fn should_ignore_unmanaged_urls() {
+        assert_eq!(storage().local_path("https://other.example.com/media/a.jpg"), None);
+        assert_eq!(storage().local_path("https://cdn.example.com/mediaa.jpg"), None);
+        assert_eq!(storage().local_path("https://cdn.example.com/media/"), None);
+        assert_eq!(MediaStorage::default().local_path("https://cdn.example.com/media/a.jpg"), None);
+    }
+
+    #[actix_web::test]
+    async fn should_save_files_under_the_storage_directory() {
+        let path = std::env::temp_dir().join("insta-storage-test");
+        let storage = MediaStorage::new(Some(StorageSettings {
+            base_url: String::from("https://cdn.example.com/media"),
+            path: path.to_string_lossy().into_owned(),
+        }));
+
+        let file_url = storage.save_file("uploads/a.jpg", Bytes::from_static(b"jpeg")).await.unwrap();
+
+        assert_eq!(file_url.as_deref(), Some("https://cdn.example.com/media/uploads/a.jpg"));
+        assert_eq!(std::fs::read(path.join("uploads/a.jpg")).unwrap(), b"jpeg");
+        assert!(storage.save_file("../a.jpg", Bytes::from_static(b"jpeg")).await.is_err());
+        assert_eq!(MediaStorage::default().save_file("uploads/a.jpg", Bytes::from_static(b"jpeg")).await.unwrap(), None);
+
+        storage.delete_file("https://cdn.example.com/media/uploads/a.jpg").await.unwrap();
+    }
+
+    #[test]
+    fn should_reject_paths_escaping_the_storage_directory() {
+        assert_eq!(storage().local_path("https://cdn.example.com/media/../etc/passwd"), None);
+        assert_eq!(storage().local_path("https://cdn.example.com/media/stories/../../a.jpg"), None);
+        assert_eq!(storage().local_path("https://cdn.example.com/media//etc/passwd"), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+pub const MAX_HASHTAG_LENGTH: usize = 100;
+// Extra tags past this many are ignored, like any other word of the text
+pub const MAX_HASHTAGS_PER_TEXT: usize = 30;
+// Same as the `username` column
+pub const MAX_USERNAME_LENGTH: usize = 32;
+pub const MAX_MENTIONS_PER_TEXT: usize = 20;
+// Shorter words are left out of the MySQL full-text indexes (`innodb_ft_min_token_size`)
+pub const MIN_FULLTEXT_WORD_LENGTH: usize = 3;
+pub const MAX_SEARCH_WORDS: usize = 10;
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Usernames can contain dots, but a mention never ends with one: `@bob.` is `bob`
+fn is_mention_char(c: char) -> bool {
+    is_tag_char(c) || c == '.'
+}
+
+// A tag only starts at a marker that is not glued to the end of a word,
+// so neither `a#b` nor `bob@mail.com` contain a tag
+fn extract_tags(text: &str, marker: char, is_body_char: fn(char) -> bool, max_length: usize) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut seen = HashSet::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let starts_tag = c == marker && !previous.is_some_and(is_tag_char);
+        previous = Some(c);
+
+        if !starts_tag {
+            continue;
+        }
+
+        let start = index + c.len_utf8();
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !is_body_char(next) {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        let tag = text[start..end].trim_end_matches('.').to_lowercase();
+
+        if tag.is_empty() || tag.chars().count() > max_length || !seen.insert(tag.clone()) {
+            continue;
+        }
+
+        tags.push(tag);
+    }
+
+    tags
+}
+
+// Lowercased hashtags of a text without the `#`, in order of appearance and without duplicates
+pub fn extract_hashtags(text: &str) -> Vec<String> {
+    let mut hashtags = extract_tags(text, '#', is_tag_char, MAX_HASHTAG_LENGTH);
+    hashtags.truncate(MAX_HASHTAGS_PER_TEXT);
+    hashtags
+}
+
+// Lowercased usernames mentioned in a text without the `@`. They still have to be
+// resolved against the users table, most of them may not exist
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    let mut mentions = extract_tags(text, '@', is_mention_char, MAX_USERNAME_LENGTH);
+    mentions.truncate(MAX_MENTIONS_PER_TEXT);
+    mentions
+}
+
+// Whether a path segment is a valid hashtag name, with or without the leading `#`
+pub fn normalize_hashtag(name: &str) -> Option<String> {
+    let name = name.strip_prefix('#').unwrap_or(name);
+
+    if name.is_empty() || name.chars().count() > MAX_HASHTAG_LENGTH || !name.chars().all(is_tag_char) {
+        return None;
+    }
+
+    Some(name.to_lowercase())
+}
+
+// Boolean mode query matching rows that contain every word of the search, each as a prefix.
+// Operators typed by the user are dropped with the other punctuation.
+// `None` when no word is long enough to be in the index
+pub fn to_fulltext_query(search: &str) -> Option<String> {
+    let mut words: Vec<String> = Vec::new();
+
+    for word in search.split(|c: char| !is_tag_char(c)) {
+        let word = word.to_lowercase();
+        if word.chars().count() >= MIN_FULLTEXT_WORD_LENGTH && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words.truncate(MAX_SEARCH_WORDS);
+
+    if words.is_empty() {
+        return None;
+    }
+
+    Some(words.iter().map(|word| format!("+{}*", word)).collect::<Vec<_>>().join(" "))
+}
+
+// LIKE pattern for values starting with the text, its wildcards matched literally
+pub fn to_prefix_pattern(text: &str) -> String {
+    let mut pattern = String::with_capacity(text.len() + 1);
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_hashtags, extract_mentions, normalize_hashtag, to_fulltext_query, to_prefix_pattern};
+
+    #[test]
+    fn should_extract_hashtags_in_order_without_duplicates() {
+        assert_eq!(
+            extract_hashtags("Sunset at the #Beach! #travel,#beach #summer_2024"),
+            vec!["beach", "travel", "summer_2024"]
+        );
+    }
+
+    #[test]
+    fn should_ignore_hashtags_glued_to_words() {
+        assert_eq!(extract_hashtags("issue#42 and # alone and ##double"), vec!["double"]);
+    }
+
+    #[test]
+    fn should_support_unicode_hashtags() {
+        assert_eq!(extract_hashtags("#Café #東京"), vec!["café", "東京"]);
+    }
+
+    #[test]
+    fn should_normalize_hashtag_names() {
+        assert_eq!(normalize_hashtag("#Travel"), Some(String::from("travel")));
+        assert_eq!(normalize_hashtag("two words"), None);
+        assert_eq!(normalize_hashtag(""), None);
+    }
+
+    #[test]
+    fn should_extract_mentions_without_emails_and_trailing_dots() {
+        assert_eq!(
+            extract_mentions("Thanks @Jane.Doe and @bob. Write to bob@mail.com, cc @jane.doe"),
+            vec!["jane.doe", "bob"]
+        );
+    }
+
+    #[test]
+    fn should_build_fulltext_query_from_words() {
+        assert_eq!(to_fulltext_query("Sunset +beach -\"at\" sunset*"), Some(String::from("+sunset* +beach*")));
+        assert_eq!(to_fulltext_query("a b"), None);
+    }
+
+    #[test]
+    fn should_escape_prefix_pattern() {
+        assert_eq!(to_prefix_pattern("john_doe%"), "john\\_doe\\%%");
+    }
+}
@@ -19,7 +19,7 @@ async fn signup_should_required_signup_data() {
     let app = utils::start_test_server().await;
     let client = Client::new();
 
-    let resp = client.post(&format!("{}/api/v1/auth/signup", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/signup", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({}))
         .send()
@@ -57,7 +57,7 @@ async fn signup_password_and_c_password_should_match() {
     let app = utils::start_test_server().await;
     let client = Client::new();
 
-    let resp = client.post(&format!("{}/api/v1/auth/signup", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/signup", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "email": SafeEmail().fake::<String>(),
@@ -92,7 +92,7 @@ async fn signup_should_not_allow_username_with_non_allowed_character() {
     let password: String = Password(5..10).fake();
     let username: String = format!("{}@&_hello", Username().fake::<String>());
 
-    let resp = client.post(&format!("{}/api/v1/auth/signup", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/signup", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "email": SafeEmail().fake::<String>(),
@@ -129,7 +129,7 @@ async fn signup_should_success() {
 
     let password = Password(5..10).fake::<String>();
 
-    let resp = client.post(&format!("{}/api/v1/auth/signup", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/signup", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "email": user_email,
@@ -175,7 +175,7 @@ async fn signup_should_not_allowed_duplicate_email() {
 
     let client = Client::new();
 
-    let resp = client.post(&format!("{}/api/v1/auth/signup", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/signup", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "email": &created_user.email,
@@ -212,7 +212,7 @@ async fn signin_should_required_signin_data() {
     let app = utils::start_test_server().await;
     let client = Client::new();
 
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({}))
         .send()
@@ -242,7 +242,7 @@ async fn signin_should_not_allow_wrong_credentials() {
 
     let (created_user, _p) = create_random_user(&app.db).await;
 
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.email,
@@ -271,7 +271,7 @@ async fn signin_email_password_should_valid() {
 
     let (created_user, password) = create_random_user(&app.db).await;
 
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.email,
@@ -296,7 +296,7 @@ async fn signin_username_password_should_valid() {
 
     let (created_user, password) = create_random_user(&app.db).await;
 
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.username,
@@ -321,7 +321,7 @@ async fn signin_should_return_proper_data() {
 
     let (created_user, password) = create_random_user(&app.db).await;
 
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.email,
@@ -362,7 +362,7 @@ async fn getnewtoken_should_required_refresh_token() {
     let client = Client::new();
 
     // Try to request a new token
-    let resp = client.post(&format!("{}/api/v1/auth/token", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/token", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({}))
         .send()
@@ -387,7 +387,7 @@ async fn getnewtoken_should_success() {
     let (created_user, password) = create_random_user(&app.db).await;
 
     // Logged in the user
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.email,
@@ -407,7 +407,7 @@ async fn getnewtoken_should_success() {
     let refresh_token = response_body["refreshToken"].as_str().expect("Token existed here!");
 
     // Try to request a new token
-    let resp = client.post(&format!("{}/api/v1/auth/token", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth/token", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "refreshToken": refresh_token
@@ -453,7 +453,7 @@ async fn getme_should_unauthorized_invalid_jwt() {
     let encoding_key = EncodingKey::from_secret("HELLO WORLD".as_bytes());
     let invalid_token = encode(&jsonwebtoken::Header::default(), &token_payload, &encoding_key).expect("should encode jwt");
 
-    let resp = client.get(&format!("{}/api/v1/auth/me", &app.address))
+    let resp = client.get(format!("{}/api/v1/auth/me", &app.address))
         .header("Accept", "application/json")
         .bearer_auth(&invalid_token)
         .send()
@@ -478,7 +478,7 @@ async fn getme_should_success() {
     let (created_user, password) = create_random_user(&app.db).await;
 
     // Logged in the user
-    let resp = client.post(&format!("{}/api/v1/auth", &app.address))
+    let resp = client.post(format!("{}/api/v1/auth", &app.address))
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
             "emailUsername": &created_user.email,
@@ -498,7 +498,7 @@ async fn getme_should_success() {
     let token = response_body["token"].as_str().unwrap();
 
     // Try to request a new token
-    let resp = client.get(&format!("{}/api/v1/auth/me", &app.address))
+    let resp = client.get(format!("{}/api/v1/auth/me", &app.address))
         .header("Accept", "application/json")
         .bearer_auth(token)
         .send()
@@ -0,0 +1,209 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use uuid::Uuid;
+use insta::error::HttpResponseError;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_follows, delete_post, delete_user, follow_user, get_user_token, parse_response_body};
+
+mod utils;
+
+// ---- BLOCK UNIT TESTS ----
+
+#[actix_web::test]
+async fn block_should_remove_follows_in_both_directions() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (blocker, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &blocker.id, &blocked.id).await;
+    follow_user(&app.db, &blocked.id, &blocker.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &blocked.username))
+        .bearer_auth(get_user_token(&app.config, &blocker))
+        .send()
+        .await
+        .unwrap();
+
+    let follows_count = entity::follows::Entity::find()
+        .filter(
+            entity::follows::Column::FollowerId.eq(blocker.id.clone())
+                .or(entity::follows::Column::FolloweeId.eq(blocker.id.clone()))
+        )
+        .count(&app.db)
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &blocker.id).await;
+    delete_follows(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocked.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(follows_count, 0);
+}
+
+#[actix_web::test]
+async fn block_should_prevent_new_follows() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (blocker, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &blocked.username))
+        .bearer_auth(get_user_token(&app.config, &blocker))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/followers", &app.address, &blocker.username))
+        .bearer_auth(get_user_token(&app.config, &blocked))
+        .send()
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &blocker.id).await;
+    delete_follows(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocked.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    let error = response_body.errors.first().unwrap();
+    assert_eq!(error.error, Some("You cannot interact with this user".to_owned()));
+}
+
+#[actix_web::test]
+async fn block_should_hide_posts_from_blocked_user() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (blocker, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &blocker.id).await;
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &blocked.username))
+        .bearer_auth(get_user_token(&app.config, &blocker))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &blocker.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &blocked))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_blocks_and_mutes(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocked.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn unblock_should_success() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (blocker, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &blocker);
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &blocked.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &blocked.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client.get(format!("{}/api/v1/users/{}/blocks", &app.address, &blocker.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocker.id).await;
+    delete_user(&app.db, &blocked.id).await;
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert!(response_body["data"].as_array().unwrap().is_empty());
+}
+
+// ---- END OF BLOCK UNIT TESTS ----
+
+// ---- MUTE UNIT TESTS ----
+
+#[actix_web::test]
+async fn mute_should_be_listed_and_undone() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (muter, _p) = create_random_user(&app.db).await;
+    let (muted, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &muter);
+
+    let resp = client.post(format!("{}/api/v1/users/{}/mutes/{}", &app.address, &muter.username, &muted.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client.get(format!("{}/api/v1/users/{}/mutes", &app.address, &muter.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let muted_users = response_body["data"].as_array().unwrap();
+
+    assert_eq!(muted_users.len(), 1);
+    assert_eq!(muted_users[0]["username"].as_str(), Some(muted.username.as_str()));
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/mutes/{}", &app.address, &muter.username, &muted.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &muter.id).await;
+    delete_user(&app.db, &muter.id).await;
+    delete_user(&app.db, &muted.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn mute_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (muter, _p) = create_random_user(&app.db).await;
+    let (other_user, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/mutes", &app.address, &muter.username))
+        .bearer_auth(get_user_token(&app.config, &other_user))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &muter.id).await;
+    delete_user(&app.db, &other_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF MUTE UNIT TESTS ----
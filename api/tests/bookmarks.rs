@@ -0,0 +1,468 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, create_upload, delete_bookmark_collections, delete_post, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// ---- CREATE BOOKMARK UNIT TESTS ----
+
+#[actix_web::test]
+async fn createbookmark_should_be_idempotent() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let mut statuses = vec![];
+    for _ in 0..2 {
+        let resp = client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let resp = client.get(url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CREATED]);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"].as_array().unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn createbookmark_should_return_404_for_hidden_post() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &author).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &reader))
+        .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF CREATE BOOKMARK UNIT TESTS ----
+
+
+
+// ---- GET BOOKMARKS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getbookmarks_should_be_private() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (reader, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &reader.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn getbookmarks_should_paginate_newest_first() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let posts = vec![
+        create_random_post(&app.db, &author.id).await,
+        create_random_post(&app.db, &author.id).await,
+        create_random_post(&app.db, &author.id).await,
+    ];
+
+    let url = format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    for post in &posts {
+        client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let first_page = client.get(format!("{}?limit=2", url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let first_page: serde_json::Value = parse_response_body(first_page).await;
+
+    let second_page = client.get(format!("{}?limit=2&cursor={}", url, first_page["nextCursor"].as_str().unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let second_page: serde_json::Value = parse_response_body(second_page).await;
+
+    for post in &posts {
+        delete_post(&app.db, &post.id).await;
+    }
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    let post_ids: Vec<String> = posts.iter().map(|post| Uuid::from_slice(&post.id).unwrap().to_string()).collect();
+    let first_ids: Vec<&str> = first_page["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+    let second_ids: Vec<&str> = second_page["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+
+    assert_eq!(first_ids, vec![post_ids[2].as_str(), post_ids[1].as_str()]);
+    assert_eq!(second_ids, vec![post_ids[0].as_str()]);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+// ---- END OF GET BOOKMARKS UNIT TESTS ----
+
+
+
+// ---- DELETE BOOKMARK UNIT TESTS ----
+
+#[actix_web::test]
+async fn deletebookmark_should_remove_post_from_collections() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+    let post_id = Uuid::from_slice(&post.id).unwrap();
+
+    let resp = client.post(format!("{}/collections", url))
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Recipes" }))
+        .send()
+        .await
+        .unwrap();
+    let collection: serde_json::Value = parse_response_body(resp).await;
+    let collection_id = collection["data"]["id"].as_str().unwrap().to_owned();
+
+    client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "postId": post_id, "collectionIds": [collection_id] }))
+        .send()
+        .await
+        .unwrap();
+
+    let delete_resp = client.delete(format!("{}/{}", url, post_id))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/collections", url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(delete_resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"][0]["bookmarksCount"].as_u64(), Some(0));
+}
+
+// ---- END OF DELETE BOOKMARK UNIT TESTS ----
+
+
+
+// ---- BOOKMARK COLLECTIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn addtocollection_should_bookmark_post_and_filter_bookmarks() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let saved = create_random_post(&app.db, &author.id).await;
+    let other = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let resp = client.post(format!("{}/collections", url))
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Travel" }))
+        .send()
+        .await
+        .unwrap();
+    let collection: serde_json::Value = parse_response_body(resp).await;
+    let collection_id = collection["data"]["id"].as_str().unwrap().to_owned();
+
+    let add_resp = client.put(format!("{}/collections/{}/posts/{}", url, collection_id, Uuid::from_slice(&saved.id).unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "postId": Uuid::from_slice(&other.id).unwrap() }))
+        .send()
+        .await
+        .unwrap();
+
+    let all = client.get(url.clone())
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let all: serde_json::Value = parse_response_body(all).await;
+
+    let filtered = client.get(format!("{}?collectionId={}", url, collection_id))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let filtered: serde_json::Value = parse_response_body(filtered).await;
+
+    let collections = client.get(format!("{}/collections", url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let collections: serde_json::Value = parse_response_body(collections).await;
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_post(&app.db, &saved.id).await;
+    delete_post(&app.db, &other.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(add_resp.status(), StatusCode::OK);
+    assert_eq!(all["data"].as_array().unwrap().len(), 2);
+    assert_eq!(filtered["data"].as_array().unwrap().len(), 1);
+    assert_eq!(filtered["data"][0]["id"].as_str(), Some(Uuid::from_slice(&saved.id).unwrap().to_string().as_str()));
+    assert_eq!(collections["data"][0]["bookmarksCount"].as_u64(), Some(1));
+    assert_eq!(collections["data"][0]["coverUrl"].as_str(), Some("https://bit.ly/3REd7XG"));
+}
+
+#[actix_web::test]
+async fn updatecollection_should_rename_and_set_cover() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (reader, _p) = create_random_user(&app.db).await;
+    let cover_url = create_upload(&app.db, &reader.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks/collections", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let resp = client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Later" }))
+        .send()
+        .await
+        .unwrap();
+    let collection: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.patch(format!("{}/{}", url, collection["data"]["id"].as_str().unwrap()))
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Read later", "coverUrl": cover_url }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"]["name"].as_str(), Some("Read later"));
+    assert_eq!(response_body["data"]["coverUrl"].as_str(), Some(cover_url.as_str()));
+}
+
+#[actix_web::test]
+async fn createcollection_should_reject_covers_of_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (reader, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let cover_url = create_upload(&app.db, &other.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/bookmarks/collections", &app.address, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &reader))
+        .json(&json!({ "name": "Not mine", "coverUrl": cover_url }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_user(&app.db, &reader.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn updatecollection_should_accept_post_files_and_remove_cover() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let saved = create_random_post(&app.db, &author.id).await;
+    let own_cover_url = create_upload(&app.db, &reader.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks/collections", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let resp = client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Design", "coverUrl": own_cover_url }))
+        .send()
+        .await
+        .unwrap();
+    let collection: serde_json::Value = parse_response_body(resp).await;
+    let collection_url = format!("{}/{}", url, collection["data"]["id"].as_str().unwrap());
+
+    // The post is not in the collection yet
+    let unsaved_file_resp = client.patch(collection_url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "coverUrl": "https://bit.ly/3REd7XG" }))
+        .send()
+        .await
+        .unwrap();
+
+    client.put(format!("{}/posts/{}", collection_url, Uuid::from_slice(&saved.id).unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let saved_file_resp = client.patch(collection_url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "coverUrl": "https://bit.ly/3REd7XG" }))
+        .send()
+        .await
+        .unwrap();
+
+    let both_resp = client.patch(collection_url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "coverUrl": "https://bit.ly/3REd7XG", "removeCover": true }))
+        .send()
+        .await
+        .unwrap();
+
+    let remove_resp = client.patch(collection_url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "removeCover": true }))
+        .send()
+        .await
+        .unwrap();
+    let remove_status = remove_resp.status();
+    let removed: serde_json::Value = parse_response_body(remove_resp).await;
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_post(&app.db, &saved.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(unsaved_file_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(saved_file_resp.status(), StatusCode::OK);
+    assert_eq!(both_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(remove_status, StatusCode::OK);
+    assert_eq!(removed["data"]["coverUrl"].as_str(), Some("https://bit.ly/3REd7XG"));
+}
+
+#[actix_web::test]
+async fn deletecollection_should_keep_bookmarks() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/bookmarks", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let resp = client.post(format!("{}/collections", url))
+        .bearer_auth(&token)
+        .json(&json!({ "name": "Music" }))
+        .send()
+        .await
+        .unwrap();
+    let collection: serde_json::Value = parse_response_body(resp).await;
+    let collection_id = collection["data"]["id"].as_str().unwrap().to_owned();
+
+    client.put(format!("{}/collections/{}/posts/{}", url, collection_id, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let delete_resp = client.delete(format!("{}/collections/{}", url, collection_id))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_bookmark_collections(&app.db, &reader.id).await;
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(delete_resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"].as_array().unwrap().len(), 1);
+}
+
+// ---- END OF BOOKMARK COLLECTIONS UNIT TESTS ----
@@ -0,0 +1,152 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use uuid::Uuid;
+use insta::comment_likes::comment_likes_service::reconcile_comment_likes_counts;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_post, delete_user, get_user_token, parse_response_body};
+
+mod utils;
+
+async fn create_comment(db: &sea_orm::DatabaseConnection, post: &entity::posts::Model, user_id: &[u8]) -> entity::post_comments::Model {
+    entity::post_comments::ActiveModel {
+        user_id: Set(user_id.to_vec()),
+        post_id: Set(post.id.clone()),
+        comment: Set("Nice".to_owned()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to insert comment")
+}
+
+// ---- LIKE COMMENT UNIT TESTS ----
+
+#[actix_web::test]
+async fn likecomment_should_keep_count_in_sync() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let comment = create_comment(&app.db, &post, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap(), comment.id);
+    let token = get_user_token(&app.config, &liker);
+
+    let mut likes_counts = vec![];
+    for _ in 0..2 {
+        let resp = client.post(url.clone())
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+        likes_counts.push(response_body["data"]["likesCount"].as_u64());
+    }
+
+    let resp = client.delete(url.clone())
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    likes_counts.push(response_body["data"]["likesCount"].as_u64());
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(likes_counts, vec![Some(1), Some(1), Some(0)]);
+}
+
+#[actix_web::test]
+async fn likecomment_should_not_found_unknown_comment() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts/{}/comments/0/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn likecomment_should_forbid_blocked_commenter() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let comment = create_comment(&app.db, &post, &commenter.id).await;
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &commenter.username, &liker.username))
+        .bearer_auth(get_user_token(&app.config, &commenter))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts/{}/comments/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap(), comment.id))
+        .bearer_auth(get_user_token(&app.config, &liker))
+        .send()
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &commenter.id).await;
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF LIKE COMMENT UNIT TESTS ----
+
+
+
+// ---- RECONCILIATION UNIT TESTS ----
+
+#[actix_web::test]
+async fn reconciliation_should_fix_drifted_counters() {
+    let app = utils::start_test_server().await;
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let comment = create_comment(&app.db, &post, &author.id).await;
+
+    entity::comment_likes::ActiveModel {
+        comment_id: Set(comment.id),
+        user_id: Set(author.id.clone()),
+        ..Default::default()
+    }.insert(&app.db).await.unwrap();
+
+    let mut drifted: entity::post_comments::ActiveModel = comment.clone().into();
+    drifted.likes_count = Set(7);
+    drifted.update(&app.db).await.unwrap();
+
+    reconcile_comment_likes_counts(&app.db).await.unwrap();
+
+    let reconciled = entity::post_comments::Entity::find_by_id(comment.id)
+        .one(&app.db)
+        .await
+        .unwrap()
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(reconciled.likes_count, 1);
+}
+
+// ---- END OF RECONCILIATION UNIT TESTS ----
@@ -0,0 +1,334 @@
+use chrono::Utc;
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use crate::utils::{create_random_user, create_story, create_upload, delete_blocks_and_mutes, delete_conversations, delete_follows, delete_stories, delete_user, follow_user, get_user_token, parse_response_body, wait_for_notifications};
+
+mod utils;
+
+async fn create_conversation(client: &Client, address: &str, username: &str, others: &[&str], token: &str) -> serde_json::Value {
+    let resp = client.post(format!("{}/api/v1/users/{}/conversations", address, username))
+        .bearer_auth(token)
+        .json(&json!({ "usernames": others }))
+        .send()
+        .await
+        .unwrap();
+
+    parse_response_body(resp).await
+}
+
+async fn send_text(client: &Client, address: &str, username: &str, conversation_id: &str, text: &str, token: &str) -> reqwest::Response {
+    client.post(format!("{}/api/v1/users/{}/conversations/{}/messages", address, username, conversation_id))
+        .bearer_auth(token)
+        .json(&json!({ "text": text }))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn get_json(client: &Client, address: &str, username: &str, path: &str, token: &str) -> serde_json::Value {
+    let resp = client.get(format!("{}/api/v1/users/{}/conversations{}", address, username, path))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    parse_response_body(resp).await
+}
+
+// ---- CONVERSATIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn conversations_should_put_messages_from_strangers_in_requests() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let recipient_token = get_user_token(&app.config, &recipient);
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap().to_string();
+    send_text(&client, &app.address, &sender.username, &conversation_id, "Hello", &sender_token).await;
+
+    let inbox = get_json(&client, &app.address, &recipient.username, "", &recipient_token).await;
+    let requests = get_json(&client, &app.address, &recipient.username, "/requests", &recipient_token).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/conversations/{}/accept", &app.address, &recipient.username, &conversation_id))
+        .bearer_auth(&recipient_token)
+        .send()
+        .await
+        .unwrap();
+    let accept_status = resp.status();
+
+    let inbox_after = get_json(&client, &app.address, &recipient.username, "", &recipient_token).await;
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(inbox["data"].as_array().unwrap().len(), 0);
+
+    let requests = requests["data"].as_array().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["status"].as_str(), Some("request"));
+    assert_eq!(requests[0]["participants"][0]["user"]["username"].as_str(), Some(sender.username.as_str()));
+    assert_eq!(requests[0]["lastMessage"]["text"].as_str(), Some("Hello"));
+
+    assert_eq!(accept_status, StatusCode::OK);
+    assert_eq!(inbox_after["data"][0]["id"].as_str(), Some(conversation_id.as_str()));
+}
+
+#[actix_web::test]
+async fn conversations_should_put_messages_from_followed_users_in_the_inbox() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &recipient.id, &sender.id).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let recipient_token = get_user_token(&app.config, &recipient);
+
+    let first = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let second = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = first["data"]["id"].as_str().unwrap().to_string();
+    send_text(&client, &app.address, &sender.username, &conversation_id, "Hello", &sender_token).await;
+
+    let inbox = get_json(&client, &app.address, &recipient.username, "", &recipient_token).await;
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_follows(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(second["data"]["id"].as_str(), Some(conversation_id.as_str()));
+
+    let inbox = inbox["data"].as_array().unwrap();
+    assert_eq!(inbox.len(), 1);
+    assert_eq!(inbox[0]["status"].as_str(), Some("accepted"));
+    assert_eq!(inbox[0]["unreadCount"].as_u64(), Some(1));
+}
+
+#[actix_web::test]
+async fn conversations_should_only_be_visible_to_participants() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &get_user_token(&app.config, &sender)).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap();
+
+    let resp = client.get(format!("{}/api/v1/users/{}/conversations/{}/messages", &app.address, &stranger.username, conversation_id))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF CONVERSATIONS UNIT TESTS ----
+
+
+
+// ---- MESSAGES UNIT TESTS ----
+
+#[actix_web::test]
+async fn messages_should_move_the_read_cursor() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let recipient_token = get_user_token(&app.config, &recipient);
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap().to_string();
+    send_text(&client, &app.address, &sender.username, &conversation_id, "First", &sender_token).await;
+    let resp = send_text(&client, &app.address, &sender.username, &conversation_id, "Second", &sender_token).await;
+    let send_status = resp.status();
+    let last_message: serde_json::Value = parse_response_body(resp).await;
+    let last_message_id = last_message["data"]["id"].as_i64().unwrap();
+
+    let messages = get_json(&client, &app.address, &recipient.username, &format!("/{}/messages", conversation_id), &recipient_token).await;
+    let before = get_json(&client, &app.address, &recipient.username, &format!("/{}", conversation_id), &recipient_token).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/conversations/{}/read", &app.address, &recipient.username, &conversation_id))
+        .bearer_auth(&recipient_token)
+        .json(&json!({ "messageId": last_message_id }))
+        .send()
+        .await
+        .unwrap();
+    let read_status = resp.status();
+
+    let after = get_json(&client, &app.address, &recipient.username, &format!("/{}", conversation_id), &recipient_token).await;
+    let seen_by_sender = get_json(&client, &app.address, &sender.username, &format!("/{}", conversation_id), &sender_token).await;
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(send_status, StatusCode::CREATED);
+
+    let messages = messages["data"].as_array().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["text"].as_str(), Some("Second"));
+    assert_eq!(messages[1]["text"].as_str(), Some("First"));
+
+    assert_eq!(before["data"]["unreadCount"].as_u64(), Some(2));
+    assert_eq!(read_status, StatusCode::OK);
+    assert_eq!(after["data"]["unreadCount"].as_u64(), Some(0));
+    assert_eq!(seen_by_sender["data"]["participants"][0]["lastReadMessageId"].as_i64(), Some(last_message_id));
+}
+
+#[actix_web::test]
+async fn messages_should_not_be_sent_through_a_block() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap().to_string();
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &recipient.username, &sender.username))
+        .bearer_auth(get_user_token(&app.config, &recipient))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = send_text(&client, &app.address, &sender.username, &conversation_id, "Hello", &sender_token).await;
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_blocks_and_mutes(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn messages_should_only_share_media_uploaded_by_the_sender() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let own_media = create_upload(&app.db, &sender.id).await;
+    let other_media = create_upload(&app.db, &recipient.id).await;
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap().to_string();
+
+    let mut statuses = vec![];
+    for media_url in [other_media.as_str(), "https://bit.ly/3REd7XG", own_media.as_str()] {
+        let resp = client.post(format!("{}/api/v1/users/{}/conversations/{}/messages", &app.address, &sender.username, conversation_id))
+            .bearer_auth(&sender_token)
+            .json(&json!({ "mediaUrl": media_url }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::BAD_REQUEST, StatusCode::BAD_REQUEST, StatusCode::CREATED]);
+}
+
+#[actix_web::test]
+async fn messages_should_only_be_deleted_by_the_sender() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let recipient_token = get_user_token(&app.config, &recipient);
+
+    let conversation = create_conversation(&client, &app.address, &sender.username, &[&recipient.username], &sender_token).await;
+    let conversation_id = conversation["data"]["id"].as_str().unwrap().to_string();
+    let resp = send_text(&client, &app.address, &sender.username, &conversation_id, "Hello", &sender_token).await;
+    let message: serde_json::Value = parse_response_body(resp).await;
+    let message_id = message["data"]["id"].as_i64().unwrap();
+
+    let forbidden = client.delete(format!("{}/api/v1/users/{}/conversations/{}/messages/{}", &app.address, &recipient.username, &conversation_id, message_id))
+        .bearer_auth(&recipient_token)
+        .send()
+        .await
+        .unwrap();
+
+    let deleted = client.delete(format!("{}/api/v1/users/{}/conversations/{}/messages/{}", &app.address, &sender.username, &conversation_id, message_id))
+        .bearer_auth(&sender_token)
+        .send()
+        .await
+        .unwrap();
+
+    let messages = get_json(&client, &app.address, &recipient.username, &format!("/{}/messages", conversation_id), &recipient_token).await;
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    assert_eq!(forbidden.status(), StatusCode::FORBIDDEN);
+    assert_eq!(deleted.status(), StatusCode::OK);
+    assert_eq!(messages["data"].as_array().unwrap().len(), 0);
+}
+
+// ---- END OF MESSAGES UNIT TESTS ----
+
+
+
+// ---- STORY REPLIES UNIT TESTS ----
+
+#[actix_web::test]
+async fn storyreplies_should_message_and_notify_the_owner() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &owner.id, Utc::now()).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/stories/{}/replies", &app.address, &owner.username, Uuid::from_slice(&story.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .json(&json!({ "text": "Nice!" }))
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+    let message: serde_json::Value = parse_response_body(resp).await;
+
+    let replies_count = wait_for_notifications(&app.db, &owner.id, "story_reply", 1).await;
+    let requests = get_json(&client, &app.address, &owner.username, "/requests", &get_user_token(&app.config, &owner)).await;
+
+    delete_conversations(&app.db, &viewer.id).await;
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &viewer.id).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(message["data"]["kind"].as_str(), Some("story_reply"));
+    assert_eq!(message["data"]["storyId"].as_str(), Some(Uuid::from_slice(&story.id).unwrap().to_string().as_str()));
+    assert_eq!(replies_count, 1);
+    assert_eq!(requests["data"][0]["lastMessage"]["text"].as_str(), Some("Nice!"));
+}
+
+// ---- END OF STORY REPLIES UNIT TESTS ----
@@ -0,0 +1,184 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+use insta::explore::explore_service::refresh_explore_entries;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_follows, delete_post, delete_user, follow_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+async fn like_post(db: &DatabaseConnection, user_id: &[u8], post_id: &[u8]) {
+    entity::post_likes::ActiveModel {
+        user_id: Set(user_id.to_vec()),
+        post_id: Set(post_id.to_vec()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to like post");
+}
+
+// The ranking is shared with every other test, so the whole explore page is read
+// and only the posts created by the test are kept, in the order they came
+async fn get_explore_post_ids(client: &Client, address: &str, token: &str, post_ids: &[&[u8]]) -> (StatusCode, Vec<String>) {
+    let post_ids: Vec<String> = post_ids.iter().map(|id| Uuid::from_slice(id).unwrap().to_string()).collect();
+    let mut found = vec![];
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut url = format!("{}/api/v1/explore?limit=50", address);
+        if let Some(cursor) = &cursor {
+            url.push_str(&format!("&cursor={}", cursor));
+        }
+
+        let resp = client.get(url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap();
+
+        if resp.status() != StatusCode::OK {
+            return (resp.status(), found);
+        }
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+        for post in response_body["data"].as_array().unwrap() {
+            let id = post["id"].as_str().unwrap().to_string();
+            if post_ids.contains(&id) {
+                found.push(id);
+            }
+        }
+
+        match response_body["nextCursor"].as_str() {
+            Some(next_cursor) => cursor = Some(next_cursor.to_string()),
+            None => return (StatusCode::OK, found),
+        }
+    }
+}
+
+// ---- EXPLORE UNIT TESTS ----
+
+#[actix_web::test]
+async fn explore_should_rank_posts_by_engagement() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (first_author, _p) = create_random_user(&app.db).await;
+    let (second_author, _p) = create_random_user(&app.db).await;
+    let (first_liker, _p) = create_random_user(&app.db).await;
+    let (second_liker, _p) = create_random_user(&app.db).await;
+
+    let popular_post = create_random_post(&app.db, &first_author.id).await;
+    let liked_post = create_random_post(&app.db, &second_author.id).await;
+    let ignored_post = create_random_post(&app.db, &second_author.id).await;
+    like_post(&app.db, &first_liker.id, &popular_post.id).await;
+    like_post(&app.db, &second_liker.id, &popular_post.id).await;
+    like_post(&app.db, &first_liker.id, &liked_post.id).await;
+    // The author's own like doesn't count
+    like_post(&app.db, &second_author.id, &ignored_post.id).await;
+
+    let refreshed = refresh_explore_entries(&app.db).await;
+    let (status, found) = get_explore_post_ids(
+        &client,
+        &app.address,
+        &get_user_token(&app.config, &viewer),
+        &[&popular_post.id, &liked_post.id, &ignored_post.id],
+    ).await;
+
+    delete_post(&app.db, &popular_post.id).await;
+    delete_post(&app.db, &liked_post.id).await;
+    delete_post(&app.db, &ignored_post.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &first_author.id).await;
+    delete_user(&app.db, &second_author.id).await;
+    delete_user(&app.db, &first_liker.id).await;
+    delete_user(&app.db, &second_liker.id).await;
+
+    assert!(refreshed.is_ok());
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found, vec![
+        Uuid::from_slice(&popular_post.id).unwrap().to_string(),
+        Uuid::from_slice(&liked_post.id).unwrap().to_string(),
+    ]);
+}
+
+#[actix_web::test]
+async fn explore_should_exclude_followed_blocked_muted_and_private_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+    let (muted, _p) = create_random_user(&app.db).await;
+    let (private_author, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    make_user_private(&app.db, &private_author).await;
+
+    let token = get_user_token(&app.config, &viewer);
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &viewer.username, &blocked.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    client.post(format!("{}/api/v1/users/{}/mutes/{}", &app.address, &viewer.username, &muted.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let mut posts = vec![];
+    for author in [&stranger, &followee, &blocked, &muted, &private_author, &viewer] {
+        let post = create_random_post(&app.db, &author.id).await;
+        like_post(&app.db, &liker.id, &post.id).await;
+        posts.push(post);
+    }
+
+    refresh_explore_entries(&app.db).await.unwrap();
+    let post_ids: Vec<&[u8]> = posts.iter().map(|post| post.id.as_slice()).collect();
+    let (status, found) = get_explore_post_ids(&client, &app.address, &token, &post_ids).await;
+
+    for post in &posts {
+        delete_post(&app.db, &post.id).await;
+    }
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    for user in [&viewer, &liker, &stranger, &followee, &blocked, &muted, &private_author] {
+        delete_user(&app.db, &user.id).await;
+    }
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found, vec![Uuid::from_slice(&posts[0].id).unwrap().to_string()]);
+}
+
+#[actix_web::test]
+async fn explore_should_limit_the_posts_of_each_author() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+
+    let mut posts = vec![];
+    for _ in 0..5 {
+        let post = create_random_post(&app.db, &author.id).await;
+        like_post(&app.db, &liker.id, &post.id).await;
+        posts.push(post);
+    }
+
+    refresh_explore_entries(&app.db).await.unwrap();
+    let post_ids: Vec<&[u8]> = posts.iter().map(|post| post.id.as_slice()).collect();
+    let (status, found) = get_explore_post_ids(&client, &app.address, &get_user_token(&app.config, &viewer), &post_ids).await;
+
+    for post in &posts {
+        delete_post(&app.db, &post.id).await;
+    }
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found.len(), 3);
+}
+
+// ---- END OF EXPLORE UNIT TESTS ----
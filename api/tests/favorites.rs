@@ -0,0 +1,256 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_post, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// ---- ADD FAVORITE UNIT TESTS ----
+
+#[actix_web::test]
+async fn addfavorite_should_be_idempotent() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    let mut statuses = vec![];
+    for _ in 0..2 {
+        let resp = client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let resp = client.get(url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CREATED]);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(response_body["data"][0]["firstFileUrl"].as_str(), Some("https://bit.ly/3REd7XG"));
+    assert_eq!(response_body["data"][0]["likesCount"].as_u64(), Some(0));
+}
+
+#[actix_web::test]
+async fn addfavorite_should_return_404_for_hidden_post() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &author).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &reader))
+        .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF ADD FAVORITE UNIT TESTS ----
+
+
+
+// ---- REMOVE FAVORITE UNIT TESTS ----
+
+#[actix_web::test]
+async fn removefavorite_should_be_idempotent() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+    let post_id = Uuid::from_slice(&post.id).unwrap();
+
+    client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "postId": post_id }))
+        .send()
+        .await
+        .unwrap();
+
+    let mut statuses = vec![];
+    for _ in 0..2 {
+        let resp = client.delete(format!("{}/{}", url, post_id))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let resp = client.get(url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::OK]);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert!(response_body["data"].as_array().unwrap().is_empty());
+}
+
+// ---- END OF REMOVE FAVORITE UNIT TESTS ----
+
+
+
+// ---- GET FAVORITES UNIT TESTS ----
+
+#[actix_web::test]
+async fn getfavorites_should_be_private() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (reader, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &reader.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn getfavorites_should_paginate_newest_first() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let posts = vec![
+        create_random_post(&app.db, &author.id).await,
+        create_random_post(&app.db, &author.id).await,
+        create_random_post(&app.db, &author.id).await,
+    ];
+
+    let url = format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    for post in &posts {
+        client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let first_page = client.get(format!("{}?limit=2", url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let first_page: serde_json::Value = parse_response_body(first_page).await;
+
+    let second_page = client.get(format!("{}?limit=2&cursor={}", url, first_page["nextCursor"].as_str().unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let second_page: serde_json::Value = parse_response_body(second_page).await;
+
+    for post in &posts {
+        delete_post(&app.db, &post.id).await;
+    }
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    let post_ids: Vec<String> = posts.iter().map(|post| Uuid::from_slice(&post.id).unwrap().to_string()).collect();
+    let first_ids: Vec<&str> = first_page["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+    let second_ids: Vec<&str> = second_page["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+
+    assert_eq!(first_ids, vec![post_ids[2].as_str(), post_ids[1].as_str()]);
+    assert_eq!(second_ids, vec![post_ids[0].as_str()]);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+#[actix_web::test]
+async fn getfavorites_should_drop_posts_of_blocking_author() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/favorites", &app.address, &reader.username);
+    let token = get_user_token(&app.config, &reader);
+
+    client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&json!({ "postId": Uuid::from_slice(&post.id).unwrap() }))
+        .send()
+        .await
+        .unwrap();
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &author.username, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .send()
+        .await
+        .unwrap();
+
+    // Unblocking does not bring the favorite back
+    client.delete(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &author.username, &reader.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_blocks_and_mutes(&app.db, &author.id).await;
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert!(response_body["data"].as_array().unwrap().is_empty());
+}
+
+// ---- END OF GET FAVORITES UNIT TESTS ----
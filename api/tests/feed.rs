@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+use insta::timeline::timeline_service::rebuild_timeline;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_follows, delete_post, delete_user, follow_user, get_user_token, parse_response_body};
+
+mod utils;
+
+// ---- FEED UNIT TESTS ----
+
+#[actix_web::test]
+async fn feed_should_only_return_followed_users_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+
+    let followee_post = create_random_post(&app.db, &followee.id).await;
+    let stranger_post = create_random_post(&app.db, &stranger.id).await;
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let resp = client.get(format!("{}/api/v1/users/{}/following/posts", &app.address, &viewer.username))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &followee_post.id).await;
+    delete_post(&app.db, &stranger_post.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let posts = response_body["data"].as_array().unwrap();
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["id"].as_str(), Some(Uuid::from_slice(&followee_post.id).unwrap().to_string().as_str()));
+    assert_eq!(posts[0]["user"]["username"].as_str(), Some(followee.username.as_str()));
+    assert!(posts[0]["user"]["hasStories"].is_boolean());
+    assert!(response_body["nextCursor"].is_null());
+}
+
+#[actix_web::test]
+async fn feed_should_paginate_without_repeating_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+
+    let mut posts = vec![];
+    for _ in 0..5 {
+        posts.push(create_random_post(&app.db, &followee.id).await);
+    }
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let token = get_user_token(&app.config, &viewer);
+    let mut seen = HashSet::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut url = format!("{}/api/v1/feed?limit=2", &app.address);
+        if let Some(cursor) = &cursor {
+            url.push_str(&format!("&cursor={}", cursor));
+        }
+
+        let resp = client.get(url)
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+
+        for post in response_body["data"].as_array().unwrap() {
+            assert!(seen.insert(post["id"].as_str().unwrap().to_owned()));
+        }
+
+        match response_body["nextCursor"].as_str() {
+            Some(next_cursor) => cursor = Some(next_cursor.to_owned()),
+            None => break,
+        }
+    }
+
+    for post in &posts {
+        delete_post(&app.db, &post.id).await;
+    }
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(seen.len(), posts.len());
+}
+
+#[actix_web::test]
+async fn feed_should_hide_muted_users_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    let post = create_random_post(&app.db, &followee.id).await;
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let token = get_user_token(&app.config, &viewer);
+
+    client.post(format!("{}/api/v1/users/{}/mutes/{}", &app.address, &viewer.username, &followee.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/feed", &app.address))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert!(response_body["data"].as_array().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn feed_should_reject_invalid_cursor() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/feed?cursor=invalid", &app.address))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &viewer.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ---- END OF FEED UNIT TESTS ----
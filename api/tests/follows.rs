@@ -0,0 +1,284 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use insta::error::HttpResponseError;
+use crate::utils::{create_random_user, delete_follows, delete_user, follow_user, get_user_token, make_user_private, parse_response_body, wait_for_notifications};
+
+mod utils;
+
+// ---- FOLLOW UNIT TESTS ----
+
+#[actix_web::test]
+async fn follow_should_not_allow_following_yourself() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/followers", &app.address, &created_user.username))
+        .bearer_auth(get_user_token(&app.config, &created_user))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    let error = response_body.errors.first().unwrap();
+    assert_eq!(error.error, Some("You cannot follow yourself".to_owned()));
+}
+
+#[actix_web::test]
+async fn follow_should_allow_many_followers() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (first_follower, _p) = create_random_user(&app.db).await;
+    let (second_follower, _p) = create_random_user(&app.db).await;
+
+    for follower in [&first_follower, &second_follower] {
+        let resp = client.post(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+            .bearer_auth(get_user_token(&app.config, follower))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let resp = client.get(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+
+    delete_follows(&app.db, &followee.id).await;
+    delete_user(&app.db, &first_follower.id).await;
+    delete_user(&app.db, &second_follower.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let followers = response_body["data"].as_array().unwrap();
+
+    assert_eq!(followers.len(), 2);
+    assert!(followers.iter().any(|f| f["username"].as_str() == Some(first_follower.username.as_str())));
+    assert!(followers.iter().any(|f| f["username"].as_str() == Some(second_follower.username.as_str())));
+}
+
+#[actix_web::test]
+async fn follow_should_not_allow_duplicate_follow() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &follower.id, &followee.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+        .bearer_auth(get_user_token(&app.config, &follower))
+        .send()
+        .await
+        .unwrap();
+
+    delete_follows(&app.db, &followee.id).await;
+    delete_user(&app.db, &follower.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    let error = response_body.errors.first().unwrap();
+    assert_eq!(error.error, Some("You already followed this user".to_owned()));
+}
+
+// ---- END OF FOLLOW UNIT TESTS ----
+
+// ---- UNFOLLOW UNIT TESTS ----
+
+#[actix_web::test]
+async fn unfollow_should_success() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &follower.id, &followee.id).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+        .bearer_auth(get_user_token(&app.config, &follower))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client.get(format!("{}/api/v1/users/{}/following", &app.address, &follower.username))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+
+    delete_follows(&app.db, &followee.id).await;
+    delete_user(&app.db, &follower.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert!(response_body["data"].as_array().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn unfollow_should_fail_when_not_following() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+        .bearer_auth(get_user_token(&app.config, &follower))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &follower.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ---- END OF UNFOLLOW UNIT TESTS ----
+
+// ---- PRIVATE ACCOUNT UNIT TESTS ----
+
+#[actix_web::test]
+async fn follow_private_account_should_create_request() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (private_user, _p) = create_random_user(&app.db).await;
+    let (requester, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &private_user).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/followers", &app.address, &private_user.username))
+        .bearer_auth(get_user_token(&app.config, &requester))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"]["status"].as_str(), Some("requested"));
+
+    let resp = client.get(format!("{}/api/v1/users/{}/follow-requests", &app.address, &private_user.username))
+        .bearer_auth(get_user_token(&app.config, &private_user))
+        .send()
+        .await
+        .unwrap();
+
+    delete_follows(&app.db, &private_user.id).await;
+    delete_user(&app.db, &requester.id).await;
+    delete_user(&app.db, &private_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let requests = response_body["data"].as_array().unwrap();
+
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["username"].as_str(), Some(requester.username.as_str()));
+}
+
+#[actix_web::test]
+async fn accept_follow_request_should_grant_access() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (private_user, _p) = create_random_user(&app.db).await;
+    let (requester, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &private_user).await;
+
+    let requester_token = get_user_token(&app.config, &requester);
+
+    client.post(format!("{}/api/v1/users/{}/followers", &app.address, &private_user.username))
+        .bearer_auth(&requester_token)
+        .send()
+        .await
+        .unwrap();
+
+    // Hidden before the request is accepted
+    let resp = client.get(format!("{}/api/v1/users/{}/followers", &app.address, &private_user.username))
+        .bearer_auth(&requester_token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client.post(format!("{}/api/v1/users/{}/follow-requests/{}", &app.address, &private_user.username, &requester.username))
+        .bearer_auth(get_user_token(&app.config, &private_user))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client.get(format!("{}/api/v1/users/{}/followers", &app.address, &private_user.username))
+        .bearer_auth(&requester_token)
+        .send()
+        .await
+        .unwrap();
+
+    let follow_notifications = wait_for_notifications(&app.db, &private_user.id, "follow", 1).await;
+
+    delete_follows(&app.db, &private_user.id).await;
+    delete_user(&app.db, &requester.id).await;
+    delete_user(&app.db, &private_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(follow_notifications, 1);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"].as_array().unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn decline_follow_request_should_not_follow() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (private_user, _p) = create_random_user(&app.db).await;
+    let (requester, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &private_user).await;
+
+    client.post(format!("{}/api/v1/users/{}/followers", &app.address, &private_user.username))
+        .bearer_auth(get_user_token(&app.config, &requester))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/follow-requests/{}", &app.address, &private_user.username, &requester.username))
+        .bearer_auth(get_user_token(&app.config, &private_user))
+        .send()
+        .await
+        .unwrap();
+
+    let follows_count = entity::follows::Entity::find()
+        .filter(entity::follows::Column::FolloweeId.eq(private_user.id.clone()))
+        .count(&app.db)
+        .await
+        .unwrap();
+
+    delete_follows(&app.db, &private_user.id).await;
+    delete_user(&app.db, &requester.id).await;
+    delete_user(&app.db, &private_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(follows_count, 0);
+}
+
+// ---- END OF PRIVATE ACCOUNT UNIT TESTS ----
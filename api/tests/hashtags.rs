@@ -0,0 +1,310 @@
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+use crate::utils::{create_random_user, delete_hashtag, delete_post, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+fn random_hashtag() -> String {
+    format!("tag{}", Uuid::new_v4().simple())
+}
+
+async fn create_post(client: &Client, address: &str, username: &str, token: &str, description: &str) -> Vec<u8> {
+    let resp = client.post(format!("{}/api/v1/users/{}/posts", address, username))
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "description": description,
+            "files": ["https://bit.ly/3REd7XG"]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let post_id = Uuid::parse_str(response_body["data"]["id"].as_str().unwrap()).unwrap();
+
+    post_id.into()
+}
+
+async fn get_posts_count(client: &Client, address: &str, hashtag: &str) -> Option<u64> {
+    let resp = client.get(format!("{}/api/v1/hashtags/{}", address, hashtag))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    response_body["data"]["postsCount"].as_u64()
+}
+
+// ---- HASHTAG EXTRACTION UNIT TESTS ----
+
+#[actix_web::test]
+async fn createpost_should_tag_post_with_description_hashtags() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+    let hashtag = random_hashtag();
+
+    let post_id = create_post(&client, &app.address, &author.username, &token, &format!("Sunset #{} #{}", hashtag.to_uppercase(), hashtag)).await;
+
+    let posts_count = get_posts_count(&client, &app.address, &hashtag).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &hashtag).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(posts_count, Some(1));
+}
+
+#[actix_web::test]
+async fn updatepost_should_retag_post() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+    let old_hashtag = random_hashtag();
+    let new_hashtag = random_hashtag();
+
+    let post_id = create_post(&client, &app.address, &author.username, &token, &format!("#{}", old_hashtag)).await;
+
+    client.patch(format!("{}/api/v1/users/{}/posts/{}", &app.address, &author.username, Uuid::from_slice(&post_id).unwrap()))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "description": format!("#{}", new_hashtag) }))
+        .send()
+        .await
+        .unwrap();
+
+    let old_posts_count = get_posts_count(&client, &app.address, &old_hashtag).await;
+    let new_posts_count = get_posts_count(&client, &app.address, &new_hashtag).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &old_hashtag).await;
+    delete_hashtag(&app.db, &new_hashtag).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(old_posts_count, Some(0));
+    assert_eq!(new_posts_count, Some(1));
+}
+
+#[actix_web::test]
+async fn createcomment_should_only_tag_post_for_author_comments() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let author_token = get_user_token(&app.config, &author);
+    let author_hashtag = random_hashtag();
+    let commenter_hashtag = random_hashtag();
+
+    let post_id = create_post(&client, &app.address, &author.username, &author_token, "no tags").await;
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post_id).unwrap());
+
+    client.post(url.clone())
+        .bearer_auth(&author_token)
+        .json(&serde_json::json!({ "comment": format!("#{}", author_hashtag) }))
+        .send()
+        .await
+        .unwrap();
+
+    client.post(url)
+        .bearer_auth(get_user_token(&app.config, &commenter))
+        .json(&serde_json::json!({ "comment": format!("#{}", commenter_hashtag) }))
+        .send()
+        .await
+        .unwrap();
+
+    let author_posts_count = get_posts_count(&client, &app.address, &author_hashtag).await;
+    let commenter_resp = client.get(format!("{}/api/v1/hashtags/{}", &app.address, &commenter_hashtag))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &author_hashtag).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+
+    assert_eq!(author_posts_count, Some(1));
+    assert_eq!(commenter_resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF HASHTAG EXTRACTION UNIT TESTS ----
+
+
+
+// ---- HASHTAG POSTS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getrecentposts_should_paginate_newest_first() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+    let hashtag = random_hashtag();
+
+    let mut post_ids = vec![];
+    for _ in 0..3 {
+        post_ids.push(create_post(&client, &app.address, &author.username, &token, &format!("#{}", hashtag)).await);
+    }
+
+    let url = format!("{}/api/v1/hashtags/{}/recent", &app.address, &hashtag);
+
+    let first_page = client.get(format!("{}?limit=2", url))
+        .send()
+        .await
+        .unwrap();
+    let first_page: serde_json::Value = parse_response_body(first_page).await;
+
+    let second_page = client.get(format!("{}?limit=2&cursor={}", url, first_page["nextCursor"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    let second_page: serde_json::Value = parse_response_body(second_page).await;
+
+    for post_id in &post_ids {
+        delete_post(&app.db, post_id).await;
+    }
+    delete_hashtag(&app.db, &hashtag).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(first_page["data"].as_array().unwrap().len(), 2);
+    assert_eq!(second_page["data"].as_array().unwrap().len(), 1);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+#[actix_web::test]
+async fn gettopposts_should_hide_private_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+    let hashtag = random_hashtag();
+
+    let post_id = create_post(&client, &app.address, &author.username, &token, &format!("#{}", hashtag)).await;
+    make_user_private(&app.db, &author).await;
+
+    let anonymous = client.get(format!("{}/api/v1/hashtags/{}/top", &app.address, &hashtag))
+        .send()
+        .await
+        .unwrap();
+    let anonymous: serde_json::Value = parse_response_body(anonymous).await;
+
+    let own = client.get(format!("{}/api/v1/hashtags/{}/top", &app.address, &hashtag))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let own: serde_json::Value = parse_response_body(own).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &hashtag).await;
+    delete_user(&app.db, &author.id).await;
+
+    assert!(anonymous["data"].as_array().unwrap().is_empty());
+    assert_eq!(own["data"].as_array().unwrap().len(), 1);
+}
+
+// ---- END OF HASHTAG POSTS UNIT TESTS ----
+
+
+
+// ---- FOLLOW HASHTAG UNIT TESTS ----
+
+#[actix_web::test]
+async fn followhashtag_should_bring_tagged_posts_into_feed() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let reader_token = get_user_token(&app.config, &reader);
+    let hashtag = random_hashtag();
+
+    let follow_url = format!("{}/api/v1/hashtags/{}/follow", &app.address, &hashtag);
+    let mut statuses = vec![];
+    for _ in 0..2 {
+        let resp = client.post(follow_url.clone())
+            .bearer_auth(&reader_token)
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let post_id = create_post(&client, &app.address, &author.username, &get_user_token(&app.config, &author), &format!("#{}", hashtag)).await;
+
+    let feed = client.get(format!("{}/api/v1/feed", &app.address))
+        .bearer_auth(&reader_token)
+        .send()
+        .await
+        .unwrap();
+    let feed: serde_json::Value = parse_response_body(feed).await;
+
+    let followed = client.get(format!("{}/api/v1/users/{}/hashtags", &app.address, &reader.username))
+        .bearer_auth(&reader_token)
+        .send()
+        .await
+        .unwrap();
+    let followed: serde_json::Value = parse_response_body(followed).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &hashtag).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::OK]);
+
+    let feed_ids: Vec<&str> = feed["data"].as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap()).collect();
+    assert_eq!(feed_ids, vec![Uuid::from_slice(&post_id).unwrap().to_string().as_str()]);
+
+    assert_eq!(followed["data"][0]["name"].as_str(), Some(hashtag.as_str()));
+    assert_eq!(followed["data"][0]["postsCount"].as_u64(), Some(1));
+}
+
+#[actix_web::test]
+async fn unfollowhashtag_should_remove_tagged_posts_from_feed() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (reader, _p) = create_random_user(&app.db).await;
+    let reader_token = get_user_token(&app.config, &reader);
+    let hashtag = random_hashtag();
+
+    let follow_url = format!("{}/api/v1/hashtags/{}/follow", &app.address, &hashtag);
+    client.post(follow_url.clone())
+        .bearer_auth(&reader_token)
+        .send()
+        .await
+        .unwrap();
+
+    let post_id = create_post(&client, &app.address, &author.username, &get_user_token(&app.config, &author), &format!("#{}", hashtag)).await;
+
+    client.delete(follow_url)
+        .bearer_auth(&reader_token)
+        .send()
+        .await
+        .unwrap();
+
+    let feed = client.get(format!("{}/api/v1/feed", &app.address))
+        .bearer_auth(&reader_token)
+        .send()
+        .await
+        .unwrap();
+    let feed: serde_json::Value = parse_response_body(feed).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_hashtag(&app.db, &hashtag).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &reader.id).await;
+
+    assert!(feed["data"].as_array().unwrap().is_empty());
+}
+
+// ---- END OF FOLLOW HASHTAG UNIT TESTS ----
@@ -0,0 +1,385 @@
+use chrono::{Duration, Utc};
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, Set};
+use uuid::Uuid;
+use insta::configuration::StorageSettings;
+use insta::stories::stories_service::{delete_expired_stories, STORY_LIFETIME_HOURS};
+use insta::utils::storage::MediaStorage;
+use crate::utils::{create_random_user, create_story, create_upload, delete_highlights, delete_stories, delete_user, get_user_token, parse_response_body, record_upload};
+
+mod utils;
+
+fn story_ids(stories: &[&entity::stories::Model]) -> Vec<String> {
+    stories.iter().map(|story| Uuid::from_slice(&story.id).unwrap().to_string()).collect()
+}
+
+// ---- CREATE HIGHLIGHT UNIT TESTS ----
+
+#[actix_web::test]
+async fn createhighlight_should_pin_expired_stories() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let older = create_story(&app.db, &user.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 2)).await;
+    let newer = create_story(&app.db, &user.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1)).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "Summer", "storyIds": story_ids(&[&newer, &older]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_body["data"]["title"], "Summer");
+    assert_eq!(response_body["data"]["coverUrl"], older.file_url.as_str());
+    assert_eq!(response_body["data"]["items"].as_array().map(|items| items.len()), Some(2));
+    assert_eq!(response_body["data"]["items"][0]["storyId"], Uuid::from_slice(&older.id).unwrap().to_string());
+}
+
+#[actix_web::test]
+async fn createhighlight_should_reject_stories_of_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &other.id, Utc::now()).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "Not mine", "storyIds": story_ids(&[&story]) }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &other.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF CREATE HIGHLIGHT UNIT TESTS ----
+
+
+
+// ---- UPDATE HIGHLIGHT UNIT TESTS ----
+
+#[actix_web::test]
+async fn updatehighlight_should_only_accept_own_covers() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &user.id, Utc::now()).await;
+    let own_cover = create_upload(&app.db, &user.id).await;
+    let other_cover = create_upload(&app.db, &other.id).await;
+
+    let token = get_user_token(&app.config, &user);
+
+    let resp = client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "title": "Covers", "storyIds": story_ids(&[&story]) }))
+        .send()
+        .await
+        .unwrap();
+    let highlight: serde_json::Value = parse_response_body(resp).await;
+    let highlight_id = highlight["data"]["id"].as_str().unwrap().to_owned();
+
+    let mut statuses = vec![];
+    for cover_url in [other_cover.as_str(), "https://bit.ly/3REd7XG", own_cover.as_str()] {
+        let resp = client.patch(format!("{}/api/v1/users/{}/highlights/{}", &app.address, &user.username, highlight_id))
+            .bearer_auth(&token)
+            .json(&serde_json::json!({ "coverUrl": cover_url }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    // The story of the highlight uses the bit.ly file
+    assert_eq!(statuses, vec![StatusCode::BAD_REQUEST, StatusCode::OK, StatusCode::OK]);
+}
+
+// ---- END OF UPDATE HIGHLIGHT UNIT TESTS ----
+
+
+
+// ---- REORDER HIGHLIGHTS UNIT TESTS ----
+
+#[actix_web::test]
+async fn reorderhighlights_should_change_profile_order() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &user.id, Utc::now()).await;
+    let token = get_user_token(&app.config, &user);
+    let url = format!("{}/api/v1/users/{}/highlights", &app.address, &user.username);
+
+    let mut highlight_ids = vec![];
+    for title in ["First", "Second"] {
+        let resp = client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&serde_json::json!({ "title": title, "storyIds": story_ids(&[&story]) }))
+            .send()
+            .await
+            .unwrap();
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+        highlight_ids.push(response_body["data"]["id"].as_str().unwrap_or_default().to_string());
+    }
+
+    highlight_ids.reverse();
+
+    let reorder_resp = client.put(format!("{}/order", url))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "highlightIds": highlight_ids }))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(url.clone())
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(reorder_resp.status(), StatusCode::OK);
+    assert_eq!(response_body["data"][0]["title"], "Second");
+    assert_eq!(response_body["data"][1]["title"], "First");
+    assert_eq!(response_body["data"][0]["itemsCount"], 1);
+}
+
+#[actix_web::test]
+async fn reorderhighlights_should_reject_incomplete_list() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &user.id, Utc::now()).await;
+    let token = get_user_token(&app.config, &user);
+    let url = format!("{}/api/v1/users/{}/highlights", &app.address, &user.username);
+
+    for title in ["First", "Second"] {
+        client.post(url.clone())
+            .bearer_auth(&token)
+            .json(&serde_json::json!({ "title": title, "storyIds": story_ids(&[&story]) }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let resp = client.put(format!("{}/order", url))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "highlightIds": [Uuid::new_v4().to_string()] }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn reorderhighlightitems_should_change_item_order() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let first = create_story(&app.db, &user.id, Utc::now() - Duration::hours(1)).await;
+    let second = create_story(&app.db, &user.id, Utc::now()).await;
+    let token = get_user_token(&app.config, &user);
+    let url = format!("{}/api/v1/users/{}/highlights", &app.address, &user.username);
+
+    let resp = client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "title": "Trip", "storyIds": story_ids(&[&first, &second]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let created: serde_json::Value = parse_response_body(resp).await;
+    let highlight_id = created["data"]["id"].as_str().unwrap_or_default();
+    let item_ids: Vec<i64> = created["data"]["items"].as_array().unwrap().iter().rev().filter_map(|item| item["id"].as_i64()).collect();
+
+    let reorder_resp = client.put(format!("{}/{}/items/order", url, highlight_id))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "itemIds": item_ids }))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/{}", url, highlight_id))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(reorder_resp.status(), StatusCode::OK);
+    assert_eq!(response_body["data"]["items"][0]["storyId"], Uuid::from_slice(&second.id).unwrap().to_string());
+}
+
+// ---- END OF REORDER HIGHLIGHTS UNIT TESTS ----
+
+
+
+// ---- DELETE HIGHLIGHT UNIT TESTS ----
+
+#[actix_web::test]
+async fn deletehighlight_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &user.id, Utc::now()).await;
+    let url = format!("{}/api/v1/users/{}/highlights", &app.address, &user.username);
+
+    let resp = client.post(url.clone())
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "Mine", "storyIds": story_ids(&[&story]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let created: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.delete(format!("{}/{}", url, created["data"]["id"].as_str().unwrap_or_default()))
+        .bearer_auth(get_user_token(&app.config, &other))
+        .send()
+        .await
+        .unwrap();
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF DELETE HIGHLIGHT UNIT TESTS ----
+
+
+
+// ---- STORY SWEEPER UNIT TESTS ----
+
+#[actix_web::test]
+async fn sweeper_should_keep_highlighted_media() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let media_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&media_dir).unwrap();
+    std::fs::write(media_dir.join("highlighted.jpg"), b"highlighted").unwrap();
+    std::fs::write(media_dir.join("forgotten.jpg"), b"forgotten").unwrap();
+
+    let storage = MediaStorage::new(Some(StorageSettings {
+        base_url: String::from("https://cdn.example.com/media"),
+        path: media_dir.to_string_lossy().to_string(),
+    }));
+
+    let (user, _p) = create_random_user(&app.db).await;
+    record_upload(&app.db, &user.id, "https://cdn.example.com/media/highlighted.jpg").await;
+    record_upload(&app.db, &user.id, "https://cdn.example.com/media/forgotten.jpg").await;
+    let expired_at = Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1);
+
+    let mut highlighted: entity::stories::ActiveModel = create_story(&app.db, &user.id, expired_at).await.into();
+    highlighted.file_url = Set(String::from("https://cdn.example.com/media/highlighted.jpg"));
+    let highlighted = highlighted.update(&app.db).await.unwrap();
+
+    let mut forgotten: entity::stories::ActiveModel = create_story(&app.db, &user.id, expired_at).await.into();
+    forgotten.file_url = Set(String::from("https://cdn.example.com/media/forgotten.jpg"));
+    forgotten.update(&app.db).await.unwrap();
+
+    client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "Keep", "storyIds": story_ids(&[&highlighted]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let result = delete_expired_stories(&app.db, &storage).await;
+
+    let highlighted_exists = media_dir.join("highlighted.jpg").exists();
+    let forgotten_exists = media_dir.join("forgotten.jpg").exists();
+
+    std::fs::remove_dir_all(&media_dir).unwrap();
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert!(result.is_ok());
+    assert!(highlighted_exists);
+    assert!(!forgotten_exists);
+}
+
+#[actix_web::test]
+async fn sweeper_should_keep_highlighted_stories_pinnable() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let expired_at = Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1);
+    let story = create_story(&app.db, &user.id, expired_at).await;
+
+    client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "First", "storyIds": story_ids(&[&story]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let result = delete_expired_stories(&app.db, &MediaStorage::default()).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/highlights", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "title": "Second", "storyIds": story_ids(&[&story]) }))
+        .send()
+        .await
+        .unwrap();
+
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_highlights(&app.db, &user.id).await;
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert!(result.is_ok());
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_body["data"]["items"][0]["storyId"], Uuid::from_slice(&story.id).unwrap().to_string());
+}
+
+// ---- END OF STORY SWEEPER UNIT TESTS ----
@@ -0,0 +1,143 @@
+use std::time::Duration;
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Set};
+use uuid::Uuid;
+use insta::insights::insights_service::aggregate_impressions;
+use crate::utils::{create_random_post, create_random_user, delete_follows, delete_post, delete_user, follow_user, get_user_token, parse_response_body};
+
+mod utils;
+
+async fn view_post(client: &Client, address: &str, author: &entity::users::Model, post: &entity::posts::Model, token: &str) {
+    client.get(format!("{}/api/v1/users/{}/posts/{}", address, author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+}
+
+// Impressions are logged in the background, waits until the expected number is there
+async fn wait_for_impressions(db: &DatabaseConnection, post_id: &[u8], count: u64) {
+    for _ in 0..100 {
+        let logged = entity::post_impressions::Entity::find()
+            .filter(entity::post_impressions::Column::PostId.eq(post_id.to_vec()))
+            .count(db)
+            .await
+            .unwrap();
+
+        if logged >= count {
+            return;
+        }
+
+        actix_web::rt::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+// ---- INSIGHTS UNIT TESTS ----
+
+#[actix_web::test]
+async fn insights_should_count_impressions_and_unique_reach() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (first_viewer, _p) = create_random_user(&app.db).await;
+    let (second_viewer, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let author_token = get_user_token(&app.config, &author);
+    let first_token = get_user_token(&app.config, &first_viewer);
+    view_post(&client, &app.address, &author, &post, &first_token).await;
+    view_post(&client, &app.address, &author, &post, &first_token).await;
+    view_post(&client, &app.address, &author, &post, &get_user_token(&app.config, &second_viewer)).await;
+    // The author's own views don't count
+    view_post(&client, &app.address, &author, &post, &author_token).await;
+
+    wait_for_impressions(&app.db, &post.id, 4).await;
+    let aggregated = aggregate_impressions(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}/insights", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(&author_token)
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &first_viewer.id).await;
+    delete_user(&app.db, &second_viewer.id).await;
+
+    assert!(aggregated.is_ok());
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_body["data"]["impressions"].as_u64(), Some(3));
+    assert_eq!(response_body["data"]["reach"].as_u64(), Some(2));
+
+    let hourly = response_body["data"]["hourly"].as_array().unwrap();
+    assert_eq!(hourly.len(), 1);
+    assert_eq!(hourly[0]["impressions"].as_u64(), Some(3));
+    assert_eq!(hourly[0]["reach"].as_u64(), Some(2));
+}
+
+#[actix_web::test]
+async fn insights_should_summarize_the_account() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    follow_user(&app.db, &follower.id, &author.id).await;
+
+    entity::post_likes::ActiveModel {
+        user_id: Set(follower.id.clone()),
+        post_id: Set(post.id.clone()),
+        ..Default::default()
+    }.insert(&app.db).await.expect("Failed to like post");
+
+    let resp = client.get(format!("{}/api/v1/users/{}/insights?days=30", &app.address, &author.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_follows(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &follower.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_body["data"]["days"].as_i64(), Some(30));
+    assert_eq!(response_body["data"]["likes"].as_u64(), Some(1));
+    assert_eq!(response_body["data"]["comments"].as_u64(), Some(0));
+    assert_eq!(response_body["data"]["saves"].as_u64(), Some(0));
+    assert_eq!(response_body["data"]["followersCount"].as_u64(), Some(1));
+    assert_eq!(response_body["data"]["followerGrowth"].as_u64(), Some(1));
+}
+
+#[actix_web::test]
+async fn insights_should_only_be_visible_to_the_author() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}/insights", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+// ---- END OF INSIGHTS UNIT TESTS ----
@@ -0,0 +1,90 @@
+use reqwest::{Client, StatusCode};
+use crate::utils::{create_random_user, delete_stories, delete_user, get_user_token, parse_response_body, TEST_MEDIA_BASE_URL};
+
+mod utils;
+
+// ---- UPLOAD MEDIA UNIT TESTS ----
+
+#[actix_web::test]
+async fn uploadmedia_should_store_file_usable_in_stories() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &user);
+
+    let resp = client.post(format!("{}/api/v1/users/{}/media", &app.address, &user.username))
+        .bearer_auth(&token)
+        .header("Content-Type", "image/jpeg")
+        .body(b"jpeg".to_vec())
+        .send()
+        .await
+        .unwrap();
+
+    let upload_status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let file_url = response_body["data"]["fileUrl"].as_str().unwrap_or_default().to_owned();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/stories", &app.address, &user.username))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "fileUrl": file_url }))
+        .send()
+        .await
+        .unwrap();
+
+    let path = std::env::temp_dir().join("insta-test-media").join(file_url.trim_start_matches(TEST_MEDIA_BASE_URL).trim_start_matches('/'));
+    let stored = std::fs::read(&path).ok();
+    std::fs::remove_file(&path).ok();
+
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(upload_status, StatusCode::CREATED);
+    assert!(file_url.starts_with(TEST_MEDIA_BASE_URL));
+    assert_eq!(stored, Some(b"jpeg".to_vec()));
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+#[actix_web::test]
+async fn uploadmedia_should_reject_unsupported_files() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/media", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .header("Content-Type", "text/html")
+        .body("<script></script>")
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn uploadmedia_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/media", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &other))
+        .header("Content-Type", "image/png")
+        .body(b"png".to_vec())
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF UPLOAD MEDIA UNIT TESTS ----
@@ -0,0 +1,225 @@
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_notifications, delete_post, delete_user, get_user_token, make_user_private, parse_response_body, wait_for_notifications};
+
+mod utils;
+
+async fn create_post(client: &Client, address: &str, username: &str, token: &str, description: &str) -> Vec<u8> {
+    let resp = client.post(format!("{}/api/v1/users/{}/posts", address, username))
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "description": description,
+            "files": ["https://bit.ly/3REd7XG"]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let post_id = Uuid::parse_str(response_body["data"]["id"].as_str().unwrap()).unwrap();
+
+    post_id.into()
+}
+
+async fn get_mentioned_post_ids(client: &Client, address: &str, username: &str) -> Vec<String> {
+    let resp = client.get(format!("{}/api/v1/users/{}/mentions", address, username))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    response_body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| post["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+// ---- POST MENTIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn createpost_should_notify_mentioned_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (mentioned, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+
+    let description = format!("With @{} and @{} and @nobody{}", mentioned.username.to_uppercase(), author.username, Uuid::new_v4().simple());
+    let post_id = create_post(&client, &app.address, &author.username, &token, &description).await;
+
+    let notifications_count = wait_for_notifications(&app.db, &mentioned.id, "mention", 1).await;
+    let own_notifications_count = wait_for_notifications(&app.db, &author.id, "mention", 0).await;
+    let mentioned_post_ids = get_mentioned_post_ids(&client, &app.address, &mentioned.username).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_notifications(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &mentioned.id).await;
+
+    assert_eq!(notifications_count, 1);
+    assert_eq!(own_notifications_count, 0);
+    assert_eq!(mentioned_post_ids, vec![Uuid::from_slice(&post_id).unwrap().to_string()]);
+}
+
+#[actix_web::test]
+async fn updatepost_should_only_notify_new_mentions() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (first, _p) = create_random_user(&app.db).await;
+    let (second, _p) = create_random_user(&app.db).await;
+    let token = get_user_token(&app.config, &author);
+
+    let post_id = create_post(&client, &app.address, &author.username, &token, &format!("Hi @{}", first.username)).await;
+
+    client.patch(format!("{}/api/v1/users/{}/posts/{}", &app.address, &author.username, Uuid::from_slice(&post_id).unwrap()))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "description": format!("Hi @{}", second.username) }))
+        .send()
+        .await
+        .unwrap();
+
+    client.patch(format!("{}/api/v1/users/{}/posts/{}", &app.address, &author.username, Uuid::from_slice(&post_id).unwrap()))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "description": format!("Hello @{}", second.username) }))
+        .send()
+        .await
+        .unwrap();
+
+    // Events are handled in order, the first mention is withdrawn before the second is notified
+    let second_notifications_count = wait_for_notifications(&app.db, &second.id, "mention", 1).await;
+    let first_notifications_count = wait_for_notifications(&app.db, &first.id, "mention", 0).await;
+    let first_post_ids = get_mentioned_post_ids(&client, &app.address, &first.username).await;
+
+    delete_post(&app.db, &post_id).await;
+    delete_notifications(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &first.id).await;
+    delete_user(&app.db, &second.id).await;
+
+    assert_eq!(first_notifications_count, 0);
+    assert_eq!(second_notifications_count, 1);
+    assert!(first_post_ids.is_empty());
+}
+
+// ---- END OF POST MENTIONS UNIT TESTS ----
+
+
+
+// ---- COMMENT MENTIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn createcomment_should_ignore_blocked_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let (blocker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &blocker.username, &commenter.username))
+        .bearer_auth(get_user_token(&app.config, &blocker))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &commenter))
+        .json(&serde_json::json!({ "comment": format!("@{} @{}", author.username, blocker.username) }))
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+
+    let author_notifications_count = wait_for_notifications(&app.db, &author.id, "mention", 1).await;
+    let blocker_notifications_count = wait_for_notifications(&app.db, &blocker.id, "mention", 0).await;
+    let blocker_post_ids = get_mentioned_post_ids(&client, &app.address, &blocker.username).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_blocks_and_mutes(&app.db, &blocker.id).await;
+    delete_notifications(&app.db, &commenter.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+    delete_user(&app.db, &blocker.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(author_notifications_count, 1);
+    assert_eq!(blocker_notifications_count, 0);
+    assert!(blocker_post_ids.is_empty());
+}
+
+#[actix_web::test]
+async fn deletecomment_should_remove_its_mentions() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (mentioned, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let token = get_user_token(&app.config, &author);
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let resp = client.post(url.clone())
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "comment": format!("cc @{}", mentioned.username) }))
+        .send()
+        .await
+        .unwrap();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    wait_for_notifications(&app.db, &mentioned.id, "mention", 1).await;
+
+    client.delete(format!("{}/{}", url, response_body["data"]["id"].as_i64().unwrap()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let notifications_count = wait_for_notifications(&app.db, &mentioned.id, "mention", 0).await;
+    let mentioned_post_ids = get_mentioned_post_ids(&client, &app.address, &mentioned.username).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &mentioned.id).await;
+
+    assert_eq!(notifications_count, 0);
+    assert!(mentioned_post_ids.is_empty());
+}
+
+// ---- END OF COMMENT MENTIONS UNIT TESTS ----
+
+
+
+// ---- GET MENTIONED POSTS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getmentionedposts_should_follow_user_privacy() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (mentioned, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &mentioned).await;
+
+    let anonymous = client.get(format!("{}/api/v1/users/{}/mentions", &app.address, &mentioned.username))
+        .send()
+        .await
+        .unwrap();
+
+    let own = client.get(format!("{}/api/v1/users/{}/mentions", &app.address, &mentioned.username))
+        .bearer_auth(get_user_token(&app.config, &mentioned))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &mentioned.id).await;
+
+    assert_eq!(anonymous.status(), StatusCode::FORBIDDEN);
+    assert_eq!(own.status(), StatusCode::OK);
+}
+
+// ---- END OF GET MENTIONED POSTS UNIT TESTS ----
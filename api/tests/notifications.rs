@@ -0,0 +1,178 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_follows, delete_post, delete_user, get_user_token, parse_response_body, wait_for_notifications};
+
+mod utils;
+
+async fn like_post(client: &Client, address: &str, author: &str, post_id: &[u8], token: &str) {
+    let resp = client.post(format!("{}/api/v1/users/{}/posts/{}/likes", address, author, Uuid::from_slice(post_id).unwrap()))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+async fn get_notifications(client: &Client, address: &str, username: &str, token: &str) -> serde_json::Value {
+    let resp = client.get(format!("{}/api/v1/users/{}/notifications", address, username))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    parse_response_body(resp).await
+}
+
+async fn get_unread_count(client: &Client, address: &str, username: &str, token: &str) -> Option<u64> {
+    let resp = client.get(format!("{}/api/v1/users/{}/notifications/unread-count", address, username))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    response_body["data"]["unreadCount"].as_u64()
+}
+
+// ---- GET NOTIFICATIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getnotifications_should_group_likes_of_the_same_post() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (first_liker, _p) = create_random_user(&app.db).await;
+    let (second_liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let token = get_user_token(&app.config, &author);
+
+    like_post(&client, &app.address, &author.username, &post.id, &get_user_token(&app.config, &first_liker)).await;
+    like_post(&client, &app.address, &author.username, &post.id, &get_user_token(&app.config, &second_liker)).await;
+
+    let likes_count = wait_for_notifications(&app.db, &author.id, "like", 2).await;
+    let response_body = get_notifications(&client, &app.address, &author.username, &token).await;
+    let unread_count = get_unread_count(&client, &app.address, &author.username, &token).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &first_liker.id).await;
+    delete_user(&app.db, &second_liker.id).await;
+
+    assert_eq!(likes_count, 2);
+
+    let notifications = response_body["data"].as_array().unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["kind"].as_str(), Some("like"));
+    assert_eq!(notifications[0]["actor"]["username"].as_str(), Some(second_liker.username.as_str()));
+    assert_eq!(notifications[0]["othersCount"].as_u64(), Some(1));
+    assert_eq!(notifications[0]["postId"].as_str(), Some(Uuid::from_slice(&post.id).unwrap().to_string().as_str()));
+    assert_eq!(notifications[0]["read"].as_bool(), Some(false));
+    assert_eq!(unread_count, Some(1));
+}
+
+#[actix_web::test]
+async fn getnotifications_should_withdraw_undone_likes() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let liker_token = get_user_token(&app.config, &liker);
+
+    like_post(&client, &app.address, &author.username, &post.id, &liker_token).await;
+    wait_for_notifications(&app.db, &author.id, "like", 1).await;
+
+    client.delete(format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(&liker_token)
+        .send()
+        .await
+        .unwrap();
+
+    let likes_count = wait_for_notifications(&app.db, &author.id, "like", 0).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(likes_count, 0);
+}
+
+#[actix_web::test]
+async fn getnotifications_should_only_be_visible_to_the_owner() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/notifications", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF GET NOTIFICATIONS UNIT TESTS ----
+
+
+
+// ---- MARK NOTIFICATIONS READ UNIT TESTS ----
+
+#[actix_web::test]
+async fn marknotificationsread_should_read_selected_groups() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    let token = get_user_token(&app.config, &author);
+    let follower_token = get_user_token(&app.config, &follower);
+
+    client.post(format!("{}/api/v1/users/{}/followers", &app.address, &author.username))
+        .bearer_auth(&follower_token)
+        .send()
+        .await
+        .unwrap();
+    like_post(&client, &app.address, &author.username, &post.id, &follower_token).await;
+
+    wait_for_notifications(&app.db, &author.id, "follow", 1).await;
+    wait_for_notifications(&app.db, &author.id, "like", 1).await;
+
+    let before = get_notifications(&client, &app.address, &author.username, &token).await;
+    let like_id = before["data"][0]["id"].as_i64().unwrap();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/notifications/read", &app.address, &author.username))
+        .bearer_auth(&token)
+        .json(&json!({ "ids": [like_id] }))
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+
+    let after = get_notifications(&client, &app.address, &author.username, &token).await;
+    let unread_count = get_unread_count(&client, &app.address, &author.username, &token).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_follows(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &follower.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(before["data"][0]["kind"].as_str(), Some("like"));
+    assert_eq!(after["data"][0]["read"].as_bool(), Some(true));
+    assert_eq!(after["data"][1]["kind"].as_str(), Some("follow"));
+    assert_eq!(after["data"][1]["read"].as_bool(), Some(false));
+    assert_eq!(unread_count, Some(1));
+}
+
+// ---- END OF MARK NOTIFICATIONS READ UNIT TESTS ----
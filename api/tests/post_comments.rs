@@ -0,0 +1,208 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, Set};
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_post, delete_user, get_user_token, parse_response_body};
+
+mod utils;
+
+async fn post_comment(client: &Client, url: &str, token: &str, body: serde_json::Value) -> reqwest::Response {
+    client.post(url)
+        .bearer_auth(token)
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+}
+
+// ---- CREATE COMMENT UNIT TESTS ----
+
+#[actix_web::test]
+async fn createcomment_should_thread_replies_under_top_level_comment() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let token = get_user_token(&app.config, &commenter);
+
+    let resp = post_comment(&client, &url, &token, serde_json::json!({ "comment": "first" })).await;
+    let top_level: serde_json::Value = parse_response_body(resp).await;
+    let top_level_id = top_level["data"]["id"].as_i64().unwrap();
+
+    let resp = post_comment(&client, &url, &token, serde_json::json!({ "comment": "reply", "parentCommentId": top_level_id })).await;
+    let reply: serde_json::Value = parse_response_body(resp).await;
+    let reply_id = reply["data"]["id"].as_i64().unwrap();
+
+    let resp = post_comment(&client, &url, &token, serde_json::json!({ "comment": "nested reply", "parentCommentId": reply_id })).await;
+    let nested_reply: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.get(url.clone())
+        .send()
+        .await
+        .unwrap();
+    let comments: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.get(format!("{}/{}/replies", url, top_level_id))
+        .send()
+        .await
+        .unwrap();
+    let replies: serde_json::Value = parse_response_body(resp).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+
+    assert_eq!(reply["data"]["parentId"].as_i64(), Some(top_level_id));
+    assert_eq!(nested_reply["data"]["parentId"].as_i64(), Some(top_level_id));
+
+    let comments = comments["data"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["repliesCount"].as_u64(), Some(2));
+
+    assert_eq!(replies["data"].as_array().unwrap().len(), 2);
+}
+
+#[actix_web::test]
+async fn createcomment_should_fail_when_comments_are_off() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let mut active_post: entity::posts::ActiveModel = post.clone().into();
+    active_post.comments_enabled = Set(false);
+    active_post.update(&app.db).await.unwrap();
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let resp = post_comment(&client, &url, &get_user_token(&app.config, &commenter), serde_json::json!({ "comment": "hello" })).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF CREATE COMMENT UNIT TESTS ----
+
+
+
+// ---- GET COMMENTS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getcomments_should_paginate() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let token = get_user_token(&app.config, &author);
+
+    for comment in ["first", "second", "third"] {
+        post_comment(&client, &url, &token, serde_json::json!({ "comment": comment })).await;
+    }
+
+    let first_page = client.get(format!("{}?limit=2", url))
+        .send()
+        .await
+        .unwrap();
+    let first_page: serde_json::Value = parse_response_body(first_page).await;
+
+    let second_page = client.get(format!("{}?limit=2&cursor={}", url, first_page["nextCursor"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    let second_page: serde_json::Value = parse_response_body(second_page).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+
+    let first_comments: Vec<&str> = first_page["data"].as_array().unwrap().iter().map(|c| c["comment"].as_str().unwrap()).collect();
+    let second_comments: Vec<&str> = second_page["data"].as_array().unwrap().iter().map(|c| c["comment"].as_str().unwrap()).collect();
+
+    assert_eq!(first_comments, vec!["first", "second"]);
+    assert_eq!(second_comments, vec!["third"]);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+// ---- END OF GET COMMENTS UNIT TESTS ----
+
+
+
+// ---- DELETE COMMENT UNIT TESTS ----
+
+#[actix_web::test]
+async fn deletecomment_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+
+    let resp = post_comment(&client, &url, &get_user_token(&app.config, &commenter), serde_json::json!({ "comment": "hello" })).await;
+    let comment: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.delete(format!("{}/{}", url, comment["data"]["id"].as_i64().unwrap()))
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn deletecomment_by_post_owner_should_remove_replies() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (commenter, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/comments", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let token = get_user_token(&app.config, &commenter);
+
+    let resp = post_comment(&client, &url, &token, serde_json::json!({ "comment": "hello" })).await;
+    let comment: serde_json::Value = parse_response_body(resp).await;
+    let comment_id = comment["data"]["id"].as_i64().unwrap();
+
+    post_comment(&client, &url, &token, serde_json::json!({ "comment": "reply", "parentCommentId": comment_id })).await;
+
+    let resp = client.delete(format!("{}/{}", url, comment_id))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .send()
+        .await
+        .unwrap();
+
+    let post_resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .send()
+        .await
+        .unwrap();
+    let post_body: serde_json::Value = parse_response_body(post_resp).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &commenter.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(post_body["data"]["commentsCount"].as_u64(), Some(0));
+}
+
+// ---- END OF DELETE COMMENT UNIT TESTS ----
@@ -0,0 +1,220 @@
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_follows, delete_notifications, delete_post, delete_user, follow_user, get_user_token, parse_response_body, wait_for_notifications};
+
+mod utils;
+
+fn tags_url(address: &str, username: &str, post_id: &[u8]) -> String {
+    format!("{}/api/v1/users/{}/posts/{}/tags", address, username, Uuid::from_slice(post_id).unwrap())
+}
+
+async fn update_tag_settings(client: &Client, address: &str, username: &str, token: &str, allow_tags_from: &str) -> StatusCode {
+    let resp = client.put(format!("{}/api/v1/users/{}/tag-settings", address, username))
+        .bearer_auth(token)
+        .json(&json!({ "allowTagsFrom": allow_tags_from }))
+        .send()
+        .await
+        .unwrap();
+
+    resp.status()
+}
+
+// ---- CREATE POST TAG UNIT TESTS ----
+
+#[actix_web::test]
+async fn createposttag_should_notify_and_fill_tagged_tab() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (tagged, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+    let token = get_user_token(&app.config, &owner);
+
+    let mut statuses = vec![];
+    for (x, y) in [(0.25, 0.5), (0.75, 0.1)] {
+        let resp = client.post(tags_url(&app.address, &owner.username, &post.id))
+            .bearer_auth(&token)
+            .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": x, "y": y }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let tags = client.get(tags_url(&app.address, &owner.username, &post.id))
+        .send()
+        .await
+        .unwrap();
+    let tags: serde_json::Value = parse_response_body(tags).await;
+
+    let tagged_tab = client.get(format!("{}/api/v1/users/{}/tagged", &app.address, &tagged.username))
+        .send()
+        .await
+        .unwrap();
+    let tagged_tab: serde_json::Value = parse_response_body(tagged_tab).await;
+
+    let notifications_count = wait_for_notifications(&app.db, &tagged.id, "tag", 1).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &tagged.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CREATED]);
+
+    // Tagging again moves the tag
+    let tags = tags["data"].as_array().unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0]["user"]["username"].as_str(), Some(tagged.username.as_str()));
+    assert_eq!(tags[0]["x"].as_f64(), Some(0.75));
+
+    assert_eq!(tagged_tab["data"][0]["id"].as_str(), Some(Uuid::from_slice(&post.id).unwrap().to_string().as_str()));
+    assert_eq!(notifications_count, 1);
+}
+
+#[actix_web::test]
+async fn createposttag_should_reject_positions_outside_the_file() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (tagged, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+    let token = get_user_token(&app.config, &owner);
+
+    let outside = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(&token)
+        .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": 1.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+
+    let missing_file = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(&token)
+        .json(&json!({ "fileIndex": 3, "username": tagged.username, "x": 0.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &tagged.id).await;
+
+    assert_eq!(outside.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(missing_file.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn createposttag_should_follow_tag_settings() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (tagged, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+    let owner_token = get_user_token(&app.config, &owner);
+    let tagged_token = get_user_token(&app.config, &tagged);
+
+    let mut statuses = vec![];
+
+    statuses.push(update_tag_settings(&client, &app.address, &tagged.username, &tagged_token, "nobody").await);
+    let resp = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(&owner_token)
+        .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": 0.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    statuses.push(resp.status());
+
+    statuses.push(update_tag_settings(&client, &app.address, &tagged.username, &tagged_token, "following").await);
+    let resp = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(&owner_token)
+        .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": 0.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    statuses.push(resp.status());
+
+    follow_user(&app.db, &tagged.id, &owner.id).await;
+    let resp = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(&owner_token)
+        .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": 0.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    statuses.push(resp.status());
+
+    statuses.push(update_tag_settings(&client, &app.address, &tagged.username, &tagged_token, "friends").await);
+
+    wait_for_notifications(&app.db, &tagged.id, "tag", 1).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_follows(&app.db, &tagged.id).await;
+    delete_notifications(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &tagged.id).await;
+
+    assert_eq!(statuses, vec![
+        StatusCode::OK,
+        StatusCode::FORBIDDEN,
+        StatusCode::OK,
+        StatusCode::FORBIDDEN,
+        StatusCode::CREATED,
+        StatusCode::BAD_REQUEST,
+    ]);
+}
+
+// ---- END OF CREATE POST TAG UNIT TESTS ----
+
+
+
+// ---- DELETE POST TAG UNIT TESTS ----
+
+#[actix_web::test]
+async fn deleteposttag_should_let_tagged_user_remove_themselves() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (tagged, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+
+    let resp = client.post(tags_url(&app.address, &owner.username, &post.id))
+        .bearer_auth(get_user_token(&app.config, &owner))
+        .json(&json!({ "fileIndex": 0, "username": tagged.username, "x": 0.5, "y": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let tag_url = format!("{}/{}", tags_url(&app.address, &owner.username, &post.id), response_body["data"]["id"].as_i64().unwrap());
+
+    wait_for_notifications(&app.db, &tagged.id, "tag", 1).await;
+
+    let stranger_resp = client.delete(tag_url.clone())
+        .bearer_auth(get_user_token(&app.config, &stranger))
+        .send()
+        .await
+        .unwrap();
+
+    let tagged_resp = client.delete(tag_url)
+        .bearer_auth(get_user_token(&app.config, &tagged))
+        .send()
+        .await
+        .unwrap();
+
+    let notifications_count = wait_for_notifications(&app.db, &tagged.id, "tag", 0).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &tagged.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(stranger_resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(tagged_resp.status(), StatusCode::OK);
+    assert_eq!(notifications_count, 0);
+}
+
+// ---- END OF DELETE POST TAG UNIT TESTS ----
@@ -0,0 +1,195 @@
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_post, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// ---- LIKE POST UNIT TESTS ----
+
+#[actix_web::test]
+async fn likepost_should_be_idempotent() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let token = get_user_token(&app.config, &liker);
+
+    let mut likes_counts = vec![];
+    for _ in 0..2 {
+        let resp = client.post(url.clone())
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+        likes_counts.push(response_body["data"]["likesCount"].as_u64());
+    }
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(likes_counts, vec![Some(1), Some(1)]);
+}
+
+#[actix_web::test]
+async fn likepost_should_count_concurrent_likes() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (first_liker, _p) = create_random_user(&app.db).await;
+    let (second_liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let post_id = Uuid::from_slice(&post.id).unwrap();
+    let url = format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, post_id);
+
+    let (first, second) = futures::join!(
+        client.post(url.clone()).bearer_auth(get_user_token(&app.config, &first_liker)).send(),
+        client.post(url.clone()).bearer_auth(get_user_token(&app.config, &second_liker)).send(),
+    );
+
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    assert_eq!(second.unwrap().status(), StatusCode::OK);
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &author.username, post_id))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &first_liker.id).await;
+    delete_user(&app.db, &second_liker.id).await;
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["data"]["likesCount"].as_u64(), Some(2));
+}
+
+#[actix_web::test]
+async fn likepost_should_forbid_private_account_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &author).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &liker))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF LIKE POST UNIT TESTS ----
+
+
+
+// ---- UNLIKE POST UNIT TESTS ----
+
+#[actix_web::test]
+async fn unlikepost_should_be_idempotent() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+    let token = get_user_token(&app.config, &liker);
+
+    client.post(url.clone())
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let mut likes_counts = vec![];
+    for _ in 0..2 {
+        let resp = client.delete(url.clone())
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let response_body: serde_json::Value = parse_response_body(resp).await;
+        likes_counts.push(response_body["data"]["likesCount"].as_u64());
+    }
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(likes_counts, vec![Some(0), Some(0)]);
+}
+
+// ---- END OF UNLIKE POST UNIT TESTS ----
+
+
+
+// ---- GET LIKERS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getlikers_should_paginate() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (first_liker, _p) = create_random_user(&app.db).await;
+    let (second_liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let url = format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap());
+
+    for liker in [&first_liker, &second_liker] {
+        client.post(url.clone())
+            .bearer_auth(get_user_token(&app.config, liker))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let first_page = client.get(format!("{}?limit=1", url))
+        .send()
+        .await
+        .unwrap();
+    let first_page: serde_json::Value = parse_response_body(first_page).await;
+
+    let second_page = client.get(format!("{}?limit=1&cursor={}", url, first_page["nextCursor"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    let second_page: serde_json::Value = parse_response_body(second_page).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &first_liker.id).await;
+    delete_user(&app.db, &second_liker.id).await;
+
+    let first_username = first_page["data"][0]["username"].as_str().unwrap();
+    let second_username = second_page["data"][0]["username"].as_str().unwrap();
+
+    assert_ne!(first_username, second_username);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+// ---- END OF GET LIKERS UNIT TESTS ----
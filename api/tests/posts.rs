@@ -0,0 +1,210 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::EntityTrait;
+use uuid::Uuid;
+use insta::error::HttpResponseError;
+use crate::utils::{create_random_post, create_random_user, delete_post, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// ---- GET POST UNIT TESTS ----
+
+#[actix_web::test]
+async fn getpost_should_not_found_unknown_post() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &created_user.username, Uuid::new_v4()))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    assert_eq!(response_body.code, Some(StatusCode::NOT_FOUND.as_u16()));
+
+    let error = response_body.errors.first().unwrap();
+    assert_eq!(error.error, Some("Post not found".to_owned()));
+}
+
+#[actix_web::test]
+async fn getpost_should_return_proper_data() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &created_user.id).await;
+    let post_id = Uuid::from_slice(&post.id).unwrap();
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &created_user.username, post_id))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    assert_eq!(response_body["code"].as_u64(), Some(StatusCode::OK.as_u16() as u64));
+
+    let data = response_body["data"].as_object().unwrap();
+
+    assert_eq!(data["id"].as_str(), Some(post_id.to_string().as_str()));
+    assert_eq!(data["description"].as_str(), Some(post.description.as_str()));
+    assert_eq!(data["files"].as_array().unwrap().len(), 1);
+    assert_eq!(data["likesCount"].as_u64(), Some(0));
+    assert_eq!(data["commentsCount"].as_u64(), Some(0));
+    assert_eq!(data["user"]["username"].as_str(), Some(created_user.username.as_str()));
+    assert_eq!(data["user"]["hasStories"].as_bool(), Some(false));
+}
+
+#[actix_web::test]
+async fn getpost_should_hide_private_account_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &created_user).await;
+    let post = create_random_post(&app.db, &created_user.id).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/posts/{}", &app.address, &created_user.username, Uuid::from_slice(&post.id).unwrap()))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    let error = response_body.errors.first().unwrap();
+    assert_eq!(error.error, Some("This account is private".to_owned()));
+}
+
+// ---- END OF GET POST UNIT TESTS ----
+
+// ---- UPDATE POST UNIT TESTS ----
+
+#[actix_web::test]
+async fn updatepost_should_forbid_non_owner() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (other_user, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+
+    let resp = client.patch(format!("{}/api/v1/users/{}/posts/{}", &app.address, &owner.username, Uuid::from_slice(&post.id).unwrap()))
+        .header("Content-Type", "application/json")
+        .bearer_auth(get_user_token(&app.config, &other_user))
+        .json(&serde_json::json!({
+            "description": "Hijacked"
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &other_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let response_body: HttpResponseError = parse_response_body(resp).await;
+    assert_eq!(response_body.code, Some(StatusCode::FORBIDDEN.as_u16()));
+}
+
+#[actix_web::test]
+async fn updatepost_should_success() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &created_user.id).await;
+
+    let resp = client.put(format!("{}/api/v1/users/{}/posts/{}", &app.address, &created_user.username, Uuid::from_slice(&post.id).unwrap()))
+        .header("Content-Type", "application/json")
+        .bearer_auth(get_user_token(&app.config, &created_user))
+        .json(&serde_json::json!({
+            "description": "Updated description"
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let updated_post = entity::posts::Entity::find_by_id(post.id.clone())
+        .one(&app.db)
+        .await
+        .expect("Failed to find post")
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(updated_post.description, "Updated description");
+    assert!(updated_post.updated_at >= post.updated_at);
+}
+
+// ---- END OF UPDATE POST UNIT TESTS ----
+
+// ---- DELETE POST UNIT TESTS ----
+
+#[actix_web::test]
+async fn deletepost_should_forbid_non_owner() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (other_user, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &owner.id).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/posts/{}", &app.address, &owner.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &other_user))
+        .send()
+        .await
+        .unwrap();
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &other_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn deletepost_should_remove_dependent_rows() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (created_user, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &created_user.id).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/posts/{}", &app.address, &created_user.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &created_user))
+        .send()
+        .await
+        .unwrap();
+
+    let deleted_post = entity::posts::Entity::find_by_id(post.id.clone())
+        .one(&app.db)
+        .await
+        .expect("Failed to find post");
+
+    delete_user(&app.db, &created_user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(deleted_post.is_none());
+}
+
+// ---- END OF DELETE POST UNIT TESTS ----
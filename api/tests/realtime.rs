@@ -0,0 +1,313 @@
+use std::time::Duration;
+use futures::{SinkExt, StreamExt};
+use reqwest::{Client, Response, StatusCode};
+use serde_json::json;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message as WsMessage};
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, create_upload, delete_conversations, delete_follows, delete_post, delete_stories, delete_user, follow_user, get_user_token, parse_response_body};
+
+mod utils;
+
+async fn open_stream(client: &Client, address: &str, username: &str, token: &str) -> Response {
+    client.get(format!("{}/api/v1/users/{}/stream", address, username))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+}
+
+// Reads server-sent events until one with the given name arrives, gives up after a few seconds
+async fn wait_for_event(stream: &mut Response, name: &str) -> Option<serde_json::Value> {
+    let mut buffer = String::new();
+
+    loop {
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            let mut event_name = None;
+            let mut data = None;
+
+            for line in event.lines() {
+                if let Some(value) = line.strip_prefix("event: ") {
+                    event_name = Some(value.to_string());
+                }
+                if let Some(value) = line.strip_prefix("data: ") {
+                    data = serde_json::from_str(value).ok();
+                }
+            }
+
+            if event_name.as_deref() == Some(name) {
+                return data;
+            }
+        }
+
+        let chunk = actix_web::rt::time::timeout(Duration::from_secs(5), stream.chunk())
+            .await
+            .ok()?
+            .ok()??;
+
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+    }
+}
+
+type ChatSocket = WebSocketStream<MaybeTlsStream<actix_web::rt::net::TcpStream>>;
+
+async fn open_chat(address: &str, username: &str, token: &str, last_message_id: Option<i64>) -> Result<ChatSocket, WsError> {
+    let mut url = format!("{}/api/v1/users/{}/chat?accessToken={}", address.replacen("http", "ws", 1), username, token);
+    if let Some(last_message_id) = last_message_id {
+        url.push_str(&format!("&lastMessageId={}", last_message_id));
+    }
+
+    connect_async(url).await.map(|(socket, _)| socket)
+}
+
+// Reads chat frames until one of the given type arrives, gives up after a few seconds
+async fn wait_for_frame(socket: &mut ChatSocket, frame_type: &str) -> Option<serde_json::Value> {
+    loop {
+        let message = actix_web::rt::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .ok()??
+            .ok()?;
+
+        if let WsMessage::Text(text) = message {
+            let frame: serde_json::Value = serde_json::from_str(&text).ok()?;
+            if frame["type"].as_str() == Some(frame_type) {
+                return Some(frame["data"].clone());
+            }
+        }
+    }
+}
+
+async fn start_conversation(client: &Client, address: &str, sender: &entity::users::Model, recipient: &str, token: &str) -> String {
+    let resp = client.post(format!("{}/api/v1/users/{}/conversations", address, sender.username))
+        .bearer_auth(token)
+        .json(&json!({ "usernames": [recipient] }))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    response_body["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn send_text(client: &Client, address: &str, username: &str, conversation_id: &str, text: &str, token: &str) -> i64 {
+    let resp = client.post(format!("{}/api/v1/users/{}/conversations/{}/messages", address, username, conversation_id))
+        .bearer_auth(token)
+        .json(&json!({ "text": text }))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    response_body["data"]["id"].as_i64().unwrap()
+}
+
+// ---- STREAM UNIT TESTS ----
+
+#[actix_web::test]
+async fn stream_should_push_new_notifications() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (liker, _p) = create_random_user(&app.db).await;
+    let post = create_random_post(&app.db, &author.id).await;
+
+    let mut stream = open_stream(&client, &app.address, &author.username, &get_user_token(&app.config, &author)).await;
+    let status = stream.status();
+    let initial_count = wait_for_event(&mut stream, "unread_count").await;
+
+    client.post(format!("{}/api/v1/users/{}/posts/{}/likes", &app.address, &author.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &liker))
+        .send()
+        .await
+        .unwrap();
+
+    let notification = wait_for_event(&mut stream, "notification").await;
+    let unread_count = wait_for_event(&mut stream, "unread_count").await;
+    drop(stream);
+
+    delete_post(&app.db, &post.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &liker.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(initial_count, Some(json!({ "unreadCount": 0 })));
+
+    let notification = notification.unwrap();
+    assert_eq!(notification["kind"].as_str(), Some("like"));
+    assert_eq!(notification["actor"]["username"].as_str(), Some(liker.username.as_str()));
+    assert_eq!(unread_count, Some(json!({ "unreadCount": 1 })));
+}
+
+#[actix_web::test]
+async fn stream_should_push_stories_of_followed_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &follower.id, &author.id).await;
+    let file_url = create_upload(&app.db, &author.id).await;
+
+    let mut stream = open_stream(&client, &app.address, &follower.username, &get_user_token(&app.config, &follower)).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/stories", &app.address, &author.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .json(&json!({ "fileUrl": file_url }))
+        .send()
+        .await
+        .unwrap();
+    let story_status = resp.status();
+
+    let story = wait_for_event(&mut stream, "story").await;
+    drop(stream);
+
+    delete_stories(&app.db, &author.id).await;
+    delete_follows(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &follower.id).await;
+
+    assert_eq!(story_status, StatusCode::CREATED);
+    assert_eq!(story.unwrap()["user"]["username"].as_str(), Some(author.username.as_str()));
+}
+
+#[actix_web::test]
+async fn stream_should_only_be_opened_by_the_owner() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let resp = open_stream(&client, &app.address, &user.username, &get_user_token(&app.config, &stranger)).await;
+    let status = resp.status();
+
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+// ---- END OF STREAM UNIT TESTS ----
+
+
+
+// ---- CHAT UNIT TESTS ----
+
+#[actix_web::test]
+async fn chat_should_deliver_messages_and_read_receipts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+    let recipient_token = get_user_token(&app.config, &recipient);
+
+    let conversation_id = start_conversation(&client, &app.address, &sender, &recipient.username, &sender_token).await;
+    let mut sender_socket = open_chat(&app.address, &sender.username, &sender_token, None).await.unwrap();
+    let mut recipient_socket = open_chat(&app.address, &recipient.username, &recipient_token, None).await.unwrap();
+
+    let message_id = send_text(&client, &app.address, &sender.username, &conversation_id, "Hello", &sender_token).await;
+    let message = wait_for_frame(&mut recipient_socket, "message").await;
+
+    recipient_socket.send(WsMessage::Text(
+        json!({ "type": "read", "data": { "conversationId": conversation_id, "messageId": message_id } }).to_string()
+    )).await.unwrap();
+    let receipt = wait_for_frame(&mut sender_socket, "read").await;
+
+    drop(sender_socket);
+    drop(recipient_socket);
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    let message = message.unwrap();
+    assert_eq!(message["id"].as_i64(), Some(message_id));
+    assert_eq!(message["text"].as_str(), Some("Hello"));
+    assert_eq!(message["sender"]["username"].as_str(), Some(sender.username.as_str()));
+
+    let receipt = receipt.unwrap();
+    assert_eq!(receipt["conversationId"].as_str(), Some(conversation_id.as_str()));
+    assert_eq!(receipt["username"].as_str(), Some(recipient.username.as_str()));
+    assert_eq!(receipt["messageId"].as_i64(), Some(message_id));
+}
+
+#[actix_web::test]
+async fn chat_should_deliver_typing_indicators() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+
+    let conversation_id = start_conversation(&client, &app.address, &sender, &recipient.username, &sender_token).await;
+    let mut sender_socket = open_chat(&app.address, &sender.username, &sender_token, None).await.unwrap();
+    let mut recipient_socket = open_chat(&app.address, &recipient.username, &get_user_token(&app.config, &recipient), None).await.unwrap();
+
+    sender_socket.send(WsMessage::Text(
+        json!({ "type": "typing", "data": { "conversationId": conversation_id } }).to_string()
+    )).await.unwrap();
+    let typing = wait_for_frame(&mut recipient_socket, "typing").await;
+
+    drop(sender_socket);
+    drop(recipient_socket);
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    let typing = typing.unwrap();
+    assert_eq!(typing["conversationId"].as_str(), Some(conversation_id.as_str()));
+    assert_eq!(typing["username"].as_str(), Some(sender.username.as_str()));
+}
+
+#[actix_web::test]
+async fn chat_should_replay_messages_missed_since_the_last_one() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (sender, _p) = create_random_user(&app.db).await;
+    let (recipient, _p) = create_random_user(&app.db).await;
+    let sender_token = get_user_token(&app.config, &sender);
+
+    let conversation_id = start_conversation(&client, &app.address, &sender, &recipient.username, &sender_token).await;
+    let seen_id = send_text(&client, &app.address, &sender.username, &conversation_id, "Seen", &sender_token).await;
+    let missed_id = send_text(&client, &app.address, &sender.username, &conversation_id, "Missed", &sender_token).await;
+
+    let mut socket = open_chat(&app.address, &recipient.username, &get_user_token(&app.config, &recipient), Some(seen_id)).await.unwrap();
+    let replayed = wait_for_frame(&mut socket, "message").await;
+
+    drop(socket);
+
+    delete_conversations(&app.db, &sender.id).await;
+    delete_user(&app.db, &sender.id).await;
+    delete_user(&app.db, &recipient.id).await;
+
+    let replayed = replayed.unwrap();
+    assert_eq!(replayed["id"].as_i64(), Some(missed_id));
+    assert_eq!(replayed["text"].as_str(), Some("Missed"));
+}
+
+#[actix_web::test]
+async fn chat_should_only_be_opened_by_the_owner() {
+    let app = utils::start_test_server().await;
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+
+    let result = open_chat(&app.address, &user.username, &get_user_token(&app.config, &stranger), None).await;
+
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    match result {
+        Err(WsError::Http(response)) => assert_eq!(response.status().as_u16(), StatusCode::FORBIDDEN.as_u16()),
+        _ => panic!("The handshake should be refused"),
+    }
+}
+
+// ---- END OF CHAT UNIT TESTS ----
@@ -0,0 +1,156 @@
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_follows, delete_post, delete_user, follow_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// A word no other row contains, so results only hold what the test created
+fn unique_word() -> String {
+    format!("word{}", &Uuid::new_v4().simple().to_string()[..12])
+}
+
+async fn rename_user(db: &DatabaseConnection, user: &entity::users::Model, name: &str) {
+    let mut user: entity::users::ActiveModel = user.clone().into();
+    user.name = Set(name.to_owned());
+    user.update(db).await.expect("Failed to rename user");
+}
+
+async fn create_post_with_description(db: &DatabaseConnection, user_id: &[u8], description: &str) -> entity::posts::Model {
+    let post = create_random_post(db, user_id).await;
+
+    let mut post: entity::posts::ActiveModel = post.into();
+    post.description = Set(description.to_owned());
+    post.update(db).await.expect("Failed to update post description")
+}
+
+async fn search(client: &Client, address: &str, query: &str, token: Option<&str>) -> (StatusCode, serde_json::Value) {
+    let mut request = client.get(format!("{}/api/v1/search", address))
+        .query(&[("q", query)]);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    let resp = request.send().await.unwrap();
+    let status = resp.status();
+
+    (status, parse_response_body(resp).await)
+}
+
+// ---- SEARCH UNIT TESTS ----
+
+#[actix_web::test]
+async fn search_should_find_users_by_username_prefix() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+
+    let (status, response_body) = search(&client, &app.address, &format!("@{}", &user.username), None).await;
+
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_body["data"]["users"][0]["username"].as_str(), Some(user.username.as_str()));
+    assert!(response_body["data"]["users"][0]["following"].is_null());
+    assert!(response_body["data"]["posts"].is_array());
+    assert!(response_body["data"]["hashtags"].is_array());
+}
+
+#[actix_web::test]
+async fn search_should_boost_followed_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let word = unique_word();
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    let (followed, _p) = create_random_user(&app.db).await;
+    rename_user(&app.db, &stranger, &format!("{} Stranger", word)).await;
+    rename_user(&app.db, &followed, &format!("{} Friend", word)).await;
+    follow_user(&app.db, &viewer.id, &followed.id).await;
+
+    let (status, response_body) = search(&client, &app.address, &word, Some(&get_user_token(&app.config, &viewer))).await;
+
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &stranger.id).await;
+    delete_user(&app.db, &followed.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let users = response_body["data"]["users"].as_array().unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0]["username"].as_str(), Some(followed.username.as_str()));
+    assert_eq!(users[0]["following"].as_bool(), Some(true));
+    assert_eq!(users[1]["username"].as_str(), Some(stranger.username.as_str()));
+}
+
+#[actix_web::test]
+async fn search_should_exclude_blocked_and_private_content() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let word = unique_word();
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (public_author, _p) = create_random_user(&app.db).await;
+    let (private_author, _p) = create_random_user(&app.db).await;
+    let (blocked_author, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &private_author).await;
+
+    let public_post = create_post_with_description(&app.db, &public_author.id, &format!("Sunset {}", word)).await;
+    let private_post = create_post_with_description(&app.db, &private_author.id, &format!("Sunset {}", word)).await;
+    let blocked_post = create_post_with_description(&app.db, &blocked_author.id, &format!("Sunset {}", word)).await;
+
+    let token = get_user_token(&app.config, &viewer);
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &viewer.username, &blocked_author.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let (status, response_body) = search(&client, &app.address, &word, Some(&token)).await;
+
+    delete_post(&app.db, &public_post.id).await;
+    delete_post(&app.db, &private_post.id).await;
+    delete_post(&app.db, &blocked_post.id).await;
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &public_author.id).await;
+    delete_user(&app.db, &private_author.id).await;
+    delete_user(&app.db, &blocked_author.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let posts = response_body["data"]["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["id"].as_str(), Some(Uuid::from_slice(&public_post.id).unwrap().to_string().as_str()));
+}
+
+#[actix_web::test]
+async fn search_should_only_return_the_requested_type() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let resp = client.get(format!("{}/api/v1/search", &app.address))
+        .query(&[("q", "sunset"), ("type", "hashtags")])
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    let invalid = client.get(format!("{}/api/v1/search", &app.address))
+        .query(&[("q", "sunset"), ("type", "places")])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(response_body["data"]["hashtags"].is_array());
+    assert!(response_body["data"].get("users").is_none());
+    assert!(response_body["data"].get("posts").is_none());
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+}
+
+// ---- END OF SEARCH UNIT TESTS ----
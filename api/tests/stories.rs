@@ -0,0 +1,355 @@
+use chrono::{Duration, Utc};
+use reqwest::{Client, StatusCode};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use uuid::Uuid;
+use insta::stories::stories_service::{delete_expired_stories, STORY_LIFETIME_HOURS};
+use insta::configuration::StorageSettings;
+use insta::utils::storage::MediaStorage;
+use crate::utils::{create_random_user, create_story, create_upload, delete_blocks_and_mutes, delete_follows, delete_stories, delete_user, follow_user, get_user_token, parse_response_body, record_upload};
+
+mod utils;
+
+// ---- CREATE STORY UNIT TESTS ----
+
+#[actix_web::test]
+async fn createstory_should_return_created() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let file_url = create_upload(&app.db, &user.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/stories", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .json(&serde_json::json!({ "fileUrl": file_url }))
+        .send()
+        .await
+        .unwrap();
+
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_body["data"]["fileUrl"], file_url.as_str());
+    assert_eq!(response_body["data"]["user"]["hasStories"], true);
+}
+
+#[actix_web::test]
+async fn createstory_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/stories", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &other))
+        .json(&serde_json::json!({ "fileUrl": "https://bit.ly/3REd7XG" }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn createstory_should_reject_media_uploaded_by_others() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let file_url = create_upload(&app.db, &other.id).await;
+
+    let mut statuses = vec![];
+    for file_url in [file_url.as_str(), "https://bit.ly/3REd7XG"] {
+        let resp = client.post(format!("{}/api/v1/users/{}/stories", &app.address, &user.username))
+            .bearer_auth(get_user_token(&app.config, &user))
+            .json(&serde_json::json!({ "fileUrl": file_url }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::BAD_REQUEST, StatusCode::BAD_REQUEST]);
+}
+
+// ---- END OF CREATE STORY UNIT TESTS ----
+
+
+
+// ---- GET STORIES UNIT TESTS ----
+
+#[actix_web::test]
+async fn getstories_should_hide_expired_stories() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let active = create_story(&app.db, &user.id, Utc::now()).await;
+    create_story(&app.db, &user.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1)).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/stories", &app.address, &user.username))
+        .send()
+        .await
+        .unwrap();
+
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_body["data"].as_array().map(|stories| stories.len()), Some(1));
+    assert_eq!(response_body["data"][0]["id"], Uuid::from_slice(&active.id).unwrap().to_string());
+}
+
+// ---- END OF GET STORIES UNIT TESTS ----
+
+
+
+// ---- DELETE STORY UNIT TESTS ----
+
+#[actix_web::test]
+async fn deletestory_should_return_not_found_for_invalid_id() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/stories/not-a-uuid", &app.address, &user.username))
+        .bearer_auth(get_user_token(&app.config, &user))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &user.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn deletestory_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &user.id, Utc::now()).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/stories/{}", &app.address, &user.username, Uuid::from_slice(&story.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &other))
+        .send()
+        .await
+        .unwrap();
+
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ---- END OF DELETE STORY UNIT TESTS ----
+
+
+
+// ---- STORY SWEEPER UNIT TESTS ----
+
+#[actix_web::test]
+async fn sweeper_should_delete_expired_stories_only() {
+    let app = utils::start_test_server().await;
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let active = create_story(&app.db, &user.id, Utc::now()).await;
+    let expired = create_story(&app.db, &user.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1)).await;
+
+    entity::story_views::ActiveModel {
+        story_id: Set(expired.id.clone()),
+        viewer_id: Set(viewer.id.clone()),
+        ..Default::default()
+    }.insert(&app.db).await.expect("Failed to insert story view");
+
+    let result = delete_expired_stories(&app.db, &MediaStorage::default()).await;
+
+    let remaining: Vec<Vec<u8>> = entity::stories::Entity::find()
+        .filter(entity::stories::Column::UserId.eq(user.id.clone()))
+        .all(&app.db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|story| story.id)
+        .collect();
+
+    delete_stories(&app.db, &user.id).await;
+    delete_stories(&app.db, &viewer.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &viewer.id).await;
+
+    assert!(result.is_ok());
+    assert_eq!(remaining, vec![active.id]);
+}
+
+#[actix_web::test]
+async fn sweeper_should_keep_media_uploaded_by_others() {
+    let app = utils::start_test_server().await;
+
+    let media_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&media_dir).unwrap();
+    std::fs::write(media_dir.join("borrowed.jpg"), b"borrowed").unwrap();
+
+    let storage = MediaStorage::new(Some(StorageSettings {
+        base_url: String::from("https://cdn.example.com/media"),
+        path: media_dir.to_string_lossy().to_string(),
+    }));
+
+    let (user, _p) = create_random_user(&app.db).await;
+    let (uploader, _p) = create_random_user(&app.db).await;
+    record_upload(&app.db, &uploader.id, "https://cdn.example.com/media/borrowed.jpg").await;
+
+    // Stories created before uploads were checked can point to anybody's file
+    let mut borrowed: entity::stories::ActiveModel = create_story(&app.db, &user.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1)).await.into();
+    borrowed.file_url = Set(String::from("https://cdn.example.com/media/borrowed.jpg"));
+    borrowed.update(&app.db).await.unwrap();
+
+    let result = delete_expired_stories(&app.db, &storage).await;
+
+    let borrowed_exists = media_dir.join("borrowed.jpg").exists();
+
+    std::fs::remove_dir_all(&media_dir).unwrap();
+    delete_stories(&app.db, &user.id).await;
+    delete_user(&app.db, &user.id).await;
+    delete_user(&app.db, &uploader.id).await;
+
+    assert!(result.is_ok());
+    assert!(borrowed_exists);
+}
+
+// ---- END OF STORY SWEEPER UNIT TESTS ----
+
+
+
+// ---- STORY TRAY UNIT TESTS ----
+
+#[actix_web::test]
+async fn storytray_should_group_stories_of_followed_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+
+    create_story(&app.db, &followee.id, Utc::now() - Duration::hours(2)).await;
+    create_story(&app.db, &followee.id, Utc::now() - Duration::hours(1)).await;
+    create_story(&app.db, &stranger.id, Utc::now()).await;
+
+    let resp = client.get(format!("{}/api/v1/feed/stories", &app.address))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .send()
+        .await
+        .unwrap();
+
+    let status = resp.status();
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_follows(&app.db, &viewer.id).await;
+    for user in [&viewer, &followee, &stranger] {
+        delete_stories(&app.db, &user.id).await;
+        delete_user(&app.db, &user.id).await;
+    }
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_body["data"].as_array().map(|tray| tray.len()), Some(1));
+    assert_eq!(response_body["data"][0]["user"]["username"], followee.username.as_str());
+    assert_eq!(response_body["data"][0]["storiesCount"], 2);
+    assert_eq!(response_body["data"][0]["seen"], false);
+}
+
+#[actix_web::test]
+async fn storytray_should_mark_users_seen_after_viewing() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    let story = create_story(&app.db, &followee.id, Utc::now()).await;
+
+    let token = get_user_token(&app.config, &viewer);
+
+    client.post(format!("{}/api/v1/stories/views", &app.address))
+        .bearer_auth(&token)
+        .json(&serde_json::json!({ "storyIds": [Uuid::from_slice(&story.id).unwrap().to_string()] }))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/feed/stories", &app.address))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_follows(&app.db, &viewer.id).await;
+    delete_stories(&app.db, &viewer.id).await;
+    delete_stories(&app.db, &followee.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(response_body["data"][0]["seen"], true);
+}
+
+#[actix_web::test]
+async fn storytray_should_exclude_muted_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    create_story(&app.db, &followee.id, Utc::now()).await;
+
+    let token = get_user_token(&app.config, &viewer);
+
+    client.post(format!("{}/api/v1/users/{}/mutes/{}", &app.address, &viewer.username, &followee.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/feed/stories", &app.address))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_stories(&app.db, &followee.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(response_body["data"].as_array().map(|tray| tray.len()), Some(0));
+}
+
+// ---- END OF STORY TRAY UNIT TESTS ----
@@ -0,0 +1,205 @@
+use chrono::{Duration, Utc};
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+use insta::stories::stories_service::STORY_LIFETIME_HOURS;
+use crate::utils::{create_random_user, create_story, delete_stories, delete_user, get_user_token, make_user_private, parse_response_body};
+
+mod utils;
+
+// ---- MARK STORIES VIEWED UNIT TESTS ----
+
+#[actix_web::test]
+async fn markstoriesviewed_should_record_each_viewer_once() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let first = create_story(&app.db, &owner.id, Utc::now() - Duration::hours(1)).await;
+    let second = create_story(&app.db, &owner.id, Utc::now()).await;
+
+    let story_ids = vec![
+        Uuid::from_slice(&first.id).unwrap().to_string(),
+        Uuid::from_slice(&second.id).unwrap().to_string(),
+    ];
+
+    let mut statuses = vec![];
+    for _ in 0..2 {
+        let resp = client.post(format!("{}/api/v1/stories/views", &app.address))
+            .bearer_auth(get_user_token(&app.config, &viewer))
+            .json(&serde_json::json!({ "storyIds": story_ids }))
+            .send()
+            .await
+            .unwrap();
+
+        statuses.push(resp.status());
+    }
+
+    let resp = client.get(format!("{}/api/v1/users/{}/stories", &app.address, &owner.username))
+        .bearer_auth(get_user_token(&app.config, &owner))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &viewer.id).await;
+
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::OK]);
+    assert_eq!(response_body["data"][0]["viewsCount"], 1);
+    assert_eq!(response_body["data"][1]["viewsCount"], 1);
+}
+
+#[actix_web::test]
+async fn markstoriesviewed_should_skip_stories_of_private_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (viewer, _p) = create_random_user(&app.db).await;
+    make_user_private(&app.db, &owner).await;
+    let story = create_story(&app.db, &owner.id, Utc::now()).await;
+
+    client.post(format!("{}/api/v1/stories/views", &app.address))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .json(&serde_json::json!({ "storyIds": [Uuid::from_slice(&story.id).unwrap().to_string()] }))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(format!("{}/api/v1/users/{}/stories/{}/views", &app.address, &owner.username, Uuid::from_slice(&story.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &owner))
+        .send()
+        .await
+        .unwrap();
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &viewer.id).await;
+
+    assert_eq!(response_body["viewsCount"], 0);
+}
+
+#[actix_web::test]
+async fn markstoriesviewed_should_reject_invalid_ids() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/stories/views", &app.address))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .json(&serde_json::json!({ "storyIds": ["not-a-uuid"] }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &viewer.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ---- END OF MARK STORIES VIEWED UNIT TESTS ----
+
+
+
+// ---- GET STORY VIEWERS UNIT TESTS ----
+
+#[actix_web::test]
+async fn getstoryviewers_should_paginate_viewers() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (first_viewer, _p) = create_random_user(&app.db).await;
+    let (second_viewer, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &owner.id, Utc::now()).await;
+    let story_id = Uuid::from_slice(&story.id).unwrap().to_string();
+
+    for viewer in [&first_viewer, &second_viewer] {
+        client.post(format!("{}/api/v1/stories/views", &app.address))
+            .bearer_auth(get_user_token(&app.config, viewer))
+            .json(&serde_json::json!({ "storyIds": [story_id] }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let url = format!("{}/api/v1/users/{}/stories/{}/views", &app.address, &owner.username, story_id);
+    let token = get_user_token(&app.config, &owner);
+
+    let resp = client.get(format!("{}?limit=1", url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let first_page: serde_json::Value = parse_response_body(resp).await;
+
+    let resp = client.get(format!("{}?limit=1&cursor={}", url, first_page["nextCursor"].as_str().unwrap_or_default()))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+
+    let second_page: serde_json::Value = parse_response_body(resp).await;
+
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &first_viewer.id).await;
+    delete_user(&app.db, &second_viewer.id).await;
+
+    assert_eq!(first_page["viewsCount"], 2);
+    assert_eq!(first_page["data"].as_array().map(|viewers| viewers.len()), Some(1));
+    assert_eq!(second_page["data"].as_array().map(|viewers| viewers.len()), Some(1));
+    assert_ne!(first_page["data"][0]["username"], second_page["data"][0]["username"]);
+    assert!(second_page["nextCursor"].is_null());
+}
+
+#[actix_web::test]
+async fn getstoryviewers_should_forbid_other_users() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let (other, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &owner.id, Utc::now()).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/stories/{}/views", &app.address, &owner.username, Uuid::from_slice(&story.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &other))
+        .send()
+        .await
+        .unwrap();
+
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+    delete_user(&app.db, &other.id).await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn getstoryviewers_should_return_not_found_for_expired_story() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (owner, _p) = create_random_user(&app.db).await;
+    let story = create_story(&app.db, &owner.id, Utc::now() - Duration::hours(STORY_LIFETIME_HOURS + 1)).await;
+
+    let resp = client.get(format!("{}/api/v1/users/{}/stories/{}/views", &app.address, &owner.username, Uuid::from_slice(&story.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &owner))
+        .send()
+        .await
+        .unwrap();
+
+    delete_stories(&app.db, &owner.id).await;
+    delete_user(&app.db, &owner.id).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ---- END OF GET STORY VIEWERS UNIT TESTS ----
@@ -0,0 +1,157 @@
+use reqwest::{Client, StatusCode};
+use crate::utils::{create_random_user, delete_blocks_and_mutes, delete_follows, delete_suggestion_dismissals, delete_user, follow_user, get_user_token, parse_response_body};
+
+mod utils;
+
+async fn get_suggestions(client: &Client, address: &str, token: &str) -> (StatusCode, serde_json::Value) {
+    let resp = client.get(format!("{}/api/v1/users/suggestions?limit=50", address))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    let status = resp.status();
+
+    (status, parse_response_body(resp).await)
+}
+
+fn find_suggestion<'a>(response_body: &'a serde_json::Value, username: &str) -> Option<&'a serde_json::Value> {
+    response_body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|suggestion| suggestion["username"].as_str() == Some(username))
+}
+
+fn position_of(response_body: &serde_json::Value, username: &str) -> Option<usize> {
+    response_body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .position(|suggestion| suggestion["username"].as_str() == Some(username))
+}
+
+// ---- SUGGESTIONS UNIT TESTS ----
+
+#[actix_web::test]
+async fn suggestions_should_rank_friends_of_friends_by_mutual_count() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (first_friend, _p) = create_random_user(&app.db).await;
+    let (second_friend, _p) = create_random_user(&app.db).await;
+    let (third_friend, _p) = create_random_user(&app.db).await;
+    let (well_connected, _p) = create_random_user(&app.db).await;
+    let (barely_connected, _p) = create_random_user(&app.db).await;
+
+    for friend in [&first_friend, &second_friend, &third_friend] {
+        follow_user(&app.db, &viewer.id, &friend.id).await;
+        follow_user(&app.db, &friend.id, &well_connected.id).await;
+    }
+    follow_user(&app.db, &first_friend.id, &barely_connected.id).await;
+
+    let (status, response_body) = get_suggestions(&client, &app.address, &get_user_token(&app.config, &viewer)).await;
+
+    for user in [&viewer, &first_friend, &second_friend, &third_friend] {
+        delete_follows(&app.db, &user.id).await;
+    }
+    for user in [&viewer, &first_friend, &second_friend, &third_friend, &well_connected, &barely_connected] {
+        delete_user(&app.db, &user.id).await;
+    }
+
+    assert_eq!(status, StatusCode::OK);
+
+    let well_connected_position = position_of(&response_body, &well_connected.username).unwrap();
+    let barely_connected_position = position_of(&response_body, &barely_connected.username).unwrap();
+    assert!(well_connected_position < barely_connected_position);
+
+    let suggestion = find_suggestion(&response_body, &well_connected.username).unwrap();
+    assert_eq!(suggestion["mutualCount"].as_u64(), Some(3));
+    assert!(suggestion["explanation"].as_str().unwrap().ends_with(" and 2 others"));
+
+    let suggestion = find_suggestion(&response_body, &barely_connected.username).unwrap();
+    assert_eq!(suggestion["mutualCount"].as_u64(), Some(1));
+    assert_eq!(suggestion["followedBy"].as_str(), Some(first_friend.username.as_str()));
+    assert_eq!(suggestion["explanation"].as_str(), Some(format!("Followed by {}", first_friend.username).as_str()));
+
+    for user in [&viewer, &first_friend, &second_friend, &third_friend] {
+        assert!(find_suggestion(&response_body, &user.username).is_none());
+    }
+}
+
+#[actix_web::test]
+async fn suggestions_should_exclude_blocked_and_dismissed_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (friend, _p) = create_random_user(&app.db).await;
+    let (suggested, _p) = create_random_user(&app.db).await;
+    let (blocked, _p) = create_random_user(&app.db).await;
+    let (dismissed, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &friend.id).await;
+    for user in [&suggested, &blocked, &dismissed] {
+        follow_user(&app.db, &friend.id, &user.id).await;
+    }
+
+    let token = get_user_token(&app.config, &viewer);
+    client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &viewer.username, &blocked.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let resp = client.delete(format!("{}/api/v1/users/suggestions/{}", &app.address, &dismissed.username))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let dismiss_status = resp.status();
+
+    let (status, response_body) = get_suggestions(&client, &app.address, &token).await;
+
+    delete_suggestion_dismissals(&app.db, &viewer.id).await;
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &friend.id).await;
+    for user in [&viewer, &friend, &suggested, &blocked, &dismissed] {
+        delete_user(&app.db, &user.id).await;
+    }
+
+    assert_eq!(dismiss_status, StatusCode::OK);
+    assert_eq!(status, StatusCode::OK);
+    assert!(find_suggestion(&response_body, &suggested.username).is_some());
+    assert!(find_suggestion(&response_body, &blocked.username).is_none());
+    assert!(find_suggestion(&response_body, &dismissed.username).is_none());
+}
+
+#[actix_web::test]
+async fn suggestions_should_fall_back_to_popular_accounts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (popular, _p) = create_random_user(&app.db).await;
+
+    let mut followers = vec![];
+    for _ in 0..3 {
+        let (follower, _p) = create_random_user(&app.db).await;
+        follow_user(&app.db, &follower.id, &popular.id).await;
+        followers.push(follower);
+    }
+
+    let (status, response_body) = get_suggestions(&client, &app.address, &get_user_token(&app.config, &viewer)).await;
+
+    delete_follows(&app.db, &popular.id).await;
+    for user in followers.iter().chain([&viewer, &popular]) {
+        delete_user(&app.db, &user.id).await;
+    }
+
+    assert_eq!(status, StatusCode::OK);
+
+    let suggestion = find_suggestion(&response_body, &popular.username).unwrap();
+    assert_eq!(suggestion["mutualCount"].as_u64(), Some(0));
+    assert!(suggestion["followedBy"].is_null());
+    assert_eq!(suggestion["explanation"].as_str(), Some("Popular account"));
+}
+
+// ---- END OF SUGGESTIONS UNIT TESTS ----
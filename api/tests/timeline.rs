@@ -0,0 +1,238 @@
+use std::time::Duration;
+use reqwest::{Client, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use uuid::Uuid;
+use insta::timeline::timeline_service::{rebuild_timeline, FAN_OUT_FOLLOWER_LIMIT};
+use crate::utils::{create_random_post, create_random_user, delete_blocks_and_mutes, delete_follows, delete_post, delete_user, follow_user, get_user_token, parse_response_body, set_followers_count};
+
+mod utils;
+
+async fn count_timeline_entries(db: &sea_orm::DatabaseConnection, user_id: &[u8]) -> u64 {
+    entity::timeline_entries::Entity::find()
+        .filter(entity::timeline_entries::Column::UserId.eq(user_id.to_vec()))
+        .count(db)
+        .await
+        .unwrap()
+}
+
+// ---- TIMELINE UNIT TESTS ----
+
+#[actix_web::test]
+async fn creating_post_should_fan_out_to_followers() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &follower.id, &author.id).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts", &app.address, &author.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .json(&serde_json::json!({
+            "description": "hello",
+            "files": ["https://bit.ly/3REd7XG"]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let response_body: serde_json::Value = parse_response_body(resp).await;
+    let post_id = Uuid::parse_str(response_body["data"]["id"].as_str().unwrap()).unwrap();
+
+    // The fan out happens in the background
+    let mut entries_count = 0;
+    for _ in 0..20 {
+        entries_count = count_timeline_entries(&app.db, &follower.id).await;
+        if entries_count > 0 {
+            break;
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    delete_post(&app.db, post_id.as_bytes()).await;
+    delete_follows(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &follower.id).await;
+
+    assert_eq!(entries_count, 1);
+}
+
+#[actix_web::test]
+async fn create_post_without_files_should_fail() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+
+    let resp = client.post(format!("{}/api/v1/users/{}/posts", &app.address, &author.username))
+        .bearer_auth(get_user_token(&app.config, &author))
+        .json(&serde_json::json!({ "description": "hello", "files": [] }))
+        .send()
+        .await
+        .unwrap();
+
+    delete_user(&app.db, &author.id).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn rebuild_should_regenerate_timeline_from_follows() {
+    let app = utils::start_test_server().await;
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    let (stranger, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+
+    let first_post = create_random_post(&app.db, &followee.id).await;
+    let second_post = create_random_post(&app.db, &followee.id).await;
+    let stranger_post = create_random_post(&app.db, &stranger.id).await;
+
+    let entries_count = rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+    // Rebuilding twice must not duplicate anything
+    let rebuilt_entries_count = rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    delete_post(&app.db, &first_post.id).await;
+    delete_post(&app.db, &second_post.id).await;
+    delete_post(&app.db, &stranger_post.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+    delete_user(&app.db, &stranger.id).await;
+
+    assert_eq!(entries_count, 2);
+    assert_eq!(rebuilt_entries_count, 2);
+}
+
+#[actix_web::test]
+async fn unfollow_should_remove_timeline_entries() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    let post = create_random_post(&app.db, &followee.id).await;
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/followers", &app.address, &followee.username))
+        .bearer_auth(get_user_token(&app.config, &viewer))
+        .send()
+        .await
+        .unwrap();
+
+    let entries_count = count_timeline_entries(&app.db, &viewer.id).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(entries_count, 0);
+}
+
+#[actix_web::test]
+async fn block_should_remove_timeline_entries() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    let post = create_random_post(&app.db, &followee.id).await;
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let resp = client.post(format!("{}/api/v1/users/{}/blocks/{}", &app.address, &followee.username, &viewer.username))
+        .bearer_auth(get_user_token(&app.config, &followee))
+        .send()
+        .await
+        .unwrap();
+
+    let entries_count = count_timeline_entries(&app.db, &viewer.id).await;
+
+    delete_post(&app.db, &post.id).await;
+    delete_blocks_and_mutes(&app.db, &viewer.id).await;
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(entries_count, 0);
+}
+
+#[actix_web::test]
+async fn deleting_post_should_remove_it_from_timelines() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (viewer, _p) = create_random_user(&app.db).await;
+    let (followee, _p) = create_random_user(&app.db).await;
+    follow_user(&app.db, &viewer.id, &followee.id).await;
+    let post = create_random_post(&app.db, &followee.id).await;
+    rebuild_timeline(&app.db, &viewer.id).await.unwrap();
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/posts/{}", &app.address, &followee.username, Uuid::from_slice(&post.id).unwrap()))
+        .bearer_auth(get_user_token(&app.config, &followee))
+        .send()
+        .await
+        .unwrap();
+
+    let entries_count = count_timeline_entries(&app.db, &viewer.id).await;
+
+    delete_follows(&app.db, &viewer.id).await;
+    delete_user(&app.db, &viewer.id).await;
+    delete_user(&app.db, &followee.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(entries_count, 0);
+}
+
+#[actix_web::test]
+async fn dropping_under_fan_out_limit_should_fan_out_posts() {
+    let app = utils::start_test_server().await;
+    let client = Client::new();
+
+    let (author, _p) = create_random_user(&app.db).await;
+    let (follower, _p) = create_random_user(&app.db).await;
+    let (leaving_follower, _p) = create_random_user(&app.db).await;
+
+    // One follower above the limit, posts are read from the posts table
+    set_followers_count(&app.db, &author.id, FAN_OUT_FOLLOWER_LIMIT as i32 - 1).await;
+    follow_user(&app.db, &follower.id, &author.id).await;
+    follow_user(&app.db, &leaving_follower.id, &author.id).await;
+    let post = create_random_post(&app.db, &author.id).await;
+    rebuild_timeline(&app.db, &follower.id).await.unwrap();
+    let entries_before = count_timeline_entries(&app.db, &follower.id).await;
+
+    let resp = client.delete(format!("{}/api/v1/users/{}/followers", &app.address, &author.username))
+        .bearer_auth(get_user_token(&app.config, &leaving_follower))
+        .send()
+        .await
+        .unwrap();
+
+    // The fan out happens in the background
+    let mut entries_after = 0;
+    for _ in 0..20 {
+        entries_after = count_timeline_entries(&app.db, &follower.id).await;
+        if entries_after > 0 {
+            break;
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    delete_post(&app.db, &post.id).await;
+    delete_follows(&app.db, &author.id).await;
+    delete_user(&app.db, &author.id).await;
+    delete_user(&app.db, &follower.id).await;
+    delete_user(&app.db, &leaving_follower.id).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(entries_before, 0);
+    assert_eq!(entries_after, 1);
+}
+
+// ---- END OF TIMELINE UNIT TESTS ----
@@ -1,13 +1,22 @@
+#![allow(dead_code)]
+
 use std::net::TcpListener;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
 use fake::Fake;
 use fake::faker::internet::en::{Password, Username, SafeEmail};
+use fake::faker::lorem::en::Sentence;
 use fake::faker::name::en::Name;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, Set};
+use sea_orm::sea_query::Expr;
 use uuid::Uuid;
 use insta::app::app;
-use insta::utils::password;
+use insta::auth::JwtTokenPayload;
+use insta::utils::{jwt, password};
 use insta::db;
-use insta::configuration::Settings;
+use insta::configuration::{Settings, StorageSettings};
+
+pub const TEST_MEDIA_BASE_URL: &str = "https://cdn.example.com/media";
 
 #[derive(Clone)]
 pub struct MyTestServer {
@@ -30,7 +39,12 @@ pub async fn start_test_server() -> MyTestServer {
     let port = listener.local_addr().unwrap().port();
     let address = format!("http://{}:{}", host, port);
 
-    let config = Settings::get_configuration();
+    let mut config = Settings::get_configuration();
+    // Uploads land in a scratch directory, served from a made up host
+    config.storage = Some(StorageSettings {
+        base_url: String::from(TEST_MEDIA_BASE_URL),
+        path: std::env::temp_dir().join("insta-test-media").to_string_lossy().into_owned(),
+    });
 
     let server = app(listener, config.clone())
         .await
@@ -63,13 +77,474 @@ pub async fn create_random_user(db: &DatabaseConnection) -> (entity::users::Mode
     (model, random_password)
 }
 
+// Notifications are written in the background and can still be arriving,
+// they go together with the user
 pub async fn delete_user(db: &DatabaseConnection, user_id: &[u8]) {
+    delete_notifications(db, user_id).await;
+
+    entity::media_uploads::Entity::delete_many()
+        .filter(entity::media_uploads::Column::UserId.eq(user_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete media uploads");
+
     entity::users::Entity::delete_by_id(Uuid::from_slice(user_id).unwrap())
         .exec(db)
         .await
         .expect("Failed to delete user");
 }
 
+pub fn get_user_token(config: &Settings, user: &entity::users::Model) -> String {
+    let token_payload = JwtTokenPayload {
+        aud: JwtTokenPayload::get_audience(),
+        exp: JwtTokenPayload::get_exp(),
+        id: Uuid::from_slice(&user.id).unwrap().to_string(),
+        email: user.email.clone(),
+        full_name: user.name.clone(),
+        username: user.username.clone(),
+        picture_url: user.picture_url.clone(),
+    };
+
+    jwt::sign(&token_payload, &config.jwt).expect("should sign jwt")
+}
+
+pub async fn create_random_post(db: &DatabaseConnection, user_id: &[u8]) -> entity::posts::Model {
+    let post = entity::posts::ActiveModel {
+        id: Set(Uuid::new_v4().into()),
+        user_id: Set(user_id.to_vec()),
+        description: Set(Sentence(3..10).fake()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to insert post");
+
+    entity::post_files::ActiveModel {
+        post_id: Set(post.id.clone()),
+        file_url: Set("https://bit.ly/3REd7XG".to_owned()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to insert post file");
+
+    post
+}
+
+pub async fn delete_post(db: &DatabaseConnection, post_id: &[u8]) {
+    entity::explore_entries::Entity::delete_many()
+        .filter(entity::explore_entries::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete explore entries");
+
+    entity::post_impressions::Entity::delete_many()
+        .filter(entity::post_impressions::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post impressions");
+
+    entity::post_viewers::Entity::delete_many()
+        .filter(entity::post_viewers::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post viewers");
+
+    entity::post_insights::Entity::delete_many()
+        .filter(entity::post_insights::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post insights");
+
+    entity::timeline_entries::Entity::delete_many()
+        .filter(entity::timeline_entries::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete timeline entries");
+
+    entity::post_likes::Entity::delete_many()
+        .filter(entity::post_likes::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post likes");
+
+    let comment_ids: Vec<i32> = entity::post_comments::Entity::find()
+        .filter(entity::post_comments::Column::PostId.eq(post_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to find post comments")
+        .into_iter()
+        .map(|comment| comment.id)
+        .collect();
+
+    entity::comment_likes::Entity::delete_many()
+        .filter(entity::comment_likes::Column::CommentId.is_in(comment_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete comment likes");
+
+    entity::mentions::Entity::delete_many()
+        .filter(entity::mentions::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete mentions");
+
+    entity::notifications::Entity::delete_many()
+        .filter(entity::notifications::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post notifications");
+
+    entity::post_comments::Entity::delete_many()
+        .filter(entity::post_comments::Column::PostId.eq(post_id.to_vec()))
+        .filter(entity::post_comments::Column::ParentId.is_not_null())
+        .exec(db)
+        .await
+        .expect("Failed to delete comment replies");
+
+    entity::post_comments::Entity::delete_many()
+        .filter(entity::post_comments::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post comments");
+
+    entity::post_hashtags::Entity::delete_many()
+        .filter(entity::post_hashtags::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post hashtags");
+
+    entity::favorites::Entity::delete_many()
+        .filter(entity::favorites::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete favorites");
+
+    let bookmark_ids: Vec<i32> = entity::bookmarks::Entity::find()
+        .filter(entity::bookmarks::Column::PostId.eq(post_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to find bookmarks")
+        .into_iter()
+        .map(|bookmark| bookmark.id)
+        .collect();
+
+    entity::bookmark_collection_items::Entity::delete_many()
+        .filter(entity::bookmark_collection_items::Column::BookmarkId.is_in(bookmark_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete bookmark collection items");
+
+    entity::bookmarks::Entity::delete_many()
+        .filter(entity::bookmarks::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete bookmarks");
+
+    let file_ids: Vec<i32> = entity::post_files::Entity::find()
+        .filter(entity::post_files::Column::PostId.eq(post_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to find post files")
+        .into_iter()
+        .map(|file| file.id)
+        .collect();
+
+    entity::post_file_tags::Entity::delete_many()
+        .filter(entity::post_file_tags::Column::PostFileId.is_in(file_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete post file tags");
+
+    entity::post_files::Entity::delete_many()
+        .filter(entity::post_files::Column::PostId.eq(post_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete post files");
+
+    entity::posts::Entity::delete_by_id(Uuid::from_slice(post_id).unwrap())
+        .exec(db)
+        .await
+        .expect("Failed to delete post");
+}
+
+pub async fn create_story(db: &DatabaseConnection, user_id: &[u8], created_at: DateTime<Utc>) -> entity::stories::Model {
+    entity::stories::ActiveModel {
+        id: Set(Uuid::new_v4().into()),
+        user_id: Set(user_id.to_vec()),
+        file_url: Set("https://bit.ly/3REd7XG".to_owned()),
+        created_at: Set(created_at),
+    }.insert(db).await.expect("Failed to insert story")
+}
+
+// Records `file_url` as uploaded by the user, writing the file is up to the test
+pub async fn record_upload(db: &DatabaseConnection, user_id: &[u8], file_url: &str) {
+    entity::media_uploads::ActiveModel {
+        user_id: Set(user_id.to_vec()),
+        file_url: Set(file_url.to_owned()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to insert media upload");
+}
+
+// A new upload of the user, for the endpoints that only take the user's own media
+pub async fn create_upload(db: &DatabaseConnection, user_id: &[u8]) -> String {
+    let file_url = format!("{}/{}.jpg", TEST_MEDIA_BASE_URL, Uuid::new_v4());
+    record_upload(db, user_id, &file_url).await;
+    file_url
+}
+
+// Deletes the user's stories and every story view made by or on them
+pub async fn delete_stories(db: &DatabaseConnection, user_id: &[u8]) {
+    let story_ids: Vec<Vec<u8>> = entity::stories::Entity::find()
+        .filter(entity::stories::Column::UserId.eq(user_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to get stories")
+        .into_iter()
+        .map(|story| story.id)
+        .collect();
+
+    entity::story_views::Entity::delete_many()
+        .filter(
+            entity::story_views::Column::ViewerId.eq(user_id.to_vec())
+                .or(entity::story_views::Column::StoryId.is_in(story_ids))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete story views");
+
+    entity::stories::Entity::delete_many()
+        .filter(entity::stories::Column::UserId.eq(user_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete stories");
+}
+
+pub async fn delete_highlights(db: &DatabaseConnection, user_id: &[u8]) {
+    let highlight_ids: Vec<Vec<u8>> = entity::highlights::Entity::find()
+        .filter(entity::highlights::Column::UserId.eq(user_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to get highlights")
+        .into_iter()
+        .map(|highlight| highlight.id)
+        .collect();
+
+    entity::highlight_items::Entity::delete_many()
+        .filter(entity::highlight_items::Column::HighlightId.is_in(highlight_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete highlight items");
+
+    entity::highlights::Entity::delete_many()
+        .filter(entity::highlights::Column::UserId.eq(user_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete highlights");
+}
+
+// Deletes the user's collections, the bookmarks themselves go with `delete_post`
+pub async fn delete_bookmark_collections(db: &DatabaseConnection, user_id: &[u8]) {
+    let collection_ids: Vec<Vec<u8>> = entity::bookmark_collections::Entity::find()
+        .filter(entity::bookmark_collections::Column::UserId.eq(user_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to get bookmark collections")
+        .into_iter()
+        .map(|collection| collection.id)
+        .collect();
+
+    entity::bookmark_collection_items::Entity::delete_many()
+        .filter(entity::bookmark_collection_items::Column::CollectionId.is_in(collection_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete bookmark collection items");
+
+    entity::bookmark_collections::Entity::delete_many()
+        .filter(entity::bookmark_collections::Column::UserId.eq(user_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to delete bookmark collections");
+}
+
+// Deletes the hashtag along with its follows, its posts have to be deleted first
+pub async fn delete_hashtag(db: &DatabaseConnection, name: &str) {
+    let Some(hashtag) = entity::hashtags::Entity::find()
+        .filter(entity::hashtags::Column::Name.eq(name))
+        .one(db)
+        .await
+        .expect("Failed to get hashtag") else {
+        return;
+    };
+
+    entity::hashtag_follows::Entity::delete_many()
+        .filter(entity::hashtag_follows::Column::HashtagId.eq(hashtag.id))
+        .exec(db)
+        .await
+        .expect("Failed to delete hashtag follows");
+
+    entity::hashtags::Entity::delete_by_id(hashtag.id)
+        .exec(db)
+        .await
+        .expect("Failed to delete hashtag");
+}
+
+// Deletes the notifications the user received or caused
+pub async fn delete_notifications(db: &DatabaseConnection, user_id: &[u8]) {
+    entity::notifications::Entity::delete_many()
+        .filter(
+            Condition::any()
+                .add(entity::notifications::Column::UserId.eq(user_id.to_vec()))
+                .add(entity::notifications::Column::ActorId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete notifications");
+}
+
+// Notifications are written in the background, waits until the user has the expected
+// number of notifications of the kind and returns the last count seen
+pub async fn wait_for_notifications(db: &DatabaseConnection, user_id: &[u8], kind: &str, expected: u64) -> u64 {
+    let mut notifications_count = 0;
+    for _ in 0..20 {
+        notifications_count = entity::notifications::Entity::find()
+            .filter(entity::notifications::Column::UserId.eq(user_id.to_vec()))
+            .filter(entity::notifications::Column::Kind.eq(kind))
+            .count(db)
+            .await
+            .expect("Failed to count notifications");
+        if notifications_count == expected {
+            break;
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    notifications_count
+}
+
+pub async fn follow_user(db: &DatabaseConnection, follower_id: &[u8], followee_id: &[u8]) {
+    entity::follows::ActiveModel {
+        follower_id: Set(follower_id.to_vec()),
+        followee_id: Set(followee_id.to_vec()),
+        ..Default::default()
+    }.insert(db).await.expect("Failed to insert follow");
+
+    entity::users::Entity::update_many()
+        .col_expr(entity::users::Column::FollowersCount, Expr::col(entity::users::Column::FollowersCount).add(1))
+        .filter(entity::users::Column::Id.eq(followee_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to update followers count");
+}
+
+// Stands in for the given number of followers, the fan out limit is too high to reach with real follows
+pub async fn set_followers_count(db: &DatabaseConnection, user_id: &[u8], followers_count: i32) {
+    entity::users::Entity::update_many()
+        .col_expr(entity::users::Column::FollowersCount, Expr::value(followers_count))
+        .filter(entity::users::Column::Id.eq(user_id.to_vec()))
+        .exec(db)
+        .await
+        .expect("Failed to set followers count");
+}
+
+pub async fn make_user_private(db: &DatabaseConnection, user: &entity::users::Model) {
+    let mut user: entity::users::ActiveModel = user.clone().into();
+    user.is_private = Set(true);
+    user.update(db).await.expect("Failed to make user private");
+}
+
+pub async fn delete_follows(db: &DatabaseConnection, user_id: &[u8]) {
+    entity::follows::Entity::delete_many()
+        .filter(
+            entity::follows::Column::FollowerId.eq(user_id.to_vec())
+                .or(entity::follows::Column::FolloweeId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete follows");
+
+    entity::follow_requests::Entity::delete_many()
+        .filter(
+            entity::follow_requests::Column::RequesterId.eq(user_id.to_vec())
+                .or(entity::follow_requests::Column::TargetId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete follow requests");
+
+    entity::timeline_entries::Entity::delete_many()
+        .filter(
+            entity::timeline_entries::Column::UserId.eq(user_id.to_vec())
+                .or(entity::timeline_entries::Column::AuthorId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete timeline entries");
+}
+
+pub async fn delete_blocks_and_mutes(db: &DatabaseConnection, user_id: &[u8]) {
+    entity::blocks::Entity::delete_many()
+        .filter(
+            entity::blocks::Column::BlockerId.eq(user_id.to_vec())
+                .or(entity::blocks::Column::BlockedId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete blocks");
+
+    entity::mutes::Entity::delete_many()
+        .filter(
+            entity::mutes::Column::MuterId.eq(user_id.to_vec())
+                .or(entity::mutes::Column::MutedId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete mutes");
+}
+
+pub async fn delete_suggestion_dismissals(db: &DatabaseConnection, user_id: &[u8]) {
+    entity::suggestion_dismissals::Entity::delete_many()
+        .filter(
+            entity::suggestion_dismissals::Column::UserId.eq(user_id.to_vec())
+                .or(entity::suggestion_dismissals::Column::DismissedId.eq(user_id.to_vec()))
+        )
+        .exec(db)
+        .await
+        .expect("Failed to delete suggestion dismissals");
+}
+
+// Deletes every conversation the user created or takes part in, with their messages
+pub async fn delete_conversations(db: &DatabaseConnection, user_id: &[u8]) {
+    let mut conversation_ids: Vec<Vec<u8>> = entity::conversation_participants::Entity::find()
+        .filter(entity::conversation_participants::Column::UserId.eq(user_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to get conversation participants")
+        .into_iter()
+        .map(|participant| participant.conversation_id)
+        .collect();
+
+    entity::conversations::Entity::find()
+        .filter(entity::conversations::Column::CreatorId.eq(user_id.to_vec()))
+        .all(db)
+        .await
+        .expect("Failed to get conversations")
+        .into_iter()
+        .for_each(|conversation| conversation_ids.push(conversation.id));
+
+    entity::messages::Entity::delete_many()
+        .filter(entity::messages::Column::ConversationId.is_in(conversation_ids.clone()))
+        .exec(db)
+        .await
+        .expect("Failed to delete messages");
+
+    entity::conversation_participants::Entity::delete_many()
+        .filter(entity::conversation_participants::Column::ConversationId.is_in(conversation_ids.clone()))
+        .exec(db)
+        .await
+        .expect("Failed to delete conversation participants");
+
+    entity::conversations::Entity::delete_many()
+        .filter(entity::conversations::Column::Id.is_in(conversation_ids))
+        .exec(db)
+        .await
+        .expect("Failed to delete conversations");
+}
+
 pub async fn parse_response_body<T>(resp: reqwest::Response) -> T
     where
         T: serde::de::DeserializeOwned,